@@ -5,7 +5,7 @@
 //!
 //! The API trait
 #![allow(clippy::type_complexity)]
-use std::{future::Future, ops::Deref, pin::Pin};
+use std::{future::Future, ops::Deref};
 
 use async_stream::stream;
 use bytes::Bytes;
@@ -13,7 +13,7 @@ use freedom_config::Config;
 use freedom_models::{
     account::Account,
     band::Band,
-    pagination::Paginated,
+    pagination::{Page, Paginated},
     satellite::Satellite,
     satellite_configuration::SatelliteConfiguration,
     site::Site,
@@ -21,30 +21,45 @@ use freedom_models::{
     user::User,
     utils::Embedded,
 };
-use reqwest::{Response, StatusCode};
+use reqwest::{
+    header::{CONTENT_RANGE, LOCATION},
+    Method, Response, StatusCode,
+};
 use serde::de::DeserializeOwned;
 use serde_json::Value as JsonValue;
-use time::{format_description::well_known::Iso8601, OffsetDateTime};
 use url::Url;
 
-use futures_core::Stream;
+use crate::{
+    error::Error,
+    ids::{
+        AccountId, BandId, OverrideId, SatelliteConfigurationId, SatelliteId, SiteConfigurationId,
+        SiteId, TaskId, TaskRequestId, UserId,
+    },
+    window::TimeWindow,
+};
 
-use crate::error::Error;
+pub use self::pagination::PaginatedStream;
+pub use self::query::{RequestQuery, TaskQuery};
 
+pub(crate) mod download;
+pub(crate) mod pagination;
 pub(crate) mod post;
+pub(crate) mod query;
+pub(crate) mod update;
+pub(crate) mod watch;
 
 /// A super trait containing all the requirements for Freedom API Values
 pub trait Value: std::fmt::Debug + DeserializeOwned + Clone + Send + Sync {}
 
 impl<T> Value for T where T: std::fmt::Debug + DeserializeOwned + Clone + Send + Sync {}
 
-trait PaginatedErr<'a, T> {
+pub(crate) trait PaginatedErr<'a, T> {
     fn once_err(self) -> PaginatedStream<'a, T>;
 }
 
 impl<'a, T: 'a + Send + Sync> PaginatedErr<'a, T> for Error {
     fn once_err(self) -> PaginatedStream<'a, T> {
-        Box::pin(async_stream::stream! { yield Err(self); })
+        PaginatedStream::from_stream(async_stream::stream! { yield Err(self); })
     }
 }
 
@@ -132,11 +147,6 @@ impl<T> Inner<T> {
     }
 }
 
-/// A stream of paginated results from freedom.
-///
-/// Each item in the stream is a result, since one or more items may fail to be serialized
-pub type PaginatedStream<'a, T> = Pin<Box<dyn Stream<Item = Result<T, Error>> + 'a + Send + Sync>>;
-
 /// The primary trait for interfacing with the Freedom API
 pub trait Api: Send + Sync {
     /// The [`Api`] supports implementors with different so-called "container" types.
@@ -154,9 +164,9 @@ pub trait Api: Send + Sync {
         T: Value,
     {
         async move {
-            let (body, status) = self.get(url).await?;
+            let (body, status) = self.get(url.clone()).await?;
 
-            error_on_non_success(&status)?;
+            error_on_non_success(&Method::GET, &url, &status, &body)?;
 
             let utf8_str = String::from_utf8_lossy(&body);
             serde_json::from_str(&utf8_str).map_err(From::from)
@@ -172,48 +182,101 @@ pub trait Api: Send + Sync {
         url: Url,
     ) -> impl Future<Output = Result<(Bytes, StatusCode), Error>> + Send + Sync;
 
+    /// Creates a get request at the provided absolute URI for the client's environment, returning
+    /// the response without buffering its body, so that it may be streamed.
+    ///
+    /// When `offset` is non-zero, only the bytes from the offset onward are requested, with an HTTP
+    /// `Range` header. Servers which honor the header respond with `206 Partial Content`, those
+    /// which do not respond with `200 OK` and the entire body.
+    ///
+    /// The default implementation buffers the body through [`get`](Self::get), and emulates the
+    /// `Range` header by slicing it.
+    fn get_stream(
+        &self,
+        url: Url,
+        offset: u64,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        async move {
+            let (body, status) = self.get(url).await?;
+            let len = body.len() as u64;
+
+            let response = match offset {
+                _ if offset == 0 || !status.is_success() => {
+                    http::Response::builder().status(status).body(body)
+                }
+                _ if offset >= len => http::Response::builder()
+                    .status(StatusCode::RANGE_NOT_SATISFIABLE)
+                    .header(CONTENT_RANGE, format!("bytes */{len}"))
+                    .body(Bytes::new()),
+                _ => http::Response::builder()
+                    .status(StatusCode::PARTIAL_CONTENT)
+                    .header(CONTENT_RANGE, format!("bytes {offset}-{}/{len}", len - 1))
+                    .body(body.slice(offset as usize..)),
+            };
+
+            Ok(Response::from(
+                response.expect("Invalid response construction"),
+            ))
+        }
+    }
+
     /// Creates a stream of items from a paginated endpoint.
     ///
     /// The stream is produced as a collection of `Result<T>`. This is so that if any one item fails
     /// deserialization, it is added to the stream of items as an error rather than causing the
     /// entire stream to result in an Error.
     ///
-    /// # Pinning
+    /// The page size, starting page, and sort order may be chosen with
+    /// [`PaginatedStream::with_options`], which also applies to every method of this trait which
+    /// returns a [`PaginatedStream`]. The totals reported in the page metadata are available from
+    /// the stream once the first page has arrived.
     ///
-    /// For convenience the stream is pinned on the heap via [`Box::pin`](https://doc.rust-lang.org/std/boxed/struct.Box.html#method.pin).
-    /// This allows us to treat the returned stream more like any other object, without requiring
-    /// the end user to manually  pin the result on the stack. This comes with a slight performance
-    /// penalty (it requires an allocation), however this will be negligible given the latency of
-    /// the responses. For more information on pinning in rust refer to the [pinning chapter](https://rust-lang.github.io/async-book/04_pinning/01_chapter.html)
-    /// of the async book.
+    /// Endpoints which return every item at once, without page metadata, are streamed as a single
+    /// page.
     fn get_paginated<T>(&self, head_url: Url) -> PaginatedStream<'_, Self::Container<T>>
     where
         T: 'static + Value + Send + Sync,
     {
         let base = self.config().environment().freedom_entrypoint();
-        let mut current_url = head_url; // Not necessary but makes control flow more obvious
-        Box::pin(stream! {
-            loop {
-                // Get the results for the current page.
-                let pag = self.get_json_map::<Paginated<JsonValue>>(current_url).await?;
-                for item in pag.items {
-                    let i = serde_json::from_value::<Self::Container<T>>(item).map_err(From::from);
-                    yield i;
-                }
-                if let Some(link) = pag.links.get("next") {
-                    // Update the URL to the next page.
-                    current_url = match link.has_host() {
-                        true => link.to_owned(),
-                        false => {
-                            base.clone()
-                                .join(link.as_str())
-                                .map_err(|e| crate::error::Error::pag_item(e.to_string()))?
+        PaginatedStream::new(head_url, move |mut current_url, page| {
+            Box::pin(stream! {
+                loop {
+                    // Get the results for the current page.
+                    let body = self.get_json_map::<JsonValue>(current_url).await?;
+                    // Some search endpoints return every item at once, without page metadata
+                    let pag = match body.get("page") {
+                        Some(_) => serde_json::from_value::<Paginated<JsonValue>>(body)?,
+                        None => {
+                            let embedded = serde_json::from_value::<Embedded<Vec<JsonValue>>>(body)?;
+                            let total = embedded.items.len() as u32;
+                            Paginated {
+                                items: embedded.items,
+                                links: embedded.links,
+                                page: Page { size: total, total_elements: total, total_pages: 1, number: 0 },
+                            }
                         }
                     };
-                } else {
-                    break;
+                    // Only the first page is recorded, the others are ignored
+                    let _ = page.set(pag.page);
+                    for item in pag.items {
+                        let i = serde_json::from_value::<Self::Container<T>>(item).map_err(From::from);
+                        yield i;
+                    }
+                    if let Some(link) = pag.links.get("next") {
+                        // Update the URL to the next page.
+                        current_url = match link.has_host() {
+                            true => link.to_owned(),
+                            false => {
+                                base.clone()
+                                    .join(link.as_str())
+                                    .map_err(|e| crate::error::Error::pag_item(e.to_string()))?
+                            }
+                        };
+                    } else {
+                        break;
+                    }
                 }
-            }
+            })
         })
     }
 
@@ -248,7 +311,11 @@ pub trait Api: Send + Sync {
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    fn delete_band_details(&self, id: i32) -> impl Future<Output = Result<Response, Error>> + Send {
+    fn delete_band_details(
+        &self,
+        id: impl Into<BandId>,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("satellite_bands/{id}"));
             self.delete(uri).await
@@ -270,8 +337,9 @@ pub trait Api: Send + Sync {
     /// ```
     fn delete_satellite_configuration(
         &self,
-        id: i32,
+        id: impl Into<SatelliteConfigurationId>,
     ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("satellite_configurations/{id}"));
             self.delete(uri).await
@@ -291,7 +359,11 @@ pub trait Api: Send + Sync {
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    fn delete_satellite(&self, id: i32) -> impl Future<Output = Result<Response, Error>> + Send {
+    fn delete_satellite(
+        &self,
+        id: impl Into<SatelliteId>,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("satellites/{id}"));
             self.delete(uri).await
@@ -311,7 +383,11 @@ pub trait Api: Send + Sync {
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    fn delete_override(&self, id: i32) -> impl Future<Output = Result<Response, Error>> + Send {
+    fn delete_override(
+        &self,
+        id: impl Into<OverrideId>,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("overrides/{id}"));
             self.delete(uri).await
@@ -331,7 +407,11 @@ pub trait Api: Send + Sync {
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    fn delete_user(&self, id: i32) -> impl Future<Output = Result<Response, Error>> + Send {
+    fn delete_user(
+        &self,
+        id: impl Into<UserId>,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("users/{id}"));
             self.delete(uri).await
@@ -351,7 +431,11 @@ pub trait Api: Send + Sync {
     /// # Ok::<_, Box<dyn std::error::Error>>(())
     /// # });
     /// ```
-    fn delete_task_request(&self, id: i32) -> impl Future<Output = Result<Response, Error>> + Send {
+    fn delete_task_request(
+        &self,
+        id: impl Into<TaskRequestId>,
+    ) -> impl Future<Output = Result<Response, Error>> + Send {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("requests/{id}"));
             self.delete(uri).await
@@ -369,9 +453,9 @@ pub trait Api: Send + Sync {
         T: Value,
     {
         async move {
-            let resp = self.post(url, msg).await?;
+            let resp = self.post(url.clone(), msg).await?;
 
-            resp.json::<T>().await.map_err(From::from)
+            deserialize_written(self, &Method::POST, &url, resp).await
         }
     }
 
@@ -384,6 +468,57 @@ pub trait Api: Send + Sync {
     where
         S: serde::Serialize + Send + Sync;
 
+    /// Lower level method, not intended for direct use
+    ///
+    /// The default implementation fails with [`Error::UnsupportedMethod`], for clients which
+    /// predate updates.
+    fn put<S>(
+        &self,
+        url: Url,
+        msg: S,
+    ) -> impl Future<Output = Result<Response, Error>> + Send + Sync
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let _ = (url, msg);
+
+        std::future::ready(Err(Error::UnsupportedMethod(Method::PUT.to_string())))
+    }
+
+    /// Lower level method, not intended for direct use
+    fn patch_deserialize<S, T>(
+        &self,
+        url: Url,
+        msg: S,
+    ) -> impl Future<Output = Result<T, Error>> + Send + Sync
+    where
+        S: serde::Serialize + Send + Sync,
+        T: Value,
+    {
+        async move {
+            let resp = self.patch(url.clone(), msg).await?;
+
+            deserialize_written(self, &Method::PATCH, &url, resp).await
+        }
+    }
+
+    /// Lower level method, not intended for direct use
+    ///
+    /// The default implementation fails with [`Error::UnsupportedMethod`], for clients which
+    /// predate updates.
+    fn patch<S>(
+        &self,
+        url: Url,
+        msg: S,
+    ) -> impl Future<Output = Result<Response, Error>> + Send + Sync
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let _ = (url, msg);
+
+        std::future::ready(Err(Error::UnsupportedMethod(Method::PATCH.to_string())))
+    }
+
     /// Produces a single [`Account`](freedom_models::account::Account) matching the provided ID.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
@@ -406,7 +541,7 @@ pub trait Api: Send + Sync {
     ) -> impl Future<Output = Result<Self::Container<Account>, Error>> + Send + Sync {
         async move {
             let mut uri = self.path_to_url("accounts/search/findOneByName");
-            uri.set_query(Some(&format!("name={account_name}")));
+            uri.query_pairs_mut().append_pair("name", account_name);
             self.get_json_map(uri).await
         }
     }
@@ -428,27 +563,63 @@ pub trait Api: Send + Sync {
     /// ```
     fn get_file_by_task_id_and_name(
         &self,
-        task_id: i32,
+        task_id: impl Into<TaskId>,
         file_name: &str,
     ) -> impl Future<Output = Result<Bytes, Error>> + Send + Sync {
+        let task_id = task_id.into();
         async move {
             let path = format!("downloads/{}/{}", task_id, file_name);
             let uri = self.path_to_url(path);
 
-            let (data, status) = self.get(uri).await?;
-            error_on_non_success(&status)?;
+            let (data, status) = self.get(uri.clone()).await?;
+            error_on_non_success(&Method::GET, &uri, &status, &data)?;
 
             Ok(data)
         }
     }
 
+    /// Produces a [`Download`](download::Download) of the file with the provided name, which was
+    /// produced by the task with the provided ID.
+    ///
+    /// Unlike [`Self::get_file_by_task_id_and_name`], the file is streamed to its destination
+    /// rather than buffered in memory, and interrupted transfers are resumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use freedom_api::prelude::*;
+    /// # tokio_test::block_on(async {
+    /// let client = Client::from_env()?;
+    ///
+    /// let size = client
+    ///     .download_file_by_task_id_and_name(42, "data.bin")
+    ///     .on_progress(|progress| println!("{} bytes", progress.downloaded))
+    ///     .to_path("data.bin")
+    ///     .await?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn download_file_by_task_id_and_name(
+        &self,
+        task_id: impl Into<TaskId>,
+        file_name: &str,
+    ) -> download::Download<'_, Self>
+    where
+        Self: Sized,
+    {
+        let uri = self.path_to_url(format!("downloads/{}/{}", task_id.into(), file_name));
+
+        download::new(self, uri)
+    }
+
     /// Produces a single [`Account`](freedom_models::account::Account) matching the provided ID.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
     fn get_account_by_id(
         &self,
-        account_id: i32,
+        account_id: impl Into<AccountId>,
     ) -> impl Future<Output = Result<Self::Container<Account>, Error>> + Send + Sync {
+        let account_id = account_id.into();
         async move {
             let uri = self.path_to_url(format!("accounts/{account_id}"));
             self.get_json_map(uri).await
@@ -478,8 +649,9 @@ pub trait Api: Send + Sync {
     /// See [`get`](Self::get) documentation for more details about the process and return type
     fn get_satellite_band_by_id(
         &self,
-        satellite_band_id: i32,
+        satellite_band_id: impl Into<BandId>,
     ) -> impl Future<Output = Result<Self::Container<Band>, Error>> + Send + Sync {
+        let satellite_band_id = satellite_band_id.into();
         async move {
             let uri = self.path_to_url(format!("satellite_bands/{satellite_band_id}"));
             self.get_json_map(uri).await
@@ -495,7 +667,8 @@ pub trait Api: Send + Sync {
     ) -> impl Future<Output = Result<Self::Container<Band>, Error>> + Send + Sync {
         async move {
             let mut uri = self.path_to_url("satellite_bands/search/findOneByName");
-            uri.set_query(Some(&format!("name={satellite_band_name}")));
+            uri.query_pairs_mut()
+                .append_pair("name", satellite_band_name);
             self.get_json_map(uri).await
         }
     }
@@ -509,7 +682,8 @@ pub trait Api: Send + Sync {
         account_name: &str,
     ) -> PaginatedStream<'_, Self::Container<Band>> {
         let mut uri = self.path_to_url("satellite_bands/search/findAllByAccountName");
-        uri.set_query(Some(&format!("accountName={account_name}")));
+        uri.query_pairs_mut()
+            .append_pair("accountName", account_name);
 
         self.get_paginated(uri)
     }
@@ -524,7 +698,8 @@ pub trait Api: Send + Sync {
         account_name: &str,
     ) -> PaginatedStream<'_, Self::Container<SatelliteConfiguration>> {
         let mut uri = self.path_to_url("satellite_configurations/search/findAllByAccountName");
-        uri.set_query(Some(&format!("accountName={account_name}")));
+        uri.query_pairs_mut()
+            .append_pair("accountName", account_name);
 
         self.get_paginated(uri)
     }
@@ -544,9 +719,10 @@ pub trait Api: Send + Sync {
     /// Produces a single satellite configuration matching the provided satellite configuration ID
     fn get_satellite_configuration_by_id(
         &self,
-        satellite_configuration_id: i32,
+        satellite_configuration_id: impl Into<SatelliteConfigurationId>,
     ) -> impl Future<Output = Result<Self::Container<SatelliteConfiguration>, Error>> + Send + Sync
     {
+        let satellite_configuration_id = satellite_configuration_id.into();
         async move {
             let uri = self.path_to_url(format!(
                 "satellite_configurations/{satellite_configuration_id}"
@@ -564,7 +740,8 @@ pub trait Api: Send + Sync {
     {
         async move {
             let mut uri = self.path_to_url("satellite_configurations/search/findOneByName");
-            uri.set_query(Some(&format!("name={satellite_configuration_name}")));
+            uri.query_pairs_mut()
+                .append_pair("name", satellite_configuration_name);
 
             self.get_json_map(uri).await
         }
@@ -584,8 +761,9 @@ pub trait Api: Send + Sync {
     /// See [`get`](Self::get) documentation for more details about the process and return type
     fn get_site_by_id(
         &self,
-        id: i32,
+        id: impl Into<SiteId>,
     ) -> impl Future<Output = Result<Self::Container<Site>, Error>> + Send + Sync {
+        let id = id.into();
         async move {
             let uri = self.path_to_url(format!("sites/{id}"));
             self.get_json_map(uri).await
@@ -601,8 +779,7 @@ pub trait Api: Send + Sync {
     ) -> impl Future<Output = Result<Self::Container<Site>, Error>> + Send + Sync {
         async move {
             let mut uri = self.path_to_url("sites/search/findOneByName");
-            let query = format!("name={}", name.as_ref());
-            uri.set_query(Some(&query));
+            uri.query_pairs_mut().append_pair("name", name.as_ref());
 
             self.get_json_map(uri).await
         }
@@ -613,8 +790,9 @@ pub trait Api: Send + Sync {
     /// See [`get`](Self::get) documentation for more details about the process and return type
     fn get_request_by_id(
         &self,
-        task_request_id: i32,
+        task_request_id: impl Into<TaskRequestId>,
     ) -> impl Future<Output = Result<Self::Container<TaskRequest>, Error>> + Send + Sync {
+        let task_request_id = task_request_id.into();
         async move {
             let uri = self.path_to_url(format!("requests/{task_request_id}"));
 
@@ -622,6 +800,21 @@ pub trait Api: Send + Sync {
         }
     }
 
+    /// Produces a [`RequestWatch`](watch::RequestWatch), which polls the [`TaskRequest`] with the
+    /// provided ID and streams the changes of its status, until it reaches a terminal status.
+    ///
+    /// See [`RequestWatch`](watch::RequestWatch) documentation for more details about the events
+    /// produced
+    fn watch_request(
+        &self,
+        task_request_id: impl Into<TaskRequestId>,
+    ) -> watch::RequestWatch<'_, Self>
+    where
+        Self: Sized,
+    {
+        watch::new(self, task_request_id.into())
+    }
+
     /// Produces a paginated stream of [`TaskRequest`] objects.
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
@@ -633,23 +826,38 @@ pub trait Api: Send + Sync {
         }
     }
 
+    /// Produces a paginated stream of [`TaskRequest`] objects matching the provided query.
+    ///
+    /// The search endpoint is picked from the criteria set on the query. If Freedom has no search
+    /// endpoint for the combination of criteria, [`Error::UnsupportedQuery`] is returned before
+    /// any request is made.
+    ///
+    /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
+    /// and return type
+    fn get_requests_matching(
+        &self,
+        query: &RequestQuery,
+    ) -> Result<PaginatedStream<'_, Self::Container<TaskRequest>>, Error> {
+        let uri = query.url(&self.config().environment().freedom_entrypoint())?;
+        Ok(self.get_paginated(uri))
+    }
+
     /// Produces a vector of [`TaskRequest`] items, representing all the task requests matching the
     /// target time overlapping with the provided time range.
+    #[deprecated(note = "Use `get_requests_matching` with `RequestQuery::target_date_between`")]
     fn get_requests_by_target_date_between(
         &self,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<TaskRequest>>, Error>> + Send + Sync {
         async move {
             let mut uri = self.path_to_url("requests/search/findAllByTargetDateBetween");
 
-            uri.set_query(Some(&format!(
-                "start={}&end={}",
-                start.format(&Iso8601::DEFAULT).unwrap(),
-                end.format(&Iso8601::DEFAULT).unwrap(),
-            )));
+            window.append_to(&mut uri);
 
-            self.get_json_map(uri).await
+            Ok(self
+                .get_json_map::<Embedded<Self::Container<Vec<TaskRequest>>>>(uri)
+                .await?
+                .items)
         }
     }
 
@@ -659,23 +867,22 @@ pub trait Api: Send + Sync {
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
     /// and return type
+    #[deprecated(
+        note = "Use `get_requests_matching` with `RequestQuery::account_url` and `RequestQuery::target_date_between`"
+    )]
     fn get_requests_by_account_and_target_date_between<T>(
         &self,
         account_uri: T,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> PaginatedStream<'_, Self::Container<TaskRequest>>
     where
         T: AsRef<str> + Send + Sync,
     {
         let mut uri = self.path_to_url("requests/search/findAllByAccountAndTargetDateBetween");
 
-        uri.set_query(Some(&format!(
-            "account={}&start={}&end={}",
-            account_uri.as_ref(),
-            start.format(&Iso8601::DEFAULT).unwrap(),
-            end.format(&Iso8601::DEFAULT).unwrap(),
-        )));
+        uri.query_pairs_mut()
+            .append_pair("account", account_uri.as_ref());
+        window.append_to(&mut uri);
 
         self.get_paginated(uri)
     }
@@ -694,7 +901,7 @@ pub trait Api: Send + Sync {
     }
 
     /// Produces a paginated stream of [`TaskRequest`]
-    /// objects whose satellite configuration matches that of the configuration at the
+    /// objects whose site configuration matches that of the configuration at the
     /// `configuration_uri` endpoint.
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
@@ -702,6 +909,7 @@ pub trait Api: Send + Sync {
     ///
     /// # Note
     /// The results are ordered by the creation time of the task request
+    #[deprecated(note = "Use `get_requests_matching` with `RequestQuery::configuration_url`")]
     fn get_requests_by_configuration<T>(
         &self,
         configuration_uri: T,
@@ -711,25 +919,25 @@ pub trait Api: Send + Sync {
     {
         let mut uri = self.path_to_url("requests/search/findAllByConfigurationOrderByCreatedAsc");
 
-        uri.set_query(Some(&format!(
-            "configuration={}",
-            configuration_uri.as_ref()
-        )));
+        uri.query_pairs_mut()
+            .append_pair("configuration", configuration_uri.as_ref());
 
         self.get_paginated::<TaskRequest>(uri)
     }
 
     /// Produces a vector of [`TaskRequest`] items, representing all the task requests which match
-    /// the provided configuration, whose satellite name matches one of the names provided as part
+    /// the provided site configuration, whose satellite name matches one of the names provided as part
     /// of `satellite_name`, and which overlaps the provided time range.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
+    #[deprecated(
+        note = "Use `get_requests_matching` with `RequestQuery::configuration_url`, `RequestQuery::satellite_names`, and `RequestQuery::target_date_between`"
+    )]
     fn get_requests_by_configuration_and_satellite_names_and_target_date_between<T, I, S>(
         &self,
         configuration_uri: T,
         satellites: I,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<TaskRequest>>, Error>> + Send + Sync
     where
         T: AsRef<str> + Send + Sync,
@@ -742,13 +950,10 @@ pub trait Api: Send + Sync {
                 "requests/search/findAllByConfigurationAndSatelliteNamesAndTargetDateBetween",
             );
 
-            uri.set_query(Some(&format!(
-                "configuration={}&satelliteNames={}&start={}&end={}",
-                configuration_uri.as_ref(),
-                satellites_string,
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?,
-            )));
+            uri.query_pairs_mut()
+                .append_pair("configuration", configuration_uri.as_ref())
+                .append_pair("satelliteNames", &satellites_string);
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<TaskRequest>>>>(uri)
@@ -758,16 +963,18 @@ pub trait Api: Send + Sync {
     }
 
     /// Produces a vector of [`TaskRequest`] items, representing all the task requests matching the
-    /// configuration at the provided URI and whose target time overlaps with the provided time
+    /// site configuration at the provided URI and whose target time overlaps with the provided time
     /// range.
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
     /// and return type
+    #[deprecated(
+        note = "Use `get_requests_matching` with `RequestQuery::configuration_url` and `RequestQuery::target_date_between`"
+    )]
     fn get_requests_by_configuration_and_target_date_between<T>(
         &self,
         configuration_uri: T,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<TaskRequest>>, Error>> + Send + Sync
     where
         T: AsRef<str> + Send + Sync,
@@ -775,12 +982,9 @@ pub trait Api: Send + Sync {
         async move {
             let mut uri =
                 self.path_to_url("requests/search/findAllByConfigurationAndTargetDateBetween");
-            uri.set_query(Some(&format!(
-                "configuration={}&start={}&end={}",
-                configuration_uri.as_ref(),
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?,
-            )));
+            uri.query_pairs_mut()
+                .append_pair("configuration", configuration_uri.as_ref());
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<TaskRequest>>>>(uri)
@@ -800,13 +1004,14 @@ pub trait Api: Send + Sync {
     ) -> impl Future<Output = Result<Self::Container<Vec<TaskRequest>>, Error>> + Send + Sync
     where
         I: IntoIterator<Item = S> + Send + Sync,
-        S: AsRef<str> + Send + Sync,
+        S: Into<TaskRequestId> + Send + Sync,
     {
         async move {
+            let ids = ids.into_iter().map(|id| id.into().to_string());
             let ids_string = crate::utils::list_to_string(ids);
             let mut uri = self.path_to_url("requests/search/findAllByIds");
 
-            uri.set_query(Some(&format!("ids={}", ids_string)));
+            uri.query_pairs_mut().append_pair("ids", &ids_string);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<TaskRequest>>>>(uri)
@@ -820,18 +1025,14 @@ pub trait Api: Send + Sync {
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
     /// and return type
+    #[deprecated(note = "Use `get_requests_matching` with `RequestQuery::overlapping_public`")]
     fn get_requests_by_overlapping_public(
         &self,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> PaginatedStream<'_, Self::Container<TaskRequest>> {
         let mut uri = self.path_to_url("requests/search/findAllByOverlappingPublic");
 
-        uri.set_query(Some(&format!(
-            "start={}&end={}",
-            start.format(&Iso8601::DEFAULT).unwrap(),
-            end.format(&Iso8601::DEFAULT).unwrap(),
-        )));
+        window.append_to(&mut uri);
 
         self.get_paginated(uri)
     }
@@ -841,6 +1042,7 @@ pub trait Api: Send + Sync {
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
     /// and return type
+    #[deprecated(note = "Use `get_requests_matching` with `RequestQuery::satellite_name`")]
     fn get_requests_by_satellite_name<T>(
         &self,
         satellite_name: T,
@@ -850,7 +1052,8 @@ pub trait Api: Send + Sync {
     {
         let mut uri = self.path_to_url("requests/search/findBySatelliteName");
 
-        uri.set_query(Some(&format!("name={}", satellite_name.as_ref())));
+        uri.query_pairs_mut()
+            .append_pair("name", satellite_name.as_ref());
 
         self.get_paginated(uri)
     }
@@ -860,11 +1063,13 @@ pub trait Api: Send + Sync {
     /// time range.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
+    #[deprecated(
+        note = "Use `get_requests_matching` with `RequestQuery::satellite_name` and `RequestQuery::target_date_between`"
+    )]
     fn get_requests_by_satellite_name_and_target_date_between<T>(
         &self,
         satellite_name: T,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<TaskRequest>>, Error>> + Send + Sync
     where
         T: AsRef<str> + Send + Sync,
@@ -873,12 +1078,9 @@ pub trait Api: Send + Sync {
             let mut uri =
                 self.path_to_url("requests/search/findAllBySatelliteNameAndTargetDateBetween");
 
-            uri.set_query(Some(&format!(
-                "name={}&start={}&end={}",
-                satellite_name.as_ref(),
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?
-            )));
+            uri.query_pairs_mut()
+                .append_pair("name", satellite_name.as_ref());
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<TaskRequest>>>>(uri)
@@ -892,6 +1094,7 @@ pub trait Api: Send + Sync {
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
     /// and return type
+    #[deprecated(note = "Use `get_requests_matching` with `RequestQuery::status`")]
     fn get_requests_by_status<T>(
         &self,
         status: T,
@@ -903,7 +1106,7 @@ pub trait Api: Send + Sync {
         let status: TaskStatusType = status.try_into()?;
         let mut uri = self.path_to_url("requests/search/findByStatus");
 
-        uri.set_query(Some(&format!("status={}", status.as_ref())));
+        uri.query_pairs_mut().append_pair("status", status.as_ref());
 
         Ok(self.get_paginated(uri))
     }
@@ -913,12 +1116,14 @@ pub trait Api: Send + Sync {
     ///
     /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
     /// and return type
+    #[deprecated(
+        note = "Use `get_requests_matching` with `RequestQuery::status`, `RequestQuery::account_url`, and `RequestQuery::target_date_between`"
+    )]
     fn get_requests_by_status_and_account_and_target_date_between<T, U>(
         &self,
         status: T,
         account_uri: U,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> PaginatedStream<'_, Self::Container<TaskRequest>>
     where
         T: AsRef<str> + Send + Sync,
@@ -927,13 +1132,10 @@ pub trait Api: Send + Sync {
         let mut uri =
             self.path_to_url("requests/search/findAllByStatusAndAccountAndTargetDateBetween");
 
-        uri.set_query(Some(&format!(
-            "status={}&satelliteNames={}&start={}&end={}",
-            status.as_ref(),
-            account_uri.as_ref(),
-            start.format(&Iso8601::DEFAULT).unwrap(),
-            end.format(&Iso8601::DEFAULT).unwrap()
-        )));
+        uri.query_pairs_mut()
+            .append_pair("status", status.as_ref())
+            .append_pair("account", account_uri.as_ref());
+        window.append_to(&mut uri);
 
         self.get_paginated(uri)
     }
@@ -942,11 +1144,13 @@ pub trait Api: Send + Sync {
     /// provided type, overlap with the provided time range.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
+    #[deprecated(
+        note = "Use `get_requests_matching` with `RequestQuery::task_type` and `RequestQuery::target_date_between`"
+    )]
     fn get_requests_by_type_and_target_date_between<T>(
         &self,
         typ: T,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<TaskRequest>>, Error>> + Send + Sync
     where
         T: TryInto<TaskType> + Send + Sync,
@@ -956,12 +1160,8 @@ pub trait Api: Send + Sync {
             let typ: TaskType = typ.try_into()?;
             let mut uri = self.path_to_url("requests/search/findAllByTypeAndTargetDateBetween");
 
-            uri.set_query(Some(&format!(
-                "type={}&start={}&end={}",
-                typ.as_ref(),
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?
-            )));
+            uri.query_pairs_mut().append_pair("type", typ.as_ref());
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<TaskRequest>>>>(uri)
@@ -1017,8 +1217,9 @@ pub trait Api: Send + Sync {
     /// Produces single satellite object matching the provided satellite ID
     fn get_satellite_by_id(
         &self,
-        satellite_id: i32,
+        satellite_id: impl Into<SatelliteId>,
     ) -> impl Future<Output = Result<Self::Container<Satellite>, Error>> + Send + Sync {
+        let satellite_id = satellite_id.into();
         async move {
             let uri = self.path_to_url(format!("satellites/{}", satellite_id));
 
@@ -1033,7 +1234,7 @@ pub trait Api: Send + Sync {
     ) -> impl Future<Output = Result<Self::Container<Satellite>, Error>> + Send + Sync {
         async move {
             let mut uri = self.path_to_url("satellites/findOneByName");
-            uri.set_query(Some(&format!("name={satellite_name}")));
+            uri.query_pairs_mut().append_pair("name", satellite_name);
 
             self.get_json_map(uri).await
         }
@@ -1044,8 +1245,9 @@ pub trait Api: Send + Sync {
     /// See [`get`](Self::get) documentation for more details about the process and return type
     fn get_task_by_id(
         &self,
-        task_id: i32,
+        task_id: impl Into<TaskId>,
     ) -> impl Future<Output = Result<Self::Container<Task>, Error>> + Send + Sync {
+        let task_id = task_id.into();
         async move {
             let uri = self.path_to_url(format!("tasks/{}", task_id));
 
@@ -1053,66 +1255,43 @@ pub trait Api: Send + Sync {
         }
     }
 
-    /// Produces a vector of [`Task`] items, representing all the tasks which match the provided
-    /// account, and intersect with the provided time frame.
+    /// Produces a paginated stream of [`Task`] objects matching the provided query.
     ///
-    /// See [`get`](Self::get) documentation for more details about the process and return type
-    fn get_tasks_by_account_and_pass_overlapping<T>(
+    /// The search endpoint is picked from the criteria set on the query. If Freedom has no search
+    /// endpoint for the combination of criteria, [`Error::UnsupportedQuery`] is returned before
+    /// any request is made.
+    ///
+    /// See [`get_paginated`](Self::get_paginated) documentation for more details about the process
+    /// and return type
+    fn get_tasks_matching(
         &self,
-        account_uri: T,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
-    ) -> impl Future<Output = Result<Self::Container<Vec<Task>>, Error>> + Send + Sync
-    where
-        T: AsRef<str> + Send + Sync,
-    {
-        async move {
-            let mut uri = self.path_to_url("tasks/search/findByAccountAndPassOverlapping");
-
-            uri.set_query(Some(&format!(
-                "account={}&start={}&end={}",
-                account_uri.as_ref(),
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?
-            )));
-
-            Ok(self
-                .get_json_map::<Embedded<Self::Container<Vec<Task>>>>(uri)
-                .await?
-                .items)
-        }
+        query: &TaskQuery,
+    ) -> Result<PaginatedStream<'_, Self::Container<Task>>, Error> {
+        let uri = query.url(&self.config().environment().freedom_entrypoint())?;
+        Ok(self.get_paginated(uri))
     }
 
     /// Produces a vector of [`Task`] items, representing all the tasks which match the provided
-    /// account, satellite, band, and intersect with the provided time frame.
+    /// account, and intersect with the provided time frame.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
-    fn get_tasks_by_account_and_satellite_and_band_and_pass_overlapping<T, U, V>(
+    #[deprecated(
+        note = "Use `get_tasks_matching` with `TaskQuery::account_url` and `TaskQuery::pass_overlapping`"
+    )]
+    fn get_tasks_by_account_and_pass_overlapping<T>(
         &self,
         account_uri: T,
-        satellite_config_uri: U,
-        band: V,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<Task>>, Error>> + Send + Sync
     where
         T: AsRef<str> + Send + Sync,
-        U: AsRef<str> + Send + Sync,
-        V: AsRef<str> + Send + Sync,
     {
         async move {
-            let mut uri = self.path_to_url(
-                "tasks/search/findByAccountAndSiteConfigurationAndBandAndPassOverlapping",
-            );
+            let mut uri = self.path_to_url("tasks/search/findByAccountAndPassOverlapping");
 
-            uri.set_query(Some(&format!(
-                "account={}&satellite={}&band={}&start={}&end={}",
-                account_uri.as_ref(),
-                satellite_config_uri.as_ref(),
-                band.as_ref(),
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?,
-            )));
+            uri.query_pairs_mut()
+                .append_pair("account", account_uri.as_ref());
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<Task>>>>(uri)
@@ -1125,13 +1304,15 @@ pub trait Api: Send + Sync {
     /// site configuration, band, and intersect with the provided time frame.
     ///
     /// See [`get`](Self::get) documentation for more details about the process and return type
+    #[deprecated(
+        note = "Use `get_tasks_matching` with `TaskQuery::account_url`, `TaskQuery::site_configuration_url`, `TaskQuery::band_url`, and `TaskQuery::pass_overlapping`"
+    )]
     fn get_tasks_by_account_and_site_configuration_and_band_and_pass_overlapping<T, U, V>(
         &self,
         account_uri: T,
         site_config_uri: U,
         band: V,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<Task>>, Error>> + Send + Sync
     where
         T: AsRef<str> + Send + Sync,
@@ -1143,14 +1324,11 @@ pub trait Api: Send + Sync {
                 "tasks/search/findByAccountAndSiteConfigurationAndBandAndPassOverlapping",
             );
 
-            uri.set_query(Some(&format!(
-                "account={}&siteConfig={}&band={}&start={}&end={}",
-                account_uri.as_ref(),
-                site_config_uri.as_ref(),
-                band.as_ref(),
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?
-            )));
+            uri.query_pairs_mut()
+                .append_pair("account", account_uri.as_ref())
+                .append_pair("siteConfig", site_config_uri.as_ref())
+                .append_pair("band", band.as_ref());
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<Task>>>>(uri)
@@ -1168,19 +1346,15 @@ pub trait Api: Send + Sync {
     ///
     /// This differs from [`Self::get_tasks_by_pass_overlapping`] in that it only produces tasks
     /// which are wholly contained within the window.
+    #[deprecated(note = "Use `get_tasks_matching` with `TaskQuery::start_between`")]
     fn get_tasks_by_pass_window(
         &self,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> impl Future<Output = Result<Self::Container<Vec<Task>>, Error>> + Send + Sync {
         async move {
             let mut uri = self.path_to_url("tasks/search/findByStartBetweenOrderByStartAsc");
 
-            uri.set_query(Some(&format!(
-                "start={}&end={}",
-                start.format(&Iso8601::DEFAULT)?,
-                end.format(&Iso8601::DEFAULT)?
-            )));
+            window.append_to(&mut uri);
 
             Ok(self
                 .get_json_map::<Embedded<Self::Container<Vec<Task>>>>(uri)
@@ -1198,24 +1372,13 @@ pub trait Api: Send + Sync {
     ///
     /// This differs from [`Self::get_tasks_by_pass_window`] in that it also includes tasks which
     /// only partially fall within the provided time frame.
+    #[deprecated(note = "Use `get_tasks_matching` with `TaskQuery::pass_overlapping`")]
     fn get_tasks_by_pass_overlapping(
         &self,
-        start: OffsetDateTime,
-        end: OffsetDateTime,
+        window: TimeWindow,
     ) -> PaginatedStream<'_, Self::Container<Task>> {
-        let start = match start.format(&Iso8601::DEFAULT).map_err(Error::from) {
-            Ok(start) => start,
-            Err(error) => return error.once_err(),
-        };
-
-        let end = match end.format(&Iso8601::DEFAULT).map_err(Error::from) {
-            Ok(end) => end,
-            Err(error) => return error.once_err(),
-        };
-
         let mut uri = self.path_to_url("tasks/search/findByOverlapping");
-
-        uri.set_query(Some(&format!("start={}&end={}", start, end)));
+        window.append_to(&mut uri);
 
         self.get_paginated(uri)
     }
@@ -1432,6 +1595,126 @@ pub trait Api: Send + Sync {
         post::request::new(self)
     }
 
+    /// Update the satellite matching the provided `id`
+    ///
+    /// Only the fields which are set on the builder are sent to Freedom, all other fields of the
+    /// satellite are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use freedom_api::prelude::*;
+    /// # tokio_test::block_on(async {
+    /// let client = Client::from_env()?;
+    ///
+    /// client
+    ///     .update_satellite(42)
+    ///     .description("An updated description")
+    ///     .send()
+    ///     .await?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn update_satellite(
+        &self,
+        id: impl Into<SatelliteId>,
+    ) -> update::satellite::SatelliteUpdateBuilder<'_, Self, update::NoChanges>
+    where
+        Self: Sized,
+    {
+        update::satellite::new(self, id.into())
+    }
+
+    /// Update the satellite band details matching the provided `id`
+    ///
+    /// Only the fields which are set on the builder are sent to Freedom, all other fields of the
+    /// band are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use freedom_api::prelude::*;
+    /// # tokio_test::block_on(async {
+    /// let client = Client::from_env()?;
+    ///
+    /// client
+    ///     .update_band_details(42)
+    ///     .frequency(8096.0)
+    ///     .send()
+    ///     .await?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn update_band_details(
+        &self,
+        id: impl Into<BandId>,
+    ) -> update::band::BandDetailsUpdateBuilder<'_, Self, update::NoChanges>
+    where
+        Self: Sized,
+    {
+        update::band::new(self, id.into())
+    }
+
+    /// Update the satellite configuration matching the provided `id`
+    ///
+    /// Only the fields which are set on the builder are sent to Freedom, all other fields of the
+    /// configuration are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use freedom_api::prelude::*;
+    /// # tokio_test::block_on(async {
+    /// let client = Client::from_env()?;
+    ///
+    /// client
+    ///     .update_satellite_configuration(42)
+    ///     .doppler(true)
+    ///     .send()
+    ///     .await?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn update_satellite_configuration(
+        &self,
+        id: impl Into<SatelliteConfigurationId>,
+    ) -> update::sat_config::SatelliteConfigurationUpdateBuilder<'_, Self, update::NoChanges>
+    where
+        Self: Sized,
+    {
+        update::sat_config::new(self, id.into())
+    }
+
+    /// Update the user matching the provided `id`
+    ///
+    /// Only the fields which are set on the builder are sent to Freedom, all other fields of the
+    /// user are left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use freedom_api::prelude::*;
+    /// # tokio_test::block_on(async {
+    /// let client = Client::from_env()?;
+    ///
+    /// client
+    ///     .update_user(42)
+    ///     .email("flyingsolo@gmail.com")
+    ///     .send()
+    ///     .await?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// # });
+    /// ```
+    fn update_user(
+        &self,
+        id: impl Into<UserId>,
+    ) -> update::user::UserUpdateBuilder<'_, Self, update::NoChanges>
+    where
+        Self: Sized,
+    {
+        update::user::new(self, id.into())
+    }
+
     /// Fetch an FPS token for the provided band ID and site configuration ID
     ///
     /// # Example
@@ -1439,8 +1722,8 @@ pub trait Api: Send + Sync {
     /// ```no_run
     /// # use freedom_api::prelude::*;
     /// # tokio_test::block_on(async {
-    /// const BAND_ID: u32 = 42;
-    /// const SITE_CONFIG_ID: u32 = 201;
+    /// const BAND_ID: BandId = BandId::new(42);
+    /// const SITE_CONFIG_ID: SiteConfigurationId = SiteConfigurationId::new(201);
     ///
     /// let client = Client::from_env()?;
     ///
@@ -1452,9 +1735,11 @@ pub trait Api: Send + Sync {
     /// ```
     fn new_token_by_site_configuration_id(
         &self,
-        band_id: u32,
-        site_configuration_id: u32,
+        band_id: impl Into<BandId>,
+        site_configuration_id: impl Into<SiteConfigurationId>,
     ) -> impl Future<Output = Result<String, Error>> + Send + Sync {
+        let band_id = band_id.into();
+        let site_configuration_id = site_configuration_id.into();
         async move {
             let url = self.path_to_url("fps");
             let payload = serde_json::json!({
@@ -1470,7 +1755,6 @@ pub trait Api: Send + Sync {
                 .as_str()
                 .ok_or(Error::Response(String::from("Invalid type for token")))
                 .map(|s| s.to_owned())
-                .map_err(From::from)
         }
     }
 
@@ -1481,8 +1765,8 @@ pub trait Api: Send + Sync {
     /// ```no_run
     /// # use freedom_api::prelude::*;
     /// # tokio_test::block_on(async {
-    /// const BAND_ID: u32 = 42;
-    /// const SATELLITE_ID: u32 = 101;
+    /// const BAND_ID: BandId = BandId::new(42);
+    /// const SATELLITE_ID: SatelliteId = SatelliteId::new(101);
     ///
     /// let client = Client::from_env()?;
     ///
@@ -1494,9 +1778,11 @@ pub trait Api: Send + Sync {
     /// ```
     fn new_token_by_satellite_id(
         &self,
-        band_id: u32,
-        satellite_id: u32,
+        band_id: impl Into<BandId>,
+        satellite_id: impl Into<SatelliteId>,
     ) -> impl Future<Output = Result<String, Error>> + Send + Sync {
+        let band_id = band_id.into();
+        let satellite_id = satellite_id.into();
         async move {
             let url = self.path_to_url("fps");
             let payload = serde_json::json!({
@@ -1512,14 +1798,56 @@ pub trait Api: Send + Sync {
                 .as_str()
                 .ok_or(Error::Response(String::from("Invalid type for token")))
                 .map(|s| s.to_owned())
-                .map_err(From::from)
         }
     }
 }
 
-fn error_on_non_success(status: &StatusCode) -> Result<(), Error> {
+/// Deserialize the resource returned by a write.
+///
+/// Freedom may answer a write with no body, in which case the resource is fetched from the
+/// `Location` of the response instead.
+async fn deserialize_written<C, T>(
+    client: &C,
+    method: &Method,
+    url: &Url,
+    resp: Response,
+) -> Result<T, Error>
+where
+    C: Api + ?Sized,
+    T: Value,
+{
+    let status = resp.status();
+    let location = resp
+        .headers()
+        .get(LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .and_then(|location| url.join(location).ok());
+    let body = resp.bytes().await?;
+
+    error_on_non_success(method, url, &status, &body)?;
+
+    if !body.iter().all(u8::is_ascii_whitespace) {
+        return serde_json::from_slice(&body).map_err(From::from);
+    }
+
+    match location {
+        Some(location) => client.get_json_map(location).await,
+        None => Err(Error::EmptyResponse {
+            status: status.as_u16(),
+            method: method.to_string(),
+            url: url.to_string(),
+        }),
+    }
+}
+
+pub(crate) fn error_on_non_success(
+    method: &Method,
+    url: &Url,
+    status: &StatusCode,
+    body: &[u8],
+) -> Result<(), Error> {
     if !status.is_success() {
-        return Err(Error::Response(status.to_string()));
+        return Err(Error::from_response(method, url, *status, body));
     }
 
     Ok(())
@@ -0,0 +1,582 @@
+use std::{hash::Hasher, path::Path};
+
+use reqwest::{
+    header::{HeaderMap, CONTENT_RANGE},
+    Method, Response, StatusCode,
+};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use url::Url;
+
+use crate::{api::Api, error::Error};
+
+/// The number of times an interrupted transfer is resumed, unless configured otherwise
+const DEFAULT_MAX_RESUMES: u32 = 3;
+
+/// The progress of a [`Download`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of bytes held by the destination, including those which were present before the
+    /// download was resumed
+    pub downloaded: u64,
+    /// The size of the file, when reported by Freedom
+    pub total: Option<u64>,
+}
+
+/// A streaming download of a task's file, created with
+/// [`Api::download_file_by_task_id_and_name`].
+///
+/// The file is written to its destination as it is received, rather than being buffered in
+/// memory. Transfers which are interrupted part way through are resumed from the last byte
+/// received, with an HTTP `Range` request.
+pub struct Download<'a, C> {
+    client: &'a C,
+    url: Url,
+    offset: u64,
+    max_resumes: u32,
+    expected_size: Option<u64>,
+    checksum: Option<(Box<dyn Hasher + Send + 'a>, u64)>,
+    progress: Option<Box<dyn FnMut(Progress) + Send + 'a>>,
+}
+
+pub fn new<C>(client: &C, url: Url) -> Download<'_, C> {
+    Download {
+        client,
+        url,
+        offset: 0,
+        max_resumes: DEFAULT_MAX_RESUMES,
+        expected_size: None,
+        checksum: None,
+        progress: None,
+    }
+}
+
+impl<'a, C> Download<'a, C>
+where
+    C: Api,
+{
+    /// Skip the first `offset` bytes of the file, which the destination already holds, e.g. from
+    /// an earlier partial download.
+    ///
+    /// When downloading to a path with [`Self::to_path`], the offset is instead taken from the
+    /// length of the existing file.
+    pub fn resume_from(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The number of times an interrupted transfer is resumed before failing. Defaults to 3.
+    ///
+    /// Attempts to resume which themselves fail, with a connection error or a server error, count
+    /// towards the limit.
+    pub fn max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = max_resumes;
+        self
+    }
+
+    /// Fail with [`Error::SizeMismatch`] unless the complete file is of the provided size
+    pub fn expected_size(mut self, size: u64) -> Self {
+        self.expected_size = Some(size);
+        self
+    }
+
+    /// Fail with [`Error::ChecksumMismatch`] unless the checksum of the complete file, as computed
+    /// by the hasher, matches the expected checksum.
+    ///
+    /// Any [`Hasher`] may be used, for instance `crc32fast::Hasher` for a CRC32. When resuming
+    /// with [`Self::resume_from`], the hasher must already have been fed the bytes which are
+    /// skipped. [`Self::to_path`] takes care of this by reading the existing file.
+    pub fn checksum(mut self, hasher: impl Hasher + Send + 'a, expected: u64) -> Self {
+        self.checksum = Some((Box::new(hasher), expected));
+        self
+    }
+
+    /// Call the provided function each time a chunk of the file is written
+    pub fn on_progress(mut self, progress: impl FnMut(Progress) + Send + 'a) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Write the file to the provided writer, returning the size of the complete file.
+    pub async fn to_writer<W>(mut self, writer: &mut W) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let mut downloaded = self.offset;
+        let mut total = None;
+        let mut resumes = 0;
+
+        'request: loop {
+            // Failing to reconnect after an interruption counts as another interruption, rather
+            // than failing the download outright
+            let reconnecting = resumes > 0 && resumes < self.max_resumes;
+
+            let mut response = match self.client.get_stream(self.url.clone(), downloaded).await {
+                Ok(response) => response,
+                Err(error) if reconnecting => {
+                    tracing::warn!(url = %self.url, downloaded, %error, "Download could not be resumed");
+                    resumes += 1;
+                    continue;
+                }
+                Err(error) => return Err(error),
+            };
+            let status = response.status();
+
+            if status == StatusCode::RANGE_NOT_SATISFIABLE && downloaded > 0 {
+                // The destination already holds the whole file
+                total = content_range_total(response.headers()).or(total);
+                break;
+            }
+
+            if status.is_server_error() && reconnecting {
+                tracing::warn!(url = %self.url, downloaded, %status, "Download could not be resumed");
+                resumes += 1;
+                continue;
+            }
+
+            if !status.is_success() {
+                let body = response.bytes().await?;
+                return Err(Error::from_response(&Method::GET, &self.url, status, &body));
+            }
+
+            // Servers which ignore the range send the entire file, the start of which is skipped
+            let mut skip = match status {
+                StatusCode::PARTIAL_CONTENT => 0,
+                _ => downloaded,
+            };
+            total = response_total(&response).or(total);
+
+            loop {
+                let chunk = match response.chunk().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) if total.is_some_and(|total| downloaded < total) => {
+                        tracing::warn!(url = %self.url, downloaded, ?total, "Download ended early");
+                        break;
+                    }
+                    Ok(None) => break 'request,
+                    Err(error) => {
+                        tracing::warn!(url = %self.url, downloaded, %error, "Download interrupted");
+                        break;
+                    }
+                };
+
+                let skipped = skip.min(chunk.len() as u64);
+                skip -= skipped;
+                let chunk = chunk.slice(skipped as usize..);
+                if chunk.is_empty() {
+                    continue;
+                }
+
+                writer.write_all(&chunk).await?;
+                if let Some((hasher, _)) = &mut self.checksum {
+                    hasher.write(&chunk);
+                }
+
+                downloaded += chunk.len() as u64;
+                if let Some(progress) = &mut self.progress {
+                    progress(Progress { downloaded, total });
+                }
+            }
+
+            if resumes == self.max_resumes {
+                return Err(Error::Response(format!(
+                    "Download of {} was interrupted after {downloaded} bytes",
+                    self.url
+                )));
+            }
+            resumes += 1;
+        }
+
+        writer.flush().await?;
+
+        if let Some(expected) = self.expected_size.or(total) {
+            if downloaded != expected {
+                return Err(Error::SizeMismatch {
+                    expected,
+                    actual: downloaded,
+                });
+            }
+        }
+
+        if let Some((hasher, expected)) = self.checksum {
+            let actual = hasher.finish();
+            if actual != expected {
+                return Err(Error::ChecksumMismatch { expected, actual });
+            }
+        }
+
+        Ok(downloaded)
+    }
+
+    /// Write the file to the provided path, returning the size of the complete file.
+    ///
+    /// If the file already exists, it is assumed to hold the start of the download, which is
+    /// resumed from its end.
+    pub async fn to_path(mut self, path: impl AsRef<Path>) -> Result<u64, Error> {
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .read(true)
+            .open(path)
+            .await?;
+
+        self.offset = file.metadata().await?.len();
+        if let Some((hasher, _)) = &mut self.checksum {
+            let mut buf = vec![0; 64 * 1024];
+            loop {
+                let read = file.read(&mut buf).await?;
+                if read == 0 {
+                    break;
+                }
+                hasher.write(&buf[..read]);
+            }
+        }
+
+        self.to_writer(&mut file).await
+    }
+}
+
+/// The size of the complete file, from the response's `Content-Range` or `Content-Length`
+fn response_total(response: &Response) -> Option<u64> {
+    match response.status() {
+        StatusCode::PARTIAL_CONTENT => content_range_total(response.headers()),
+        _ => response.content_length(),
+    }
+}
+
+/// Parse the complete length from a `Content-Range` header, e.g. `bytes 0-99/1000`
+fn content_range_total(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(CONTENT_RANGE)?
+        .to_str()
+        .ok()?
+        .rsplit_once('/')?
+        .1
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        hash::DefaultHasher,
+        sync::{Arc, Mutex},
+    };
+
+    use freedom_config::{Config, Env};
+    use httpmock::{Method::GET, MockServer};
+
+    use bytes::Bytes;
+
+    use crate::{Client, RetryPolicy, Value};
+
+    use super::*;
+
+    const DATA: &[u8] = b"0123456789";
+
+    #[derive(Debug, Clone)]
+    struct MockEnv(Url);
+
+    impl AsRef<str> for MockEnv {
+        fn as_ref(&self) -> &str {
+            "MockEnv"
+        }
+    }
+
+    impl Env for MockEnv {
+        fn from_str(_val: &str) -> Option<Self> {
+            None
+        }
+
+        fn fps_host(&self) -> &str {
+            "localhost"
+        }
+
+        fn freedom_entrypoint(&self) -> Url {
+            self.0.clone()
+        }
+    }
+
+    fn client(server: &MockServer) -> Client {
+        let url = Url::parse(&server.url("/api/")).unwrap();
+        let config = Config::builder()
+            .environment(MockEnv(url))
+            .key("foo")
+            .secret("bar")
+            .build()
+            .unwrap();
+
+        Client::from_config(config)
+    }
+
+    fn checksum(data: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        hasher.write(data);
+        hasher.finish()
+    }
+
+    #[tokio::test]
+    async fn streams_to_writer() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/api/downloads/42/data.bin");
+            then.status(200).body(DATA);
+        });
+        let client = client(&server);
+
+        let updates = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&updates);
+        let mut out = Vec::new();
+        let size = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .expected_size(10)
+            .checksum(DefaultHasher::new(), checksum(DATA))
+            .on_progress(move |progress| recorded.lock().unwrap().push(progress))
+            .to_writer(&mut out)
+            .await
+            .unwrap();
+
+        assert_eq!(size, 10);
+        assert_eq!(out, DATA);
+        assert_eq!(
+            updates.lock().unwrap().last(),
+            Some(&Progress {
+                downloaded: 10,
+                total: Some(10)
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn resumes_with_range_requests() {
+        let server = MockServer::start();
+        let first = server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/downloads/42/data.bin")
+                .header("range", "bytes=2-");
+            then.status(206)
+                .header("content-range", "bytes 2-5/10")
+                .body(&DATA[2..6]);
+        });
+        let second = server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/downloads/42/data.bin")
+                .header("range", "bytes=6-");
+            then.status(206)
+                .header("content-range", "bytes 6-9/10")
+                .body(&DATA[6..]);
+        });
+        let client = client(&server);
+
+        let mut out = DATA[..2].to_vec();
+        let size = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .resume_from(2)
+            .to_writer(&mut out)
+            .await
+            .unwrap();
+
+        first.assert();
+        second.assert();
+        assert_eq!(size, 10);
+        assert_eq!(out, DATA);
+    }
+
+    /// Fails the first attempt to resume from `offset` with a connection error
+    struct FlakyReconnect {
+        client: Client,
+        offset: u64,
+        failed: Mutex<bool>,
+    }
+
+    impl Api for FlakyReconnect {
+        type Container<T: Value> = <Client as Api>::Container<T>;
+
+        async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), Error> {
+            self.client.get(url).await
+        }
+
+        async fn get_stream(&self, url: Url, offset: u64) -> Result<Response, Error> {
+            if offset == self.offset && !std::mem::replace(&mut *self.failed.lock().unwrap(), true)
+            {
+                return Err(Error::Response(String::from("connection reset")));
+            }
+            self.client.get_stream(url, offset).await
+        }
+
+        async fn delete(&self, url: Url) -> Result<Response, Error> {
+            self.client.delete(url).await
+        }
+
+        async fn post<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+        where
+            S: serde::Serialize + Send + Sync,
+        {
+            self.client.post(url, msg).await
+        }
+
+        fn config(&self) -> &Config {
+            self.client.config()
+        }
+
+        fn config_mut(&mut self) -> &mut Config {
+            self.client.config_mut()
+        }
+    }
+
+    fn interrupted_at_six(server: &MockServer) {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/downloads/42/data.bin")
+                .header("range", "bytes=2-");
+            then.status(206)
+                .header("content-range", "bytes 2-5/10")
+                .body(&DATA[2..6]);
+        });
+    }
+
+    #[tokio::test]
+    async fn failed_reconnects_are_resumed() {
+        let server = MockServer::start();
+        interrupted_at_six(&server);
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/downloads/42/data.bin")
+                .header("range", "bytes=6-");
+            then.status(206)
+                .header("content-range", "bytes 6-9/10")
+                .body(&DATA[6..]);
+        });
+        let client = FlakyReconnect {
+            client: client(&server),
+            offset: 6,
+            failed: Mutex::new(false),
+        };
+
+        let mut out = DATA[..2].to_vec();
+        let size = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .resume_from(2)
+            .to_writer(&mut out)
+            .await
+            .unwrap();
+
+        assert!(*client.failed.lock().unwrap());
+        assert_eq!(size, 10);
+        assert_eq!(out, DATA);
+    }
+
+    #[tokio::test]
+    async fn unavailable_reconnects_count_towards_max_resumes() {
+        let server = MockServer::start();
+        interrupted_at_six(&server);
+        let unavailable = server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/downloads/42/data.bin")
+                .header("range", "bytes=6-");
+            then.status(503);
+        });
+        let client = client(&server).with_retry_policy(RetryPolicy::none());
+
+        let error = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .resume_from(2)
+            .max_resumes(2)
+            .to_writer(&mut Vec::new())
+            .await
+            .unwrap_err();
+
+        unavailable.assert_hits(2);
+        assert_eq!(error.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[tokio::test]
+    async fn ignored_ranges_skip_existing_bytes() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/api/downloads/42/data.bin");
+            then.status(200).body(DATA);
+        });
+        let client = client(&server);
+
+        let mut out = DATA[..4].to_vec();
+        client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .resume_from(4)
+            .to_writer(&mut out)
+            .await
+            .unwrap();
+
+        assert_eq!(out, DATA);
+    }
+
+    #[tokio::test]
+    async fn resumes_existing_file() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/downloads/42/data.bin")
+                .header("range", "bytes=3-");
+            then.status(206)
+                .header("content-range", "bytes 3-9/10")
+                .body(&DATA[3..]);
+        });
+        let client = client(&server);
+
+        let path = std::env::temp_dir().join(format!("freedom-download-{}", std::process::id()));
+        std::fs::write(&path, &DATA[..3]).unwrap();
+
+        let result = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .checksum(DefaultHasher::new(), checksum(DATA))
+            .to_path(&path)
+            .await;
+        let written = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(result, Ok(10));
+        assert_eq!(written, DATA);
+    }
+
+    #[tokio::test]
+    async fn size_mismatch_is_an_error() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/api/downloads/42/data.bin");
+            then.status(200).body(DATA);
+        });
+        let client = client(&server);
+
+        let error = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .expected_size(11)
+            .to_writer(&mut Vec::new())
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            error,
+            Error::SizeMismatch {
+                expected: 11,
+                actual: 10
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_files_are_not_found() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/api/downloads/42/data.bin");
+            then.status(404);
+        });
+        let client = client(&server);
+
+        let error = client
+            .download_file_by_task_id_and_name(42, "data.bin")
+            .to_writer(&mut Vec::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::NotFound { .. }));
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::{Context, Poll},
+};
+
+use freedom_models::pagination::Page;
+use futures_core::Stream;
+use url::Url;
+
+use crate::error::Error;
+
+type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = Result<T, Error>> + 'a + Send + Sync>>;
+
+/// Produces the stream of items for the head URL, recording the metadata of the first page
+type Start<'a, T> =
+    Box<dyn FnOnce(Url, Arc<OnceLock<Page>>) -> BoxStream<'a, T> + 'a + Send + Sync>;
+
+/// The direction in which a field is sorted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Ascending,
+    Descending,
+}
+
+impl Direction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ascending => "asc",
+            Self::Descending => "desc",
+        }
+    }
+}
+
+/// Options controlling how a paginated endpoint is traversed.
+///
+/// Options which are not set are left to Freedom's defaults.
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use futures::StreamExt;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let options = PaginationOptions::new()
+///     .size(100)
+///     .sort("name", Direction::Ascending);
+///
+/// let mut satellites = client.get_satellites().with_options(options);
+/// while let Some(satellite) = satellites.next().await {
+///     println!("{} of {:?}", satellite?.name, satellites.total_elements());
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PaginationOptions {
+    /// The number of items requested per page
+    pub size: Option<u32>,
+    /// The index of the first page requested, starting at zero
+    pub start_page: Option<u32>,
+    /// The fields by which items are sorted, in order of precedence
+    pub sort: Vec<(String, Direction)>,
+}
+
+impl PaginationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the provided number of items per page
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Start from the page with the provided index, skipping the pages before it
+    pub fn start_page(mut self, page: u32) -> Self {
+        self.start_page = Some(page);
+        self
+    }
+
+    /// Sort the items by the provided field, after any fields which were previously added
+    pub fn sort(mut self, field: impl Into<String>, direction: Direction) -> Self {
+        self.sort.push((field.into(), direction));
+        self
+    }
+
+    /// Set the query parameters of the options on the URL, replacing any existing values
+    pub(crate) fn apply(&self, url: &mut Url) {
+        let replaced = |key: &str| match key {
+            "size" => self.size.is_some(),
+            "page" => self.start_page.is_some(),
+            "sort" => !self.sort.is_empty(),
+            _ => false,
+        };
+
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !replaced(key))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        let mut query = url.query_pairs_mut();
+        query.clear().extend_pairs(pairs);
+        if let Some(size) = self.size {
+            query.append_pair("size", &size.to_string());
+        }
+        if let Some(page) = self.start_page {
+            query.append_pair("page", &page.to_string());
+        }
+        for (field, direction) in &self.sort {
+            query.append_pair("sort", &format!("{field},{}", direction.as_str()));
+        }
+        drop(query);
+
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+    }
+}
+
+enum State<'a, T> {
+    Pending {
+        url: Url,
+        options: PaginationOptions,
+        start: Start<'a, T>,
+    },
+    Started(BoxStream<'a, T>),
+    Polling,
+}
+
+/// A stream of paginated results from freedom.
+///
+/// Each item in the stream is a result, since one or more items may fail to be serialized
+///
+/// No request is made until the stream is first polled, so the traversal may be adjusted with
+/// [`Self::with_options`] beforehand. Once the first page has arrived, the totals reported by
+/// Freedom are available through [`Self::total_elements`] and [`Self::total_pages`], and are used
+/// for the lower bound of the stream's [`size_hint`](Stream::size_hint). There is no upper bound,
+/// since items may be created while the later pages are being fetched.
+///
+/// # Migrating from 2.x
+///
+/// This was previously an alias for `Pin<Box<dyn Stream<Item = Result<T, Error>> + Send + Sync>>`.
+/// It is still a `Stream` of the same items and is `Unpin`, so it may be polled and combined in
+/// the same ways, but a boxed stream must now be wrapped with [`Self::from_stream`] rather than
+/// returned directly.
+pub struct PaginatedStream<'a, T> {
+    state: State<'a, T>,
+    page: Arc<OnceLock<Page>>,
+    yielded: usize,
+}
+
+impl<'a, T> PaginatedStream<'a, T> {
+    pub(crate) fn new(
+        url: Url,
+        start: impl FnOnce(Url, Arc<OnceLock<Page>>) -> BoxStream<'a, T> + 'a + Send + Sync,
+    ) -> Self {
+        Self {
+            state: State::Pending {
+                url,
+                options: PaginationOptions::default(),
+                start: Box::new(start),
+            },
+            page: Arc::default(),
+            yielded: 0,
+        }
+    }
+
+    /// Wrap a stream which does not come from a paginated endpoint, and so has no page metadata.
+    ///
+    /// This is useful for implementors of [`Api`](crate::Api) which override
+    /// [`get_paginated`](crate::Api::get_paginated).
+    pub fn from_stream(stream: impl Stream<Item = Result<T, Error>> + 'a + Send + Sync) -> Self {
+        Self {
+            state: State::Started(Box::pin(stream)),
+            page: Arc::default(),
+            yielded: 0,
+        }
+    }
+
+    /// Traverse the endpoint according to the provided options.
+    ///
+    /// The options have no effect once the stream has been polled.
+    pub fn with_options(mut self, options: PaginationOptions) -> Self {
+        if let State::Pending {
+            options: current, ..
+        } = &mut self.state
+        {
+            *current = options;
+        }
+        self
+    }
+
+    /// The total number of items across all pages, once the first page has arrived
+    pub fn total_elements(&self) -> Option<u32> {
+        self.page.get().map(|page| page.total_elements)
+    }
+
+    /// The total number of pages, once the first page has arrived
+    pub fn total_pages(&self) -> Option<u32> {
+        self.page.get().map(|page| page.total_pages)
+    }
+}
+
+impl<T> std::fmt::Debug for PaginatedStream<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PaginatedStream")
+            .field("page", &self.page.get())
+            .field("yielded", &self.yielded)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for PaginatedStream<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if matches!(this.state, State::Pending { .. }) {
+            let State::Pending {
+                mut url,
+                options,
+                start,
+            } = std::mem::replace(&mut this.state, State::Polling)
+            else {
+                unreachable!()
+            };
+
+            options.apply(&mut url);
+            this.state = State::Started(start(url, Arc::clone(&this.page)));
+        }
+
+        let State::Started(stream) = &mut this.state else {
+            unreachable!("The stream is always started before it is polled")
+        };
+
+        let item = stream.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(_)) = item {
+            this.yielded += 1;
+        }
+
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.page.get() {
+            Some(page) => {
+                let skipped = page.number as usize * page.size as usize;
+                let remaining =
+                    (page.total_elements as usize).saturating_sub(skipped + self.yielded);
+
+                (remaining, None)
+            }
+            None => (0, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_replace_query_parameters() {
+        let mut url = Url::parse("https://example.com/api/satellites?size=5&name=foo").unwrap();
+        let options = PaginationOptions::new()
+            .size(100)
+            .start_page(2)
+            .sort("name", Direction::Ascending)
+            .sort("created", Direction::Descending);
+        options.apply(&mut url);
+
+        assert_eq!(
+            url.query(),
+            Some("name=foo&size=100&page=2&sort=name%2Casc&sort=created%2Cdesc")
+        );
+    }
+
+    #[test]
+    fn default_options_leave_url_unchanged() {
+        let mut url = Url::parse("https://example.com/api/satellites?size=5").unwrap();
+        PaginationOptions::default().apply(&mut url);
+        assert_eq!(url.query(), Some("size=5"));
+
+        let mut url = Url::parse("https://example.com/api/satellites").unwrap();
+        PaginationOptions::default().apply(&mut url);
+        assert_eq!(url.query(), None);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod band;
+pub mod batch;
 pub mod overrides;
 pub mod request;
 pub mod sat_config;
@@ -6,6 +7,11 @@ pub mod satellite;
 pub mod user;
 
 pub use self::{
-    band::BandDetailsBuilder, overrides::OverrideBuilder, request::TaskRequestBuilder,
-    sat_config::SatelliteConfigurationBuilder, satellite::SatelliteBuilder, user::UserBuilder,
+    band::BandDetailsBuilder,
+    batch::{BatchReport, BatchResult, BatchSubmission, TaskRequestBatch},
+    overrides::OverrideBuilder,
+    request::TaskRequestBuilder,
+    sat_config::SatelliteConfigurationBuilder,
+    satellite::SatelliteBuilder,
+    user::UserBuilder,
 };
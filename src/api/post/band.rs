@@ -1,5 +1,5 @@
 use freedom_models::{
-    band::{BandType, IoConfiguration, IoHardware},
+    band::{Band, BandType, IoConfiguration, IoHardware},
     task::Polarization,
 };
 use reqwest::Response;
@@ -168,7 +168,18 @@ impl<'a, C> BandDetailsBuilder<'a, C, BandDetails>
 where
     C: Api,
 {
-    pub async fn send(self) -> Result<Response, Error> {
+    /// Create the band details in Freedom, returning the created band details.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<Band>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url("satellite_bands");
+        client.post_deserialize(url, self.state).await
+    }
+
+    /// Create the band details in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
         let client = self.client;
 
         let url = client.path_to_url("satellite_bands");
@@ -0,0 +1,295 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use freedom_models::task::TaskRequest as CreatedTaskRequest;
+use futures_core::Stream;
+use futures_util::{stream::FuturesUnordered, StreamExt};
+
+use super::request::{TaskRequest, TaskRequestBuilder};
+use crate::{api::Api, error::Error};
+
+/// The default number of task requests a [`TaskRequestBatch`] submits at once
+const DEFAULT_MAX_CONCURRENT: usize = 4;
+
+type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = BatchResult<T>> + 'a + Send>>;
+
+/// The outcome of submitting a single task request of a [`TaskRequestBatch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchResult<T> {
+    /// The position of the task request in the batch, in the order in which it was added
+    pub index: usize,
+    /// The created task request, or the reason it was not created
+    pub result: Result<T, Error>,
+}
+
+/// A summary of the submission of a [`TaskRequestBatch`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchReport {
+    /// The number of task requests in the batch
+    pub total: usize,
+    /// The number of task requests which were created
+    pub succeeded: usize,
+    /// The index and error of each task request which failed, in the order they completed
+    pub failed: Vec<(usize, Error)>,
+}
+
+impl BatchReport {
+    /// The number of task requests which have not completed, which after the submission has ended
+    /// are those never sent due to an earlier failure
+    pub fn not_submitted(&self) -> usize {
+        self.total - self.succeeded - self.failed.len()
+    }
+
+    /// Whether every task request of the batch was created
+    pub fn is_success(&self) -> bool {
+        self.succeeded == self.total
+    }
+}
+
+/// Submits many task requests to Freedom at once.
+///
+/// Task requests are added either as finished builders, from
+/// [`Api::new_task_request`](crate::Api::new_task_request), or as [`TaskRequestPayload`]s, and are
+/// submitted with at most [`max_concurrent`](Self::max_concurrent) in flight at once. The result of
+/// each is streamed back as it completes, which may not be the order in which they were added.
+///
+/// By default every task request is submitted, whether or not others fail. With
+/// [`stop_on_failure`](Self::stop_on_failure), no more task requests are sent after the first
+/// failure, although those already in flight are still reported.
+///
+/// [`TaskRequestPayload`]: crate::TaskRequestPayload
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use futures::StreamExt;
+/// # use time::macros::datetime;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let requests = (0..100).map(|day| {
+///     client
+///         .new_task_request()
+///         .test_task("my_test_file.bin")
+///         .target_time_utc(datetime!(2030-01-01 12:00 UTC) + time::Duration::days(day))
+///         .task_duration(120)
+///         .satellite_id(1016)
+///         .site_id(27)
+///         .site_configuration_id(47)
+///         .band_ids([2017, 2019])
+/// });
+///
+/// let mut results = TaskRequestBatch::new(&client)
+///     .max_concurrent(8)
+///     .requests(requests)
+///     .submit();
+///
+/// while let Some(BatchResult { index, result }) = results.next().await {
+///     if let Err(error) = result {
+///         println!("Request {index} failed: {error}");
+///     }
+/// }
+/// println!("{} created", results.report().succeeded);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct TaskRequestBatch<'a, C> {
+    client: &'a C,
+    requests: Vec<TaskRequest>,
+    max_concurrent: usize,
+    stop_on_failure: bool,
+}
+
+impl<C> std::fmt::Debug for TaskRequestBatch<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskRequestBatch")
+            .field("requests", &self.requests)
+            .field("max_concurrent", &self.max_concurrent)
+            .field("stop_on_failure", &self.stop_on_failure)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, C> TaskRequestBatch<'a, C>
+where
+    C: Api,
+{
+    pub fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            requests: Vec::new(),
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            stop_on_failure: false,
+        }
+    }
+
+    /// Submit at most the provided number of task requests at once, which defaults to 4.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is zero
+    pub fn max_concurrent(mut self, max: usize) -> Self {
+        assert!(
+            max > 0,
+            "At least one task request must be submitted at once"
+        );
+        self.max_concurrent = max;
+        self
+    }
+
+    /// Whether to stop sending task requests after the first failure. Defaults to `false`.
+    pub fn stop_on_failure(mut self, stop: bool) -> Self {
+        self.stop_on_failure = stop;
+        self
+    }
+
+    /// Add a finished task request builder to the batch
+    pub fn request(mut self, request: TaskRequestBuilder<'_, C, TaskRequest>) -> Self {
+        self.requests.push(request.into_payload());
+        self
+    }
+
+    /// Add many finished task request builders to the batch
+    pub fn requests<'b>(
+        mut self,
+        requests: impl IntoIterator<Item = TaskRequestBuilder<'b, C, TaskRequest>>,
+    ) -> Self
+    where
+        C: 'b,
+    {
+        let payloads = requests.into_iter().map(TaskRequestBuilder::into_payload);
+        self.requests.extend(payloads);
+        self
+    }
+
+    /// Add a task request payload to the batch
+    pub fn payload(mut self, payload: TaskRequest) -> Self {
+        self.requests.push(payload);
+        self
+    }
+
+    /// Add many task request payloads to the batch
+    pub fn payloads(mut self, payloads: impl IntoIterator<Item = TaskRequest>) -> Self {
+        self.requests.extend(payloads);
+        self
+    }
+
+    /// Start submitting the task requests, streaming back the result of each as it completes.
+    ///
+    /// No task request is sent until the stream is first polled.
+    pub fn submit(self) -> BatchSubmission<'a, C::Container<CreatedTaskRequest>> {
+        let report = BatchReport {
+            total: self.requests.len(),
+            ..BatchReport::default()
+        };
+
+        BatchSubmission {
+            stream: submit(self),
+            report,
+        }
+    }
+}
+
+fn submit<'a, C: Api>(
+    batch: TaskRequestBatch<'a, C>,
+) -> BoxStream<'a, C::Container<CreatedTaskRequest>> {
+    let TaskRequestBatch {
+        client,
+        requests,
+        max_concurrent,
+        stop_on_failure,
+    } = batch;
+
+    Box::pin(async_stream::stream! {
+        let url = client.path_to_url("requests");
+        let mut pending = requests.into_iter().enumerate();
+        let mut in_flight = FuturesUnordered::new();
+        let mut stopped = false;
+
+        loop {
+            while !stopped && in_flight.len() < max_concurrent {
+                let Some((index, payload)) = pending.next() else {
+                    break;
+                };
+                let url = url.clone();
+                in_flight.push(async move {
+                    let result = client.post_deserialize(url, payload).await;
+                    BatchResult { index, result }
+                });
+            }
+
+            let Some(completed) = in_flight.next().await else {
+                break;
+            };
+            stopped |= stop_on_failure && completed.result.is_err();
+            yield completed;
+        }
+    })
+}
+
+/// The stream of results of a [`TaskRequestBatch`], created with [`TaskRequestBatch::submit`].
+///
+/// The outcome of the results produced so far is tallied in the [`report`](Self::report).
+pub struct BatchSubmission<'a, T> {
+    stream: BoxStream<'a, T>,
+    report: BatchReport,
+}
+
+impl<T> std::fmt::Debug for BatchSubmission<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchSubmission")
+            .field("report", &self.report)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> BatchSubmission<'_, T> {
+    /// A summary of the results produced so far
+    pub fn report(&self) -> &BatchReport {
+        &self.report
+    }
+
+    /// Submit the remaining task requests, discarding their results, and return the summary
+    pub async fn into_report(mut self) -> BatchReport {
+        while self.next().await.is_some() {}
+
+        self.report
+    }
+}
+
+impl<T> Stream for BatchSubmission<'_, T> {
+    type Item = BatchResult<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let item = this.stream.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(item)) = &item {
+            match &item.result {
+                Ok(_) => this.report.succeeded += 1,
+                Err(error) => this.report.failed.push((item.index, error.clone())),
+            }
+        }
+
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_counts_unsubmitted_requests() {
+        let report = BatchReport {
+            total: 5,
+            succeeded: 2,
+            failed: vec![(3, Error::InvalidId)],
+        };
+        assert_eq!(report.not_submitted(), 2);
+        assert!(!report.is_success());
+    }
+}
@@ -2,8 +2,13 @@ use std::collections::HashMap;
 
 use reqwest::Response;
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 
-use crate::{api::Api, error::Error};
+use crate::{
+    api::Api,
+    error::Error,
+    ids::{SatelliteConfigurationId, SatelliteId},
+};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -45,7 +50,7 @@ impl<'a, C> OverrideBuilder<'a, C, NoSatellite>
 where
     C: Api,
 {
-    pub fn satellite_id(self, id: impl Into<i32>) -> OverrideBuilder<'a, C, NoConfig> {
+    pub fn satellite_id(self, id: impl Into<SatelliteId>) -> OverrideBuilder<'a, C, NoConfig> {
         let satellite = self
             .client
             .path_to_url(format!("satellites/{}", id.into()))
@@ -78,14 +83,14 @@ where
 {
     pub fn satellite_configuration_id(
         self,
-        id: impl Into<i32>,
+        id: impl Into<SatelliteConfigurationId>,
     ) -> OverrideBuilder<'a, C, Override> {
-        let satellite = self
+        let configuration = self
             .client
-            .path_to_url(format!("satellites/{}", id.into()))
+            .path_to_url(format!("satellite_configurations/{}", id.into()))
             .to_string();
 
-        self.satellite_configuration_url(satellite)
+        self.satellite_configuration_url(configuration)
     }
 }
 
@@ -119,7 +124,19 @@ impl<'a, C> OverrideBuilder<'a, C, Override>
 where
     C: Api,
 {
-    pub async fn send(self) -> Result<Response, Error> {
+    /// Create the override in Freedom, returning the created override.
+    ///
+    /// Since there is not yet a model for overrides, the override is returned as raw JSON.
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<JsonValue>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url("overrides");
+        client.post_deserialize(url, self.state).await
+    }
+
+    /// Create the override in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
         let client = self.client;
 
         let url = client.path_to_url("overrides");
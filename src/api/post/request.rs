@@ -1,14 +1,24 @@
-use freedom_models::task::TaskType;
-use reqwest::Response;
-use serde::Serialize;
-use time::OffsetDateTime;
-
-use crate::{api::Api, error::Error};
+use std::str::FromStr;
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+use freedom_models::{satellite::Satellite, site::SiteConfiguration, task::TaskType};
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use url::Url;
+
+use crate::{
+    api::Api,
+    error::{Error, FieldError},
+    extensions::{BandExt, SatelliteConfigurationExt, SatelliteExt, SiteConfigurationExt, SiteExt},
+    ids::{BandId, OverrideId, SatelliteId, SiteConfigurationId, SiteId},
+};
+
+/// The body posted to Freedom to create a task request, as built by a task request builder
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskRequest {
-    #[serde(rename(serialize = "type"))]
+    #[serde(rename = "type")]
     typ: TaskType,
     site: String,
     satellite: String,
@@ -19,10 +29,148 @@ pub struct TaskRequest {
     minimum_duration: Option<u64>,
     hours_of_flex: Option<u8>,
     test_file: Option<String>,
-    #[serde(rename(serialize = "override"))]
+    #[serde(rename = "override")]
     with_override: Option<String>,
 }
 
+impl TaskRequest {
+    /// The type of the task
+    pub fn task_type(&self) -> TaskType {
+        self.typ
+    }
+
+    /// The URL of the site
+    pub fn site(&self) -> &str {
+        &self.site
+    }
+
+    /// The URL of the satellite
+    pub fn satellite(&self) -> &str {
+        &self.satellite
+    }
+
+    /// The URL of the site configuration
+    pub fn configuration(&self) -> &str {
+        &self.configuration
+    }
+
+    /// The URLs of the target bands
+    pub fn target_bands(&self) -> &[String] {
+        &self.target_bands
+    }
+
+    /// The target time of the task, formatted in UTC
+    pub fn target_date(&self) -> &str {
+        &self.target_date
+    }
+
+    /// The duration of the task, in seconds
+    pub fn duration(&self) -> u64 {
+        self.duration
+    }
+
+    /// The shortest duration of the task which is acceptable, in seconds
+    pub fn minimum_duration(&self) -> Option<u64> {
+        self.minimum_duration
+    }
+
+    /// The hours by which a flexible task may be moved from its target time
+    pub fn hours_of_flex(&self) -> Option<u8> {
+        self.hours_of_flex
+    }
+
+    /// The test file of a test task
+    pub fn test_file(&self) -> Option<&str> {
+        self.test_file.as_deref()
+    }
+
+    /// The URL of the override applied to the task
+    pub fn override_url(&self) -> Option<&str> {
+        self.with_override.as_deref()
+    }
+
+    /// The target time, unless it is not a valid date in UTC
+    fn parse_target_date(&self) -> Result<OffsetDateTime, FieldError> {
+        let format = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
+        PrimitiveDateTime::parse(&self.target_date, format)
+            .map(PrimitiveDateTime::assume_utc)
+            .map_err(|_| field_error("targetDate", "is not a valid date"))
+    }
+
+    /// The problems with the task request which can be found without contacting Freedom
+    fn problems(&self, now: OffsetDateTime) -> Vec<FieldError> {
+        let mut problems = Vec::new();
+
+        if self.duration == 0 {
+            problems.push(field_error("duration", "must be greater than zero"));
+        }
+        if self.minimum_duration > Some(self.duration) {
+            problems.push(field_error(
+                "minimumDuration",
+                "must not be greater than the duration",
+            ));
+        }
+        match self.parse_target_date() {
+            Ok(target) if target <= now => {
+                problems.push(field_error("targetDate", "must be in the future"));
+            }
+            Ok(_) => {}
+            Err(problem) => problems.push(problem),
+        }
+        if self.target_bands.is_empty() {
+            problems.push(field_error("targetBands", "must not be empty"));
+        }
+
+        problems
+    }
+}
+
+fn field_error(field: &str, message: impl Into<String>) -> FieldError {
+    FieldError {
+        field: field.to_string(),
+        message: message.into(),
+    }
+}
+
+fn single_problem(problem: FieldError) -> Error {
+    Error::InvalidRequest(vec![problem])
+}
+
+fn into_result(problems: Vec<FieldError>) -> Result<(), Error> {
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(Error::InvalidRequest(problems)),
+    }
+}
+
+/// The ID of the resource at the URL
+fn id_of<I: FromStr>(url: &str) -> Option<I> {
+    let url = Url::parse(url).ok()?;
+    url.path_segments()?.next_back()?.parse().ok()
+}
+
+/// Fetch the resource referenced by the field, or the problem with the reference if it does not
+/// point at an existing resource
+async fn fetch<T, C>(
+    client: &C,
+    field: &str,
+    url: &str,
+) -> Result<Result<C::Container<T>, FieldError>, Error>
+where
+    T: crate::api::Value,
+    C: Api,
+{
+    let Ok(url) = Url::parse(url) else {
+        return Ok(Err(field_error(field, "is not a valid URL")));
+    };
+
+    match client.get_json_map(url).await {
+        Ok(item) => Ok(Ok(item)),
+        Err(Error::NotFound { .. }) => Ok(Err(field_error(field, "does not exist"))),
+        Err(error) => Err(error),
+    }
+}
+
 pub struct TaskRequestBuilder<'a, C, S> {
     pub(crate) client: &'a C,
     state: S,
@@ -146,6 +294,8 @@ pub struct NoTime<T> {
 }
 
 impl<'a, C, T> TaskRequestBuilder<'a, C, NoTime<T>> {
+    /// The target time of the task. Times with any offset are accepted, and are converted to UTC
+    /// before being sent to Freedom.
     pub fn target_time_utc(self, time: OffsetDateTime) -> TaskRequestBuilder<'a, C, NoDuration<T>> {
         TaskRequestBuilder {
             client: self.client,
@@ -185,7 +335,7 @@ impl<'a, C, T> TaskRequestBuilder<'a, C, NoSatellite<T>>
 where
     C: Api,
 {
-    pub fn satellite_id(self, id: impl Into<i32>) -> TaskRequestBuilder<'a, C, NoSite<T>> {
+    pub fn satellite_id(self, id: impl Into<SatelliteId>) -> TaskRequestBuilder<'a, C, NoSite<T>> {
         let satellite = self
             .client
             .path_to_url(format!("satellites/{}", id.into()))
@@ -220,7 +370,7 @@ impl<'a, C, T> TaskRequestBuilder<'a, C, NoSite<T>>
 where
     C: Api,
 {
-    pub fn site_id(self, id: impl Into<i32>) -> TaskRequestBuilder<'a, C, NoConfig<T>> {
+    pub fn site_id(self, id: impl Into<SiteId>) -> TaskRequestBuilder<'a, C, NoConfig<T>> {
         let site = self
             .client
             .path_to_url(format!("sites/{}", id.into()))
@@ -257,7 +407,10 @@ impl<'a, C, T> TaskRequestBuilder<'a, C, NoConfig<T>>
 where
     C: Api,
 {
-    pub fn site_configuration_id(self, id: impl Into<i32>) -> TaskRequestBuilder<'a, C, NoBand<T>> {
+    pub fn site_configuration_id(
+        self,
+        id: impl Into<SiteConfigurationId>,
+    ) -> TaskRequestBuilder<'a, C, NoBand<T>> {
         let configuration = self
             .client
             .path_to_url(format!("configurations/{}", id.into()))
@@ -301,7 +454,7 @@ where
 {
     pub fn band_ids(
         self,
-        ids: impl IntoIterator<Item = i32>,
+        ids: impl IntoIterator<Item = impl Into<BandId>>,
     ) -> TaskRequestBuilder<'a, C, TaskRequest>
     where
         C: Api,
@@ -309,7 +462,7 @@ where
         let client = self.client;
         let bands = ids.into_iter().map(|id| {
             client
-                .path_to_url(format!("satellite_bands/{}", id))
+                .path_to_url(format!("satellite_bands/{}", id.into()))
                 .to_string()
         });
 
@@ -320,10 +473,15 @@ where
         mut self,
         urls: impl IntoIterator<Item = String>,
     ) -> TaskRequestBuilder<'a, C, TaskRequest> {
-        use time::macros::format_description;
         let item = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
 
-        let target_date = self.state.time.format(item).unwrap();
+        // An instant which cannot be sent in UTC is kept as is, to be reported as an invalid date
+        // by `validate` and `send`
+        let time = self.state.time;
+        let target_date = time
+            .checked_to_offset(UtcOffset::UTC)
+            .and_then(|time| time.format(item).ok())
+            .unwrap_or_else(|| time.to_string());
         let target_bands: Vec<_> = urls.into_iter().collect();
 
         let mut state = TaskRequest {
@@ -359,13 +517,31 @@ impl<'a, C> TaskRequestBuilder<'a, C, TaskRequest> {
         self.state.with_override = Some(url.into());
         self
     }
+
+    /// The payload which [`Self::send`] would post to Freedom
+    pub fn into_payload(self) -> TaskRequest {
+        self.state
+    }
+
+    /// The JSON body which [`Self::send`] would post to Freedom, without sending it
+    pub fn dry_run(&self) -> JsonValue {
+        serde_json::to_value(&self.state).expect("Task requests always serialize")
+    }
+
+    /// Check the task request for problems which Freedom would reject, without contacting Freedom.
+    ///
+    /// Every problem found is returned at once, as an [`Error::InvalidRequest`]. See
+    /// [`Self::validate_with_server`] to also check the referenced resources.
+    pub fn validate(&self) -> Result<(), Error> {
+        into_result(self.state.problems(OffsetDateTime::now_utc()))
+    }
 }
 
 impl<'a, C> TaskRequestBuilder<'a, C, TaskRequest>
 where
     C: Api,
 {
-    pub fn override_id(self, id: impl Into<i32>) -> Self {
+    pub fn override_id(self, id: impl Into<OverrideId>) -> Self {
         let override_url = self
             .client
             .path_to_url(format!("overrides/{}", id.into()))
@@ -374,7 +550,78 @@ where
         self.override_url(override_url)
     }
 
-    pub async fn send(self) -> Result<Response, Error> {
+    /// Check the task request for problems which Freedom would reject, as with [`Self::validate`],
+    /// and against the resources it references in Freedom.
+    ///
+    /// The satellite, its configuration's band details, and the site configuration are fetched, to
+    /// check that they exist, that the target bands belong to the satellite's configuration, and
+    /// that the site configuration belongs to the site. Every problem found is returned at once, as
+    /// an [`Error::InvalidRequest`], while failures to fetch the resources for any other reason are
+    /// returned as is.
+    pub async fn validate_with_server(&self) -> Result<(), Error> {
+        let mut problems = self.state.problems(OffsetDateTime::now_utc());
+        let client = self.client;
+
+        match fetch::<Satellite, C>(client, "satellite", &self.state.satellite).await? {
+            Ok(satellite) => match satellite.get_configuration(client).await {
+                Ok(config) => {
+                    let bands = config.get_band_details(client).await?;
+                    let band_ids: Vec<BandId> =
+                        bands.iter().filter_map(|band| band.get_id().ok()).collect();
+
+                    for band in &self.state.target_bands {
+                        if !id_of(band).is_some_and(|id| band_ids.contains(&id)) {
+                            problems.push(field_error(
+                                "targetBands",
+                                format!("{band} is not a band of the satellite's configuration"),
+                            ));
+                        }
+                    }
+                }
+                Err(Error::MissingUri(_) | Error::NotFound { .. }) => {
+                    problems.push(field_error("satellite", "has no configuration"));
+                }
+                Err(error) => return Err(error),
+            },
+            Err(problem) => problems.push(problem),
+        }
+
+        match fetch::<SiteConfiguration, C>(client, "configuration", &self.state.configuration)
+            .await?
+        {
+            Ok(configuration) => {
+                let site = configuration.get_site(client).await?;
+                if id_of::<SiteId>(&self.state.site) != site.get_id().ok() {
+                    problems.push(field_error(
+                        "configuration",
+                        "the site configuration does not belong to the site",
+                    ));
+                }
+            }
+            Err(problem) => problems.push(problem),
+        }
+
+        into_result(problems)
+    }
+
+    /// Create the task request in Freedom, returning the created task request.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`]. A target time which cannot
+    /// be sent in UTC fails with [`Error::InvalidRequest`], without contacting Freedom.
+    pub async fn send(self) -> Result<C::Container<freedom_models::task::TaskRequest>, Error> {
+        self.state.parse_target_date().map_err(single_problem)?;
+        let client = self.client;
+
+        let url = client.path_to_url("requests");
+        client.post_deserialize(url, self.state).await
+    }
+
+    /// Create the task request in Freedom, returning the raw response without checking its status.
+    ///
+    /// As with [`Self::send`], a target time which cannot be sent in UTC fails without contacting
+    /// Freedom.
+    pub async fn send_raw(self) -> Result<Response, Error> {
+        self.state.parse_target_date().map_err(single_problem)?;
         let client = self.client;
 
         let url = client.path_to_url("requests");
@@ -1,7 +1,7 @@
 use reqwest::Response;
 use serde::Serialize;
 
-use crate::{api::Api, error::Error};
+use crate::{api::Api, error::Error, ids::BandId};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -66,12 +66,12 @@ where
 {
     pub fn band_ids(
         self,
-        ids: impl IntoIterator<Item = i32>,
+        ids: impl IntoIterator<Item = impl Into<BandId>>,
     ) -> SatelliteConfigurationBuilder<'a, C, SatelliteConfiguration> {
         let client = self.client;
         let bands = ids.into_iter().map(|id| {
             client
-                .path_to_url(format!("satellite_bands/{}", id))
+                .path_to_url(format!("satellite_bands/{}", id.into()))
                 .to_string()
         });
 
@@ -95,7 +95,21 @@ impl<'a, C> SatelliteConfigurationBuilder<'a, C, SatelliteConfiguration>
 where
     C: Api,
 {
-    pub async fn send(self) -> Result<Response, Error> {
+    /// Create the satellite configuration in Freedom, returning the created satellite configuration.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(
+        self,
+    ) -> Result<C::Container<freedom_models::satellite_configuration::SatelliteConfiguration>, Error>
+    {
+        let client = self.client;
+
+        let url = client.path_to_url("satellite_configurations");
+        client.post_deserialize(url, self.state).await
+    }
+
+    /// Create the satellite configuration in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
         let client = self.client;
 
         let url = client.path_to_url("satellite_configurations");
@@ -1,7 +1,7 @@
 use reqwest::Response;
 use serde::Serialize;
 
-use crate::{api::Api, error::Error};
+use crate::{api::Api, error::Error, ids::SatelliteConfigurationId};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -60,7 +60,7 @@ where
 {
     pub fn satellite_configuration_id(
         self,
-        id: impl Into<i32>,
+        id: impl Into<SatelliteConfigurationId>,
     ) -> SatelliteBuilder<'a, C, NoNorad> {
         let configuration = self
             .client
@@ -103,7 +103,18 @@ impl<'a, C> SatelliteBuilder<'a, C, Satellite>
 where
     C: Api,
 {
-    pub async fn send(self) -> Result<Response, Error> {
+    /// Create the satellite in Freedom, returning the created satellite.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<freedom_models::satellite::Satellite>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url("satellites");
+        client.post_deserialize(url, self.state).await
+    }
+
+    /// Create the satellite in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
         let client = self.client;
 
         let url = client.path_to_url("satellites");
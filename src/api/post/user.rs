@@ -1,13 +1,13 @@
 use reqwest::Response;
 use serde::Serialize;
 
-use crate::{api::Api, error::Error};
+use crate::{api::Api, error::Error, ids::AccountId};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct User {
     #[serde(skip_serializing)]
-    account_id: i32,
+    account_id: AccountId,
     first_name: String,
     last_name: String,
     email: String,
@@ -30,7 +30,7 @@ pub fn new<C>(client: &C) -> UserBuilder<'_, C, NoAccount> {
 pub struct NoAccount;
 
 impl<'a, C> UserBuilder<'a, C, NoAccount> {
-    pub fn account_id(self, account_id: impl Into<i32>) -> UserBuilder<'a, C, NoFirstName> {
+    pub fn account_id(self, account_id: impl Into<AccountId>) -> UserBuilder<'a, C, NoFirstName> {
         UserBuilder {
             client: self.client,
             state: NoFirstName {
@@ -41,7 +41,7 @@ impl<'a, C> UserBuilder<'a, C, NoAccount> {
 }
 
 pub struct NoFirstName {
-    account_id: i32,
+    account_id: AccountId,
 }
 
 impl<'a, C> UserBuilder<'a, C, NoFirstName> {
@@ -57,7 +57,7 @@ impl<'a, C> UserBuilder<'a, C, NoFirstName> {
 }
 
 pub struct NoLastName {
-    account_id: i32,
+    account_id: AccountId,
     first_name: String,
 }
 
@@ -75,7 +75,7 @@ impl<'a, C> UserBuilder<'a, C, NoLastName> {
 }
 
 pub struct NoEmail {
-    account_id: i32,
+    account_id: AccountId,
     first_name: String,
     last_name: String,
 }
@@ -122,7 +122,18 @@ impl<'a, C> UserBuilder<'a, C, User>
 where
     C: Api,
 {
-    pub async fn send(self) -> Result<Response, Error> {
+    /// Create the user in Freedom, returning the created user.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<freedom_models::user::User>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("accounts/{}/newuser", self.state.account_id));
+        client.post_deserialize(url, self.state).await
+    }
+
+    /// Create the user in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
         let client = self.client;
 
         let url = client.path_to_url(format!("accounts/{}/newuser", self.state.account_id));
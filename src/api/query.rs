@@ -0,0 +1,519 @@
+use freedom_models::task::{TaskStatusType, TaskType};
+use url::Url;
+
+use crate::{
+    error::Error,
+    ids::{AccountId, BandId, SiteConfigurationId},
+    window::TimeWindow,
+};
+
+/// A resource referenced by a query, either by ID or by URL
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Reference {
+    Id(i32),
+    Url(String),
+}
+
+impl Reference {
+    fn resolve(&self, base: &Url, collection: &str) -> Result<String, Error> {
+        match self {
+            Self::Id(id) => Ok(base.join(&format!("{collection}/{id}"))?.to_string()),
+            Self::Url(url) => Ok(url.clone()),
+        }
+    }
+}
+
+/// The criteria of a query, in the order they appear in the names of Freedom's search endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Criterion {
+    Account,
+    Configuration,
+    SatelliteName,
+    SatelliteNames,
+    SiteConfiguration,
+    Band,
+    Status,
+    Type,
+    TargetDate,
+    OverlappingPublic,
+    Overlapping,
+    StartBetween,
+}
+
+impl Criterion {
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::Account => "account",
+            Self::Configuration => "site configuration",
+            Self::SatelliteName => "satellite name",
+            Self::SatelliteNames => "satellite names",
+            Self::SiteConfiguration => "site configuration",
+            Self::Band => "band",
+            Self::Status => "status",
+            Self::Type => "type",
+            Self::TargetDate => "target date",
+            Self::OverlappingPublic => "overlapping public",
+            Self::Overlapping => "pass overlapping",
+            Self::StartBetween => "start between",
+        }
+    }
+}
+
+fn unsupported(resource: &str, criteria: &[Criterion]) -> Error {
+    let criteria = match criteria {
+        [] => String::from("no criteria"),
+        criteria => criteria
+            .iter()
+            .map(Criterion::describe)
+            .collect::<Vec<_>>()
+            .join(" and "),
+    };
+
+    Error::UnsupportedQuery(format!("{resource} by {criteria}"))
+}
+
+/// A search for task requests, built from any combination of criteria.
+///
+/// Freedom exposes a separate search endpoint for each supported combination of criteria. The
+/// query picks the endpoint matching the criteria which were set, see
+/// [`Api::get_requests_matching`](crate::Api::get_requests_matching).
+///
+/// The supported combinations are:
+///
+/// + No criteria
+/// + Target date
+/// + Account and target date
+/// + Site configuration
+/// + Site configuration and target date
+/// + Site configuration, satellite names, and target date
+/// + Satellite name (only one)
+/// + Satellite name (only one), and target date
+/// + Status
+/// + Account, status, and target date
+/// + Type and target date
+/// + Overlapping public
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use futures::StreamExt;
+/// # use time::macros::datetime;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let query = RequestQuery::new()
+///     .satellite_name("FooBar 6")
+///     .target_date_between(TimeWindow::new(
+///         datetime!(2024-01-01 0:00 UTC),
+///         datetime!(2024-02-01 0:00 UTC),
+///     )?);
+///
+/// let mut requests = client.get_requests_matching(&query)?;
+/// while let Some(request) = requests.next().await {
+///     println!("{}", request?.target_date);
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestQuery {
+    account: Option<Reference>,
+    configuration: Option<Reference>,
+    satellite_names: Vec<String>,
+    status: Option<TaskStatusType>,
+    typ: Option<TaskType>,
+    target_date: Option<TimeWindow>,
+    overlapping_public: Option<TimeWindow>,
+}
+
+impl RequestQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match requests of the account with the provided ID
+    pub fn account_id(mut self, id: impl Into<AccountId>) -> Self {
+        self.account = Some(Reference::Id(id.into().get()));
+        self
+    }
+
+    /// Only match requests of the account at the provided URL
+    pub fn account_url(mut self, url: impl Into<String>) -> Self {
+        self.account = Some(Reference::Url(url.into()));
+        self
+    }
+
+    /// Only match requests for the site configuration with the provided ID
+    pub fn configuration_id(mut self, id: impl Into<SiteConfigurationId>) -> Self {
+        self.configuration = Some(Reference::Id(id.into().get()));
+        self
+    }
+
+    /// Only match requests for the site configuration at the provided URL
+    pub fn configuration_url(mut self, url: impl Into<String>) -> Self {
+        self.configuration = Some(Reference::Url(url.into()));
+        self
+    }
+
+    /// Only match requests for the satellite with the provided name, in addition to any satellite
+    /// names which were previously added
+    pub fn satellite_name(mut self, name: impl Into<String>) -> Self {
+        self.satellite_names.push(name.into());
+        self
+    }
+
+    /// Only match requests for the satellites with the provided names, in addition to any
+    /// satellite names which were previously added
+    pub fn satellite_names(mut self, names: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.satellite_names
+            .extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Only match requests whose latest status is the provided status
+    pub fn status(mut self, status: TaskStatusType) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Only match requests of the provided type
+    pub fn task_type(mut self, typ: TaskType) -> Self {
+        self.typ = Some(typ);
+        self
+    }
+
+    /// Only match requests whose target date falls within the provided time frame
+    pub fn target_date_between(mut self, window: TimeWindow) -> Self {
+        self.target_date = Some(window);
+        self
+    }
+
+    /// Only match public requests which overlap the provided time frame
+    pub fn overlapping_public(mut self, window: TimeWindow) -> Self {
+        self.overlapping_public = Some(window);
+        self
+    }
+
+    fn criteria(&self) -> Vec<Criterion> {
+        let satellites = match self.satellite_names.len() {
+            0 => None,
+            1 => Some(Criterion::SatelliteName),
+            _ => Some(Criterion::SatelliteNames),
+        };
+
+        [
+            self.account.as_ref().map(|_| Criterion::Account),
+            self.configuration
+                .as_ref()
+                .map(|_| Criterion::Configuration),
+            satellites,
+            self.status.map(|_| Criterion::Status),
+            self.typ.map(|_| Criterion::Type),
+            self.target_date.map(|_| Criterion::TargetDate),
+            self.overlapping_public
+                .map(|_| Criterion::OverlappingPublic),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The URL of the search endpoint matching the query, relative to the provided entrypoint
+    pub(crate) fn url(&self, base: &Url) -> Result<Url, Error> {
+        use Criterion::*;
+
+        let criteria = self.criteria();
+        let (endpoint, satellite_key) = match criteria.as_slice() {
+            [] => ("findAll", ""),
+            [TargetDate] => ("findAllByTargetDateBetween", ""),
+            [Account, TargetDate] => ("findAllByAccountAndTargetDateBetween", ""),
+            [Configuration] => ("findAllByConfigurationOrderByCreatedAsc", ""),
+            [Configuration, TargetDate] => ("findAllByConfigurationAndTargetDateBetween", ""),
+            [Configuration, SatelliteName | SatelliteNames, TargetDate] => (
+                "findAllByConfigurationAndSatelliteNamesAndTargetDateBetween",
+                "satelliteNames",
+            ),
+            [SatelliteName] => ("findBySatelliteName", "name"),
+            [SatelliteName, TargetDate] => ("findAllBySatelliteNameAndTargetDateBetween", "name"),
+            [Status] => ("findByStatus", ""),
+            [Account, Status, TargetDate] => ("findAllByStatusAndAccountAndTargetDateBetween", ""),
+            [Type, TargetDate] => ("findAllByTypeAndTargetDateBetween", ""),
+            [OverlappingPublic] => ("findAllByOverlappingPublic", ""),
+            _ => return Err(unsupported("task requests", &criteria)),
+        };
+
+        let mut url = base.join(&format!("requests/search/{endpoint}"))?;
+        if let Some(account) = &self.account {
+            let account = account.resolve(base, "accounts")?;
+            url.query_pairs_mut().append_pair("account", &account);
+        }
+        if let Some(configuration) = &self.configuration {
+            let configuration = configuration.resolve(base, "configurations")?;
+            url.query_pairs_mut()
+                .append_pair("configuration", &configuration);
+        }
+        if !self.satellite_names.is_empty() {
+            url.query_pairs_mut()
+                .append_pair(satellite_key, &self.satellite_names.join(","));
+        }
+        if let Some(status) = self.status {
+            url.query_pairs_mut().append_pair("status", status.as_ref());
+        }
+        if let Some(typ) = self.typ {
+            url.query_pairs_mut().append_pair("type", typ.as_ref());
+        }
+        if let Some(window) = self.target_date.or(self.overlapping_public) {
+            window.append_to(&mut url);
+        }
+
+        Ok(url)
+    }
+}
+
+/// A search for tasks, built from any combination of criteria.
+///
+/// Like [`RequestQuery`], the query picks the search endpoint matching the criteria which were
+/// set, see [`Api::get_tasks_matching`](crate::Api::get_tasks_matching).
+///
+/// The supported combinations are:
+///
+/// + Pass overlapping
+/// + Start between
+/// + Account and pass overlapping
+/// + Account, site configuration, band, and pass overlapping
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use futures::StreamExt;
+/// # use time::macros::datetime;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let query = TaskQuery::new()
+///     .pass_overlapping(TimeWindow::new(
+///         datetime!(2024-01-01 0:00 UTC),
+///         datetime!(2024-01-02 0:00 UTC),
+///     )?);
+///
+/// let mut tasks = client.get_tasks_matching(&query)?;
+/// while let Some(task) = tasks.next().await {
+///     println!("{}", task?.start);
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskQuery {
+    account: Option<Reference>,
+    site_configuration: Option<Reference>,
+    band: Option<Reference>,
+    overlapping: Option<TimeWindow>,
+    start_between: Option<TimeWindow>,
+}
+
+impl TaskQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match tasks of the account with the provided ID
+    pub fn account_id(mut self, id: impl Into<AccountId>) -> Self {
+        self.account = Some(Reference::Id(id.into().get()));
+        self
+    }
+
+    /// Only match tasks of the account at the provided URL
+    pub fn account_url(mut self, url: impl Into<String>) -> Self {
+        self.account = Some(Reference::Url(url.into()));
+        self
+    }
+
+    /// Only match tasks for the site configuration with the provided ID
+    pub fn site_configuration_id(mut self, id: impl Into<SiteConfigurationId>) -> Self {
+        self.site_configuration = Some(Reference::Id(id.into().get()));
+        self
+    }
+
+    /// Only match tasks for the site configuration at the provided URL
+    pub fn site_configuration_url(mut self, url: impl Into<String>) -> Self {
+        self.site_configuration = Some(Reference::Url(url.into()));
+        self
+    }
+
+    /// Only match tasks for the band with the provided ID
+    pub fn band_id(mut self, id: impl Into<BandId>) -> Self {
+        self.band = Some(Reference::Id(id.into().get()));
+        self
+    }
+
+    /// Only match tasks for the band at the provided URL
+    pub fn band_url(mut self, url: impl Into<String>) -> Self {
+        self.band = Some(Reference::Url(url.into()));
+        self
+    }
+
+    /// Only match tasks whose pass overlaps the provided time frame, even partially
+    pub fn pass_overlapping(mut self, window: TimeWindow) -> Self {
+        self.overlapping = Some(window);
+        self
+    }
+
+    /// Only match tasks which start within the provided time frame, ordered by their start
+    pub fn start_between(mut self, window: TimeWindow) -> Self {
+        self.start_between = Some(window);
+        self
+    }
+
+    fn criteria(&self) -> Vec<Criterion> {
+        [
+            self.account.as_ref().map(|_| Criterion::Account),
+            self.site_configuration
+                .as_ref()
+                .map(|_| Criterion::SiteConfiguration),
+            self.band.as_ref().map(|_| Criterion::Band),
+            self.overlapping.map(|_| Criterion::Overlapping),
+            self.start_between.map(|_| Criterion::StartBetween),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The URL of the search endpoint matching the query, relative to the provided entrypoint
+    pub(crate) fn url(&self, base: &Url) -> Result<Url, Error> {
+        use Criterion::*;
+
+        let criteria = self.criteria();
+        let endpoint = match criteria.as_slice() {
+            [Overlapping] => "findByOverlapping",
+            [StartBetween] => "findByStartBetweenOrderByStartAsc",
+            [Account, Overlapping] => "findByAccountAndPassOverlapping",
+            [Account, SiteConfiguration, Band, Overlapping] => {
+                "findByAccountAndSiteConfigurationAndBandAndPassOverlapping"
+            }
+            _ => return Err(unsupported("tasks", &criteria)),
+        };
+
+        let mut url = base.join(&format!("tasks/search/{endpoint}"))?;
+        let references = [
+            ("account", "accounts", &self.account),
+            ("siteConfig", "configurations", &self.site_configuration),
+            ("band", "satellite_bands", &self.band),
+        ];
+        for (key, collection, reference) in references {
+            if let Some(reference) = reference {
+                let reference = reference.resolve(base, collection)?;
+                url.query_pairs_mut().append_pair(key, &reference);
+            }
+        }
+        if let Some(window) = self.overlapping.or(self.start_between) {
+            window.append_to(&mut url);
+        }
+
+        Ok(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn base() -> Url {
+        Url::parse("https://test-api.atlasground.com/api/").unwrap()
+    }
+
+    fn window() -> TimeWindow {
+        TimeWindow::new(
+            datetime!(2024-01-01 0:00 UTC),
+            datetime!(2024-01-02 0:00 UTC),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn request_query_picks_endpoint() {
+        let query = RequestQuery::new()
+            .satellite_name("FooBar 6")
+            .target_date_between(window());
+        let url = query.url(&base()).unwrap();
+
+        assert_eq!(
+            url.path(),
+            "/api/requests/search/findAllBySatelliteNameAndTargetDateBetween"
+        );
+        let pairs: Vec<_> = url.query_pairs().into_owned().collect();
+        assert_eq!(pairs[0], ("name".into(), "FooBar 6".into()));
+        assert_eq!(pairs[1].0, "start");
+        assert_eq!(pairs[2].0, "end");
+
+        let url = RequestQuery::new().url(&base()).unwrap();
+        assert_eq!(url.path(), "/api/requests/search/findAll");
+        assert_eq!(url.query(), None);
+    }
+
+    #[test]
+    fn request_query_resolves_references() {
+        let query = RequestQuery::new()
+            .configuration_id(47)
+            .satellite_names(["A", "B"])
+            .target_date_between(window());
+        let url = query.url(&base()).unwrap();
+
+        assert_eq!(
+            url.path(),
+            "/api/requests/search/findAllByConfigurationAndSatelliteNamesAndTargetDateBetween"
+        );
+        let pairs: Vec<_> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            pairs[0],
+            (
+                "configuration".into(),
+                "https://test-api.atlasground.com/api/configurations/47".into()
+            )
+        );
+        assert_eq!(pairs[1], ("satelliteNames".into(), "A,B".into()));
+    }
+
+    #[test]
+    fn unsupported_combinations_are_errors() {
+        let query = RequestQuery::new()
+            .status(TaskStatusType::Received)
+            .satellite_names(["A", "B"]);
+
+        assert_eq!(
+            query.url(&base()),
+            Err(Error::UnsupportedQuery(String::from(
+                "task requests by satellite names and status"
+            )))
+        );
+
+        assert_eq!(
+            TaskQuery::new().url(&base()),
+            Err(Error::UnsupportedQuery(String::from(
+                "tasks by no criteria"
+            )))
+        );
+    }
+
+    #[test]
+    fn task_query_picks_endpoint() {
+        let query = TaskQuery::new()
+            .account_id(1)
+            .site_configuration_id(47)
+            .band_id(1573)
+            .pass_overlapping(window());
+        let url = query.url(&base()).unwrap();
+
+        assert_eq!(
+            url.path(),
+            "/api/tasks/search/findByAccountAndSiteConfigurationAndBandAndPassOverlapping"
+        );
+        let keys: Vec<_> = url.query_pairs().map(|(key, _)| key.into_owned()).collect();
+        assert_eq!(keys, ["account", "siteConfig", "band", "start", "end"]);
+    }
+}
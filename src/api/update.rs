@@ -0,0 +1,14 @@
+pub mod band;
+pub mod sat_config;
+pub mod satellite;
+pub mod user;
+
+pub use self::{
+    band::BandDetailsUpdateBuilder, sat_config::SatelliteConfigurationUpdateBuilder,
+    satellite::SatelliteUpdateBuilder, user::UserUpdateBuilder,
+};
+
+/// The initial state of every update builder, before any field has been changed.
+///
+/// Update builders can only be sent once at least one field has been set.
+pub struct NoChanges;
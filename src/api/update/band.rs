@@ -0,0 +1,174 @@
+use freedom_models::{
+    band::{Band, BandType, IoHardware},
+    task::Polarization,
+};
+use reqwest::Response;
+use serde::Serialize;
+
+use crate::{api::Api, error::Error, ids::BandId};
+
+use super::NoChanges;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BandDetailsUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename(serialize = "type"), skip_serializing_if = "Option::is_none")]
+    typ: Option<BandType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_mghz: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_band_width_mghz: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modulation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    eirp: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gain: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    io_configuration: Option<IoConfigurationUpdate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    polarization: Option<Polarization>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    manual_transmit_control: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct IoConfigurationUpdate {
+    io_hardware: IoHardware,
+}
+
+impl From<NoChanges> for BandDetailsUpdate {
+    fn from(_: NoChanges) -> Self {
+        Self::default()
+    }
+}
+
+pub struct BandDetailsUpdateBuilder<'a, C, S> {
+    pub(crate) client: &'a C,
+    id: BandId,
+    state: S,
+}
+
+pub fn new<C>(client: &C, id: BandId) -> BandDetailsUpdateBuilder<'_, C, NoChanges> {
+    BandDetailsUpdateBuilder {
+        client,
+        id,
+        state: NoChanges,
+    }
+}
+
+impl<'a, C, S> BandDetailsUpdateBuilder<'a, C, S>
+where
+    S: Into<BandDetailsUpdate>,
+{
+    fn change(
+        self,
+        f: impl FnOnce(&mut BandDetailsUpdate),
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        let mut state = self.state.into();
+        f(&mut state);
+
+        BandDetailsUpdateBuilder {
+            client: self.client,
+            id: self.id,
+            state,
+        }
+    }
+
+    pub fn name(
+        self,
+        name: impl Into<String>,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.name = Some(name.into()))
+    }
+
+    pub fn band_type(
+        self,
+        band_type: BandType,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.typ = Some(band_type))
+    }
+
+    pub fn frequency(
+        self,
+        frequency: impl Into<f64>,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.frequency_mghz = Some(frequency.into()))
+    }
+
+    pub fn default_band_width(
+        self,
+        bandwidth_mghz: impl Into<f64>,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.default_band_width_mghz = Some(bandwidth_mghz.into()))
+    }
+
+    pub fn io_hardware(
+        self,
+        hardware: IoHardware,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        let io_configuration = IoConfigurationUpdate {
+            io_hardware: hardware,
+        };
+
+        self.change(|state| state.io_configuration = Some(io_configuration))
+    }
+
+    pub fn polarization(
+        self,
+        polarization: Polarization,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.polarization = Some(polarization))
+    }
+
+    pub fn modulation(
+        self,
+        modulation: impl Into<String>,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.modulation = Some(modulation.into()))
+    }
+
+    pub fn effective_isotropic_radiative_power(
+        self,
+        eirp: impl Into<f64>,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.eirp = Some(eirp.into()))
+    }
+
+    pub fn gain(self, gain: impl Into<f64>) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.gain = Some(gain.into()))
+    }
+
+    pub fn manual_transmit_control(
+        self,
+        control: bool,
+    ) -> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate> {
+        self.change(|state| state.manual_transmit_control = Some(control))
+    }
+}
+
+impl<'a, C> BandDetailsUpdateBuilder<'a, C, BandDetailsUpdate>
+where
+    C: Api,
+{
+    /// Apply the changes in Freedom, returning the updated band details.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<Band>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("satellite_bands/{}", self.id));
+        client.patch_deserialize(url, self.state).await
+    }
+
+    /// Apply the changes in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("satellite_bands/{}", self.id));
+        client.patch(url, self.state).await
+    }
+}
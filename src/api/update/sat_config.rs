@@ -0,0 +1,143 @@
+use reqwest::Response;
+use serde::Serialize;
+
+use crate::{
+    api::Api,
+    error::Error,
+    ids::{BandId, SatelliteConfigurationId},
+};
+
+use super::NoChanges;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SatelliteConfigurationUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    doppler: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    band_details: Option<Vec<String>>,
+}
+
+impl From<NoChanges> for SatelliteConfigurationUpdate {
+    fn from(_: NoChanges) -> Self {
+        Self::default()
+    }
+}
+
+pub struct SatelliteConfigurationUpdateBuilder<'a, C, S> {
+    pub(crate) client: &'a C,
+    id: SatelliteConfigurationId,
+    state: S,
+}
+
+pub fn new<C>(
+    client: &C,
+    id: SatelliteConfigurationId,
+) -> SatelliteConfigurationUpdateBuilder<'_, C, NoChanges> {
+    SatelliteConfigurationUpdateBuilder {
+        client,
+        id,
+        state: NoChanges,
+    }
+}
+
+impl<'a, C, S> SatelliteConfigurationUpdateBuilder<'a, C, S>
+where
+    S: Into<SatelliteConfigurationUpdate>,
+{
+    fn change(
+        self,
+        f: impl FnOnce(&mut SatelliteConfigurationUpdate),
+    ) -> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate> {
+        let mut state = self.state.into();
+        f(&mut state);
+
+        SatelliteConfigurationUpdateBuilder {
+            client: self.client,
+            id: self.id,
+            state,
+        }
+    }
+
+    pub fn name(
+        self,
+        name: impl Into<String>,
+    ) -> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate> {
+        self.change(|state| state.name = Some(name.into()))
+    }
+
+    pub fn doppler(
+        self,
+        doppler: bool,
+    ) -> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate> {
+        self.change(|state| state.doppler = Some(doppler))
+    }
+
+    pub fn notes(
+        self,
+        notes: impl Into<String>,
+    ) -> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate> {
+        self.change(|state| state.notes = Some(notes.into()))
+    }
+
+    /// Replaces the bands associated with the configuration
+    pub fn band_urls(
+        self,
+        urls: impl IntoIterator<Item = String>,
+    ) -> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate> {
+        let band_details: Vec<_> = urls.into_iter().collect();
+
+        self.change(|state| state.band_details = Some(band_details))
+    }
+}
+
+impl<'a, C, S> SatelliteConfigurationUpdateBuilder<'a, C, S>
+where
+    C: Api,
+    S: Into<SatelliteConfigurationUpdate>,
+{
+    /// Replaces the bands associated with the configuration
+    pub fn band_ids(
+        self,
+        ids: impl IntoIterator<Item = impl Into<BandId>>,
+    ) -> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate> {
+        let client = self.client;
+        let bands = ids.into_iter().map(|id| {
+            client
+                .path_to_url(format!("satellite_bands/{}", id.into()))
+                .to_string()
+        });
+
+        self.band_urls(bands)
+    }
+}
+
+impl<'a, C> SatelliteConfigurationUpdateBuilder<'a, C, SatelliteConfigurationUpdate>
+where
+    C: Api,
+{
+    /// Apply the changes in Freedom, returning the updated satellite configuration.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(
+        self,
+    ) -> Result<C::Container<freedom_models::satellite_configuration::SatelliteConfiguration>, Error>
+    {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("satellite_configurations/{}", self.id));
+        client.patch_deserialize(url, self.state).await
+    }
+
+    /// Apply the changes in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("satellite_configurations/{}", self.id));
+        client.patch(url, self.state).await
+    }
+}
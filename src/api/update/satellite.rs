@@ -0,0 +1,125 @@
+use reqwest::Response;
+use serde::Serialize;
+
+use crate::{
+    api::Api,
+    error::Error,
+    ids::{SatelliteConfigurationId, SatelliteId},
+};
+
+use super::NoChanges;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SatelliteUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    norad_cat_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    configuration: Option<String>,
+}
+
+impl From<NoChanges> for SatelliteUpdate {
+    fn from(_: NoChanges) -> Self {
+        Self::default()
+    }
+}
+
+pub struct SatelliteUpdateBuilder<'a, C, S> {
+    pub(crate) client: &'a C,
+    id: SatelliteId,
+    state: S,
+}
+
+pub fn new<C>(client: &C, id: SatelliteId) -> SatelliteUpdateBuilder<'_, C, NoChanges> {
+    SatelliteUpdateBuilder {
+        client,
+        id,
+        state: NoChanges,
+    }
+}
+
+impl<'a, C, S> SatelliteUpdateBuilder<'a, C, S>
+where
+    S: Into<SatelliteUpdate>,
+{
+    fn change(
+        self,
+        f: impl FnOnce(&mut SatelliteUpdate),
+    ) -> SatelliteUpdateBuilder<'a, C, SatelliteUpdate> {
+        let mut state = self.state.into();
+        f(&mut state);
+
+        SatelliteUpdateBuilder {
+            client: self.client,
+            id: self.id,
+            state,
+        }
+    }
+
+    pub fn name(self, name: impl Into<String>) -> SatelliteUpdateBuilder<'a, C, SatelliteUpdate> {
+        self.change(|state| state.name = Some(name.into()))
+    }
+
+    pub fn description(
+        self,
+        description: impl Into<String>,
+    ) -> SatelliteUpdateBuilder<'a, C, SatelliteUpdate> {
+        self.change(|state| state.description = Some(description.into()))
+    }
+
+    pub fn norad_id(self, norad_id: u32) -> SatelliteUpdateBuilder<'a, C, SatelliteUpdate> {
+        self.change(|state| state.norad_cat_id = Some(norad_id))
+    }
+
+    pub fn satellite_configuration_url(
+        self,
+        url: impl Into<String>,
+    ) -> SatelliteUpdateBuilder<'a, C, SatelliteUpdate> {
+        self.change(|state| state.configuration = Some(url.into()))
+    }
+}
+
+impl<'a, C, S> SatelliteUpdateBuilder<'a, C, S>
+where
+    C: Api,
+    S: Into<SatelliteUpdate>,
+{
+    pub fn satellite_configuration_id(
+        self,
+        id: impl Into<SatelliteConfigurationId>,
+    ) -> SatelliteUpdateBuilder<'a, C, SatelliteUpdate> {
+        let configuration = self
+            .client
+            .path_to_url(format!("satellite_configurations/{}", id.into()))
+            .to_string();
+
+        self.satellite_configuration_url(configuration)
+    }
+}
+
+impl<'a, C> SatelliteUpdateBuilder<'a, C, SatelliteUpdate>
+where
+    C: Api,
+{
+    /// Apply the changes in Freedom, returning the updated satellite.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<freedom_models::satellite::Satellite>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("satellites/{}", self.id));
+        client.patch_deserialize(url, self.state).await
+    }
+
+    /// Apply the changes in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("satellites/{}", self.id));
+        client.patch(url, self.state).await
+    }
+}
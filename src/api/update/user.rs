@@ -0,0 +1,107 @@
+use reqwest::Response;
+use serde::Serialize;
+
+use crate::{api::Api, error::Error, ids::UserId};
+
+use super::NoChanges;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    machine_service: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<String>>,
+}
+
+impl From<NoChanges> for UserUpdate {
+    fn from(_: NoChanges) -> Self {
+        Self::default()
+    }
+}
+
+pub struct UserUpdateBuilder<'a, C, S> {
+    client: &'a C,
+    id: UserId,
+    state: S,
+}
+
+pub fn new<C>(client: &C, id: UserId) -> UserUpdateBuilder<'_, C, NoChanges> {
+    UserUpdateBuilder {
+        client,
+        id,
+        state: NoChanges,
+    }
+}
+
+impl<'a, C, S> UserUpdateBuilder<'a, C, S>
+where
+    S: Into<UserUpdate>,
+{
+    fn change(self, f: impl FnOnce(&mut UserUpdate)) -> UserUpdateBuilder<'a, C, UserUpdate> {
+        let mut state = self.state.into();
+        f(&mut state);
+
+        UserUpdateBuilder {
+            client: self.client,
+            id: self.id,
+            state,
+        }
+    }
+
+    pub fn first_name(self, first_name: impl Into<String>) -> UserUpdateBuilder<'a, C, UserUpdate> {
+        self.change(|state| state.first_name = Some(first_name.into()))
+    }
+
+    pub fn last_name(self, last_name: impl Into<String>) -> UserUpdateBuilder<'a, C, UserUpdate> {
+        self.change(|state| state.last_name = Some(last_name.into()))
+    }
+
+    pub fn email(self, email: impl Into<String>) -> UserUpdateBuilder<'a, C, UserUpdate> {
+        self.change(|state| state.email = Some(email.into()))
+    }
+
+    pub fn machine_service(self, machine_service: bool) -> UserUpdateBuilder<'a, C, UserUpdate> {
+        self.change(|state| state.machine_service = Some(machine_service))
+    }
+
+    /// Replaces the roles assigned to the user
+    pub fn roles<I, T>(self, roles: I) -> UserUpdateBuilder<'a, C, UserUpdate>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let roles = roles.into_iter().map(Into::into).collect();
+
+        self.change(|state| state.roles = Some(roles))
+    }
+}
+
+impl<'a, C> UserUpdateBuilder<'a, C, UserUpdate>
+where
+    C: Api,
+{
+    /// Apply the changes in Freedom, returning the updated user.
+    ///
+    /// Responses with a non-success status are returned as an [`Error`].
+    pub async fn send(self) -> Result<C::Container<freedom_models::user::User>, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("users/{}", self.id));
+        client.patch_deserialize(url, self.state).await
+    }
+
+    /// Apply the changes in Freedom, returning the raw response without checking its status.
+    pub async fn send_raw(self) -> Result<Response, Error> {
+        let client = self.client;
+
+        let url = client.path_to_url(format!("users/{}", self.id));
+        client.patch(url, self.state).await
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use async_stream::stream;
+use freedom_models::task::{Task, TaskStatus, TaskStatusType};
+use futures_core::Stream;
+use time::OffsetDateTime;
+
+use crate::{
+    api::{Api, Container},
+    error::Error,
+    extensions::TaskRequestExt,
+    ids::TaskRequestId,
+};
+
+/// The time waited between polls of the task request, unless configured otherwise
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = Result<T, Error>> + 'a + Send>>;
+
+/// A change in the status of a task request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusChange {
+    /// The status before the change, or `None` for the status found when the watch began
+    pub previous: Option<TaskStatusType>,
+    /// The status after the change
+    pub status: TaskStatusType,
+    /// The reason given by Freedom for the change
+    pub reason: String,
+    /// The time at which Freedom recorded the change
+    pub at: OffsetDateTime,
+}
+
+/// An event produced by a [`RequestWatch`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestEvent<T> {
+    /// The status of the task request changed
+    StatusChanged(StatusChange),
+    /// The task scheduled for the request was found. This is produced at most once.
+    Task(T),
+}
+
+enum State<'a, C: Api> {
+    Pending {
+        client: &'a C,
+        id: TaskRequestId,
+        interval: Duration,
+    },
+    Started(BoxStream<'a, RequestEvent<C::Container<Task>>>),
+    Polling,
+}
+
+/// A stream of the events of a task request, created with [`Api::watch_request`].
+///
+/// The task request is polled until its status is terminal, at which point the stream ends. The
+/// first event is always the status of the request when the watch began, followed by every change
+/// recorded by Freedom since, in the order in which they happened. Once a task has been scheduled
+/// for the request, it is produced as a [`RequestEvent::Task`].
+///
+/// The terminal statuses are those after which the request no longer changes in a way which
+/// concerns its outcome: `REJECTED`, `DENIED`, `CANCELLED`, `SYSTEM_ERROR`, `COMPLETED` and
+/// `COMPLETED_ERROR`, as well as the delivery and billing statuses which may follow them.
+///
+/// The stream ends after producing an error, since the client has already retried the request
+/// according to its [`RetryPolicy`](crate::RetryPolicy).
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use freedom_api::prelude::*;
+/// # use futures::StreamExt;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let mut events = client
+///     .watch_request(42)
+///     .poll_interval(Duration::from_secs(30));
+///
+/// while let Some(event) = events.next().await {
+///     match event? {
+///         RequestEvent::StatusChanged(change) => println!("{:?}", change.status),
+///         RequestEvent::Task(task) => println!("Scheduled at {}", task.start),
+///     }
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct RequestWatch<'a, C: Api> {
+    state: State<'a, C>,
+}
+
+pub fn new<C: Api>(client: &C, id: TaskRequestId) -> RequestWatch<'_, C> {
+    RequestWatch {
+        state: State::Pending {
+            client,
+            id,
+            interval: DEFAULT_POLL_INTERVAL,
+        },
+    }
+}
+
+impl<C: Api> RequestWatch<'_, C> {
+    /// The time waited between polls of the task request. Defaults to 10 seconds.
+    ///
+    /// The interval has no effect once the stream has been polled.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        if let State::Pending {
+            interval: current, ..
+        } = &mut self.state
+        {
+            *current = interval;
+        }
+        self
+    }
+}
+
+impl<C: Api> std::fmt::Debug for RequestWatch<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("RequestWatch");
+        if let State::Pending { id, interval, .. } = &self.state {
+            debug.field("id", id).field("interval", interval);
+        }
+        debug.finish_non_exhaustive()
+    }
+}
+
+impl<C: Api> Stream for RequestWatch<'_, C> {
+    type Item = Result<RequestEvent<C::Container<Task>>, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if matches!(this.state, State::Pending { .. }) {
+            let State::Pending {
+                client,
+                id,
+                interval,
+            } = std::mem::replace(&mut this.state, State::Polling)
+            else {
+                unreachable!()
+            };
+
+            this.state = State::Started(watch(client, id, interval));
+        }
+
+        let State::Started(stream) = &mut this.state else {
+            unreachable!("The stream is always started before it is polled")
+        };
+
+        stream.as_mut().poll_next(cx)
+    }
+}
+
+fn watch<C: Api>(
+    client: &C,
+    id: TaskRequestId,
+    interval: Duration,
+) -> BoxStream<'_, RequestEvent<C::Container<Task>>> {
+    Box::pin(stream! {
+        let mut last: Option<TaskStatus> = None;
+        let mut found_task = false;
+
+        loop {
+            let request = match client.get_request_by_id(id).await {
+                Ok(request) => request.into_inner(),
+                Err(error) => {
+                    yield Err(error);
+                    return;
+                }
+            };
+
+            for change in new_changes(last.as_ref(), &request.status_changes, &request.latest_status_change) {
+                yield Ok(RequestEvent::StatusChanged(StatusChange {
+                    previous: last.as_ref().map(|last| last.status),
+                    status: change.status,
+                    reason: change.reason.clone(),
+                    at: change.created,
+                }));
+                last = Some(change);
+            }
+
+            if !found_task {
+                match request.get_task(client).await {
+                    Ok(task) => {
+                        found_task = true;
+                        yield Ok(RequestEvent::Task(task));
+                    }
+                    Err(Error::MissingUri(_) | Error::NotFound { .. }) => {}
+                    Err(error) => {
+                        yield Err(error);
+                        return;
+                    }
+                }
+            }
+
+            if last.as_ref().is_some_and(|last| is_terminal(last.status)) {
+                return;
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    })
+}
+
+/// The status changes recorded after the last one seen, oldest first.
+///
+/// When nothing has been seen yet, only the latest status is returned.
+fn new_changes(
+    last: Option<&TaskStatus>,
+    changes: &[TaskStatus],
+    latest: &TaskStatus,
+) -> Vec<TaskStatus> {
+    let Some(last) = last else {
+        return vec![latest.clone()];
+    };
+
+    let mut changes: Vec<_> = changes
+        .iter()
+        .filter(|change| change.created > last.created)
+        .cloned()
+        .collect();
+    changes.sort_by_key(|change| change.created);
+    if changes.is_empty() && latest.created > last.created {
+        changes.push(latest.clone());
+    }
+
+    changes
+}
+
+fn is_terminal(status: TaskStatusType) -> bool {
+    use TaskStatusType::*;
+
+    matches!(
+        status,
+        Rejected
+            | Denied
+            | Cancelled
+            | SystemError
+            | Completed
+            | CompletedError
+            | PushedToCustomer
+            | ErrorPushToCustomer
+            | Invoiced
+            | Paid
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn status(status: TaskStatusType, created: OffsetDateTime) -> TaskStatus {
+        TaskStatus {
+            created,
+            status,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn only_the_latest_status_is_new_at_first() {
+        let received = status(TaskStatusType::Received, datetime!(2024-01-01 00:00 UTC));
+        let scheduled = status(TaskStatusType::Scheduled, datetime!(2024-01-01 00:01 UTC));
+
+        let changes = new_changes(None, &[received, scheduled.clone()], &scheduled);
+        assert_eq!(changes, [scheduled]);
+    }
+
+    #[test]
+    fn changes_after_the_last_are_new_in_order() {
+        let received = status(TaskStatusType::Received, datetime!(2024-01-01 00:00 UTC));
+        let scheduled = status(TaskStatusType::Scheduled, datetime!(2024-01-01 00:01 UTC));
+        let cancelled = status(TaskStatusType::Cancelled, datetime!(2024-01-01 00:02 UTC));
+
+        let changes = new_changes(
+            Some(&received),
+            &[cancelled.clone(), received.clone(), scheduled.clone()],
+            &cancelled,
+        );
+        assert_eq!(changes, [scheduled, cancelled.clone()]);
+
+        let changes = new_changes(Some(&cancelled), &[], &cancelled);
+        assert!(changes.is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+use std::path::PathBuf;
+
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use freedom_api::{
+    models::TaskStatusType, BandId, OverrideId, SatelliteId, SiteConfigurationId, SiteId, TaskId,
+    TaskRequestId,
+};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use crate::output::Format;
+
+/// Query and manage resources in Freedom.
+///
+/// Credentials are read from the `ATLAS_ENV`, `ATLAS_KEY`, and `ATLAS_SECRET` environment
+/// variables.
+#[derive(Debug, Parser)]
+#[command(name = "freedom", version)]
+pub struct Cli {
+    /// The format in which resources are written
+    #[arg(short, long, value_enum, default_value_t, global = true)]
+    pub format: Format,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Accounts
+    Accounts {
+        #[command(subcommand)]
+        action: ReadAction,
+    },
+    /// Satellites
+    Satellites {
+        #[command(subcommand)]
+        action: ManageAction,
+    },
+    /// Satellite bands
+    Bands {
+        #[command(subcommand)]
+        action: ManageAction,
+    },
+    /// Satellite configurations
+    Configurations {
+        #[command(subcommand)]
+        action: ManageAction,
+    },
+    /// Sites
+    Sites {
+        #[command(subcommand)]
+        action: ReadAction,
+    },
+    /// Task requests
+    Requests {
+        #[command(subcommand)]
+        action: RequestAction,
+    },
+    /// Tasks
+    Tasks {
+        #[command(subcommand)]
+        action: TaskAction,
+    },
+    /// Mint an FPS token for a band, and either a satellite or a site configuration
+    #[command(group(ArgGroup::new("target").required(true).args(["satellite", "site_configuration"])))]
+    Token {
+        /// The ID of the band
+        #[arg(long)]
+        band: BandId,
+        /// The ID of the satellite
+        #[arg(long)]
+        satellite: Option<SatelliteId>,
+        /// The ID of the site configuration
+        #[arg(long)]
+        site_configuration: Option<SiteConfigurationId>,
+    },
+    /// Download a file produced by a task
+    Download {
+        /// The ID of the task
+        task: TaskId,
+        /// The name of the file
+        file: String,
+        /// Where to write the file, `-` for standard output. Defaults to the name of the file in
+        /// the current directory. Partial downloads left at the path are resumed
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Actions for resources which may only be read
+#[derive(Debug, Subcommand)]
+pub enum ReadAction {
+    /// List all resources
+    List(ListArgs),
+    /// Show a single resource
+    Show(Lookup),
+}
+
+/// Actions for resources which may be read and deleted
+#[derive(Debug, Subcommand)]
+pub enum ManageAction {
+    /// List all resources
+    List(ListArgs),
+    /// Show a single resource
+    Show(Lookup),
+    /// Delete a resource
+    Delete {
+        /// The ID of the resource
+        id: i32,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RequestAction {
+    /// List task requests, optionally filtered. Freedom only supports some combinations of
+    /// filters
+    List {
+        /// Only list requests with this status, e.g. `SCHEDULED`
+        #[arg(long, value_parser = parse_status)]
+        status: Option<TaskStatusType>,
+        /// Only list requests for the satellite with this name
+        #[arg(long)]
+        satellite: Option<String>,
+        /// Only list requests targeting a time after this RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time, requires = "end")]
+        start: Option<OffsetDateTime>,
+        /// Only list requests targeting a time before this RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time, requires = "start")]
+        end: Option<OffsetDateTime>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a single task request
+    Show {
+        /// The ID of the task request
+        id: TaskRequestId,
+    },
+    /// Create a task request
+    Create(CreateRequest),
+    /// Delete a task request
+    Delete {
+        /// The ID of the task request
+        id: TaskRequestId,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TaskAction {
+    /// List tasks overlapping a time frame, or the upcoming tasks of today if none is provided
+    List {
+        /// The start of the time frame, as an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time, requires = "end")]
+        start: Option<OffsetDateTime>,
+        /// The end of the time frame, as an RFC 3339 timestamp
+        #[arg(long, value_parser = parse_time, requires = "start")]
+        end: Option<OffsetDateTime>,
+        #[command(flatten)]
+        list: ListArgs,
+    },
+    /// Show a single task
+    Show {
+        /// The ID of the task
+        id: TaskId,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    /// The maximum number of resources to list
+    #[arg(long)]
+    pub limit: Option<usize>,
+}
+
+/// A resource identified by either its ID or its name
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct Lookup {
+    /// The ID of the resource
+    pub id: Option<i32>,
+    /// The name of the resource
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+impl Lookup {
+    /// The name of the resource, when it is not identified by its ID
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+}
+
+impl ListArgs {
+    /// Drop the resources beyond the limit
+    pub fn truncate<T>(&self, mut items: Vec<T>) -> Vec<T> {
+        items.truncate(self.limit.unwrap_or(usize::MAX));
+        items
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct CreateRequest {
+    /// The type of task
+    #[arg(long = "type", value_enum, default_value_t = RequestType::Exact)]
+    pub typ: RequestType,
+    /// The hours of flexibility around the target time of a flex task
+    #[arg(long, required_if_eq_any([("typ", "before"), ("typ", "after"), ("typ", "around")]))]
+    pub hours_of_flex: Option<u8>,
+    /// The file transmitted by a test task
+    #[arg(long, required_if_eq("typ", "test"))]
+    pub test_file: Option<String>,
+    /// The target time of the task, as an RFC 3339 timestamp
+    #[arg(long, value_parser = parse_time)]
+    pub target: OffsetDateTime,
+    /// The duration of the task, in seconds
+    #[arg(long)]
+    pub duration: u64,
+    /// The minimum acceptable duration of the task, in seconds
+    #[arg(long)]
+    pub minimum_duration: Option<u64>,
+    /// The ID of the satellite
+    #[arg(long)]
+    pub satellite: SatelliteId,
+    /// The ID of the site
+    #[arg(long)]
+    pub site: SiteId,
+    /// The ID of the site configuration
+    #[arg(long)]
+    pub site_configuration: SiteConfigurationId,
+    /// The IDs of the target bands
+    #[arg(long = "band", required = true, value_delimiter = ',')]
+    pub bands: Vec<BandId>,
+    /// The ID of an override to apply to the task
+    #[arg(long = "override")]
+    pub override_id: Option<OverrideId>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RequestType {
+    Exact,
+    Test,
+    Before,
+    After,
+    Around,
+}
+
+fn parse_time(value: &str) -> Result<OffsetDateTime, String> {
+    OffsetDateTime::parse(value, &Rfc3339).map_err(|error| error.to_string())
+}
+
+fn parse_status(value: &str) -> Result<TaskStatusType, String> {
+    value
+        .to_ascii_uppercase()
+        .parse()
+        .map_err(|_| format!("Unknown task status '{value}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn verify_cli() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn flex_tasks_require_hours() {
+        let args = [
+            "freedom",
+            "requests",
+            "create",
+            "--type",
+            "before",
+            "--target",
+            "2030-01-01T12:00:00Z",
+            "--duration",
+            "120",
+            "--satellite",
+            "1",
+            "--site",
+            "2",
+            "--site-configuration",
+            "3",
+            "--band",
+            "4,5",
+        ];
+
+        assert!(Cli::try_parse_from(args).is_err());
+
+        let cli = Cli::try_parse_from(args.into_iter().chain(["--hours-of-flex", "2"])).unwrap();
+        let Command::Requests {
+            action: RequestAction::Create(request),
+        } = cli.command
+        else {
+            panic!("Expected a create request command");
+        };
+        assert_eq!(request.bands, [BandId::new(4), BandId::new(5)]);
+        assert_eq!(request.hours_of_flex, Some(2));
+    }
+
+    #[test]
+    fn lookup_takes_id_or_name() {
+        assert!(Cli::try_parse_from(["freedom", "satellites", "show", "710"]).is_ok());
+        assert!(Cli::try_parse_from(["freedom", "satellites", "show", "--name", "Foo"]).is_ok());
+        assert!(Cli::try_parse_from(["freedom", "satellites", "show"]).is_err());
+        assert!(
+            Cli::try_parse_from(["freedom", "satellites", "show", "710", "--name", "Foo"]).is_err()
+        );
+    }
+}
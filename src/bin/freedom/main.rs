@@ -0,0 +1,274 @@
+//! `freedom`, a command-line interface to Freedom built on the [`Api`] trait.
+//!
+//! Run `freedom --help` for the available commands.
+mod cli;
+mod output;
+
+use std::{fmt::Display, io::Write, path::Path, process::ExitCode};
+
+use clap::Parser;
+use freedom_api::{
+    error::{check_response, Error},
+    prelude::*,
+};
+use futures::{StreamExt, TryStreamExt};
+use reqwest::Method;
+
+use self::{
+    cli::{
+        Cli, Command, CreateRequest, ListArgs, ManageAction, ReadAction, RequestAction,
+        RequestType, TaskAction,
+    },
+    output::{write_all, write_one, Format},
+};
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match Client::from_env() {
+        Ok(client) => run(&client, cli).await,
+        Err(error) => {
+            Err(format!("Failed to load credentials from the environment: {error}").into())
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run<A: Api>(client: &A, cli: Cli) -> CliResult {
+    let format = cli.format;
+    let out = &mut std::io::stdout();
+
+    match cli.command {
+        Command::Accounts { action } => match action {
+            ReadAction::List(list) => {
+                write_all(out, format, &collect(client.get_accounts(), &list).await?)
+            }
+            ReadAction::Show(lookup) => {
+                let account = match lookup.id {
+                    Some(id) => client.get_account_by_id(id).await?,
+                    None => client.get_account_by_name(lookup.name()).await?,
+                };
+                write_one(out, format, &*account)
+            }
+        },
+        Command::Satellites { action } => match action {
+            ManageAction::List(list) => {
+                write_all(out, format, &collect(client.get_satellites(), &list).await?)
+            }
+            ManageAction::Show(lookup) => {
+                let satellite = match lookup.id {
+                    Some(id) => client.get_satellite_by_id(id).await?,
+                    None => client.get_satellite_by_name(lookup.name()).await?,
+                };
+                write_one(out, format, &*satellite)
+            }
+            ManageAction::Delete { id } => {
+                check_response(&Method::DELETE, client.delete_satellite(id).await?).await?;
+                deleted(out, "satellite", id)
+            }
+        },
+        Command::Bands { action } => match action {
+            ManageAction::List(list) => write_all(
+                out,
+                format,
+                &collect(client.get_satellite_bands(), &list).await?,
+            ),
+            ManageAction::Show(lookup) => {
+                let band = match lookup.id {
+                    Some(id) => client.get_satellite_band_by_id(id).await?,
+                    None => client.get_satellite_band_by_name(lookup.name()).await?,
+                };
+                write_one(out, format, &*band)
+            }
+            ManageAction::Delete { id } => {
+                check_response(&Method::DELETE, client.delete_band_details(id).await?).await?;
+                deleted(out, "band", id)
+            }
+        },
+        Command::Configurations { action } => match action {
+            ManageAction::List(list) => {
+                let configurations = collect(client.get_satellite_configurations(), &list).await?;
+                write_all(out, format, &configurations)
+            }
+            ManageAction::Show(lookup) => {
+                let configuration = match lookup.id {
+                    Some(id) => client.get_satellite_configuration_by_id(id).await?,
+                    None => {
+                        client
+                            .get_satellite_configuration_by_name(lookup.name())
+                            .await?
+                    }
+                };
+                write_one(out, format, &*configuration)
+            }
+            ManageAction::Delete { id } => {
+                let response = client.delete_satellite_configuration(id).await?;
+                check_response(&Method::DELETE, response).await?;
+                deleted(out, "satellite configuration", id)
+            }
+        },
+        Command::Sites { action } => match action {
+            ReadAction::List(list) => {
+                write_all(out, format, &collect(client.get_sites(), &list).await?)
+            }
+            ReadAction::Show(lookup) => {
+                let site = match lookup.id {
+                    Some(id) => client.get_site_by_id(id).await?,
+                    None => client.get_site_by_name(lookup.name()).await?,
+                };
+                write_one(out, format, &*site)
+            }
+        },
+        Command::Requests { action } => match action {
+            RequestAction::List {
+                status,
+                satellite,
+                start,
+                end,
+                list,
+            } => {
+                let mut query = RequestQuery::new();
+                if let Some(status) = status {
+                    query = query.status(status);
+                }
+                if let Some(name) = satellite {
+                    query = query.satellite_name(name);
+                }
+                if let Some((start, end)) = start.zip(end) {
+                    query = query.target_date_between(TimeWindow::new(start, end)?);
+                }
+                let requests = collect(client.get_requests_matching(&query)?, &list).await?;
+                write_all(out, format, &requests)
+            }
+            RequestAction::Show { id } => {
+                write_one(out, format, &*client.get_request_by_id(id).await?)
+            }
+            RequestAction::Create(request) => {
+                let created = create_request(client, request).await?;
+                write_one(out, format, &*created)
+            }
+            RequestAction::Delete { id } => {
+                check_response(&Method::DELETE, client.delete_task_request(id).await?).await?;
+                deleted(out, "task request", id)
+            }
+        },
+        Command::Tasks { action } => match action {
+            TaskAction::List { start, end, list } => {
+                let tasks = match start.zip(end) {
+                    Some((start, end)) => {
+                        let query = TaskQuery::new().pass_overlapping(TimeWindow::new(start, end)?);
+                        collect(client.get_tasks_matching(&query)?, &list).await?
+                    }
+                    None => list.truncate(client.get_tasks_upcoming_today().await?.into_inner()),
+                };
+                write_all(out, format, &tasks)
+            }
+            TaskAction::Show { id } => write_one(out, format, &*client.get_task_by_id(id).await?),
+        },
+        Command::Token {
+            band,
+            satellite,
+            site_configuration,
+        } => {
+            let token = match (satellite, site_configuration) {
+                (Some(satellite), _) => client.new_token_by_satellite_id(band, satellite).await?,
+                (None, Some(configuration)) => {
+                    client
+                        .new_token_by_site_configuration_id(band, configuration)
+                        .await?
+                }
+                (None, None) => unreachable!("A satellite or a site configuration is required"),
+            };
+
+            match format {
+                Format::Json => writeln!(out, "{}", serde_json::json!({ "token": token }))?,
+                Format::Table | Format::Csv => writeln!(out, "{token}")?,
+            }
+            Ok(())
+        }
+        Command::Download { task, file, output } => {
+            let download = client.download_file_by_task_id_and_name(task, &file);
+
+            match output {
+                Some(path) if path == Path::new("-") => {
+                    download.to_writer(&mut tokio::io::stdout()).await?;
+                }
+                path => {
+                    let path = path.unwrap_or_else(|| {
+                        Path::new(&file)
+                            .file_name()
+                            .map(Into::into)
+                            .unwrap_or_else(|| file.clone().into())
+                    });
+                    let size = download.to_path(&path).await?;
+                    eprintln!("Wrote {size} bytes to {}", path.display());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Collect the items of a paginated stream, up to the limit of the listing
+async fn collect<C, T>(stream: PaginatedStream<'_, C>, list: &ListArgs) -> Result<Vec<T>, Error>
+where
+    C: Container<T>,
+{
+    stream
+        .take(list.limit.unwrap_or(usize::MAX))
+        .map_ok(Container::into_inner)
+        .try_collect()
+        .await
+}
+
+async fn create_request<A: Api>(
+    client: &A,
+    request: CreateRequest,
+) -> Result<A::Container<TaskRequest>, Error> {
+    // Each type of task produces a differently typed builder, so the remaining steps are repeated
+    // for each of them
+    macro_rules! send {
+        ($builder:expr) => {{
+            let mut builder = $builder
+                .target_time_utc(request.target)
+                .task_duration(request.duration)
+                .satellite_id(request.satellite)
+                .site_id(request.site)
+                .site_configuration_id(request.site_configuration)
+                .band_ids(request.bands);
+            if let Some(duration) = request.minimum_duration {
+                builder = builder.task_minimum_duration(duration);
+            }
+            if let Some(id) = request.override_id {
+                builder = builder.override_id(id);
+            }
+            builder.send().await
+        }};
+    }
+
+    // Clap ensures the hours of flex and test file are present for the types which require them
+    let hours_of_flex = request.hours_of_flex.unwrap_or_default();
+    let builder = client.new_task_request();
+    match request.typ {
+        RequestType::Exact => send!(builder.exact_task()),
+        RequestType::Test => send!(builder.test_task(request.test_file.unwrap_or_default())),
+        RequestType::Before => send!(builder.flex_task_before(hours_of_flex)),
+        RequestType::After => send!(builder.flex_task_after(hours_of_flex)),
+        RequestType::Around => send!(builder.flex_task_around(hours_of_flex)),
+    }
+}
+
+fn deleted(out: &mut impl Write, kind: &str, id: impl Display) -> CliResult {
+    writeln!(out, "Deleted {kind} {id}")?;
+    Ok(())
+}
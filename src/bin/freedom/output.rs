@@ -0,0 +1,306 @@
+use std::{collections::HashMap, io::Write};
+
+use clap::ValueEnum;
+use freedom_api::models::{
+    Account, Band, Satellite, SatelliteConfiguration, Site, Task, TaskRequest,
+};
+use serde::Serialize;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+use url::Url;
+
+/// The format in which resources are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// A table with aligned columns, for reading in a terminal
+    #[default]
+    Table,
+    /// The resources as returned by Freedom, as pretty printed JSON
+    Json,
+    /// The same columns as the table, as comma separated values
+    Csv,
+}
+
+/// A resource which may be written as a row of a table
+pub trait Record: Serialize {
+    /// The column headers of the table
+    const HEADERS: &'static [&'static str];
+
+    /// The values of the row, one for each header
+    fn fields(&self) -> Vec<String>;
+}
+
+/// Write a listing of resources in the provided format.
+pub fn write_all<T: Record>(
+    out: &mut impl Write,
+    format: Format,
+    items: &[T],
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        Format::Table => write_table(out, T::HEADERS, items.iter().map(Record::fields)),
+        Format::Csv => write_csv(out, T::HEADERS, items.iter().map(Record::fields)),
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *out, items)?;
+            writeln!(out)?;
+            Ok(())
+        }
+    }
+}
+
+/// Write a single resource in the provided format.
+///
+/// Unlike [`write_all`], the JSON output is the resource itself rather than an array.
+pub fn write_one<T: Record>(
+    out: &mut impl Write,
+    format: Format,
+    item: &T,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *out, item)?;
+            writeln!(out)?;
+            Ok(())
+        }
+        _ => write_all(out, format, std::slice::from_ref(item)),
+    }
+}
+
+fn write_table(
+    out: &mut impl Write,
+    headers: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rows: Vec<_> = rows.collect();
+    let mut widths: Vec<_> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in &rows {
+        for (width, field) in widths.iter_mut().zip(row) {
+            *width = (*width).max(field.chars().count());
+        }
+    }
+
+    let headers = headers.iter().map(|header| header.to_string()).collect();
+    for row in std::iter::once(&headers).chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(field, width)| format!("{field:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        writeln!(out, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+fn write_csv(
+    out: &mut impl Write,
+    headers: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let headers = headers.iter().map(|header| header.to_string()).collect();
+    for row in std::iter::once(headers).chain(rows) {
+        let line = row
+            .iter()
+            .map(|field| escape_csv(field))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        writeln!(out, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Quote the field if it contains a delimiter, quote, or line break, as described in RFC 4180
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// The ID at the end of the resource's `self` link
+fn id(links: &HashMap<String, Url>) -> String {
+    links
+        .get("self")
+        .and_then(|url| url.path_segments()?.next_back())
+        .unwrap_or_default()
+        .to_owned()
+}
+
+fn timestamp(time: &OffsetDateTime) -> String {
+    time.format(&Rfc3339).unwrap_or_default()
+}
+
+impl Record for Account {
+    const HEADERS: &'static [&'static str] = &["ID", "NAME", "VERIFIED", "CREATED"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            self.name.clone(),
+            self.verified.to_string(),
+            timestamp(&self.created),
+        ]
+    }
+}
+
+impl Record for Satellite {
+    const HEADERS: &'static [&'static str] = &["ID", "NAME", "NORAD ID", "ACCOUNT", "DESCRIPTION"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            self.name.clone(),
+            self.norad_cat_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            self.account_name.clone(),
+            self.description.clone(),
+        ]
+    }
+}
+
+impl Record for Band {
+    const HEADERS: &'static [&'static str] = &[
+        "ID",
+        "NAME",
+        "TYPE",
+        "FREQUENCY (MHZ)",
+        "BANDWIDTH (MHZ)",
+        "ACCOUNT",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            self.name.clone(),
+            self.typ
+                .as_ref()
+                .map(|typ| typ.as_ref().to_owned())
+                .unwrap_or_default(),
+            self.frequency_mghz.to_string(),
+            self.default_band_width_mghz.to_string(),
+            self.account_name.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl Record for SatelliteConfiguration {
+    const HEADERS: &'static [&'static str] = &["ID", "NAME", "ORBIT", "ACCOUNT", "NOTES"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            self.name.clone(),
+            self.orbit.clone(),
+            self.account_name.clone(),
+            self.notes.clone(),
+        ]
+    }
+}
+
+impl Record for Site {
+    const HEADERS: &'static [&'static str] = &["ID", "NAME", "BASE FPS PORT", "DESCRIPTION"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            self.name.clone(),
+            self.base_fps_port.to_string(),
+            self.description.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+impl Record for TaskRequest {
+    const HEADERS: &'static [&'static str] = &[
+        "ID",
+        "TYPE",
+        "STATUS",
+        "TARGET DATE",
+        "EARLIEST START",
+        "LATEST START",
+        "DURATION",
+    ];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            self.task_type.as_ref().to_owned(),
+            self.latest_status_change.status.as_ref().to_owned(),
+            timestamp(&self.target_date),
+            timestamp(&self.earliest_start),
+            timestamp(&self.latest_start),
+            self.duration.to_string(),
+        ]
+    }
+}
+
+impl Record for Task {
+    const HEADERS: &'static [&'static str] = &["ID", "START", "END", "DURATION", "FILES"];
+
+    fn fields(&self) -> Vec<String> {
+        vec![
+            id(&self.links),
+            timestamp(&self.start),
+            timestamp(&self.end),
+            self.duration_in_seconds.to_string(),
+            self.file_results.join(" "),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Row(&'static str, &'static str);
+
+    impl Record for Row {
+        const HEADERS: &'static [&'static str] = &["NAME", "VALUE"];
+
+        fn fields(&self) -> Vec<String> {
+            vec![self.0.to_owned(), self.1.to_owned()]
+        }
+    }
+
+    fn render(format: Format, rows: &[Row]) -> String {
+        let mut out = Vec::new();
+        write_all(&mut out, format, rows).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn table_columns_are_aligned() {
+        let rows = [Row("FooBar 6", "1"), Row("X", "22")];
+
+        assert_eq!(
+            render(Format::Table, &rows),
+            "NAME      VALUE\nFooBar 6  1\nX         22\n"
+        );
+    }
+
+    #[test]
+    fn csv_fields_are_escaped() {
+        let rows = [Row("plain", "with, comma"), Row("with \"quote\"", "")];
+
+        assert_eq!(
+            render(Format::Csv, &rows),
+            "NAME,VALUE\nplain,\"with, comma\"\n\"with \"\"quote\"\"\",\n"
+        );
+    }
+
+    #[test]
+    fn json_is_an_array() {
+        let rows = [Row("a", "b")];
+        let value: serde_json::Value = serde_json::from_str(&render(Format::Json, &rows)).unwrap();
+
+        assert_eq!(value, serde_json::json!([["a", "b"]]));
+    }
+}
@@ -1,4 +1,10 @@
-use std::sync::Arc;
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use bytes::Bytes;
 use freedom_config::Config;
@@ -11,6 +17,9 @@ use crate::{
     Client,
 };
 
+/// The default number of responses held by the cache
+const DEFAULT_MAX_CAPACITY: u64 = 10_000;
+
 /// An asynchronous `Client` for interfacing with the ATLAS freedom API, which implements query
 /// caching.
 ///
@@ -20,10 +29,43 @@ use crate::{
 /// As a result, the items which are returned to the caller are wrapped in [`Arc`](std::sync::Arc).
 /// This makes cloning items out of the cache extremely cheap, regardless of the object's actual
 /// size.
+///
+/// # Cache Behavior
+///
+/// + Only successful responses are cached, error responses are always fetched from Freedom.
+/// + Concurrent requests for the same URL are coalesced, such that only one of them results in a
+///   request to Freedom, the others wait for and share its response.
+/// + Any `POST`, `PUT`, `PATCH`, or `DELETE` issued through the client evicts the cached responses
+///   for the mutated resource. For instance deleting `satellites/710` evicts both `satellites/710`,
+///   and every page of the `satellites` listing.
+/// + A response fetched while a write was being issued is returned but not cached, since it may
+///   predate the write.
+/// + Changing the configuration through [`config_mut`](Api::config_mut) clears the cache, since the
+///   cached responses may belong to another environment.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use freedom_api::prelude::*;
+/// let config = Config::builder()
+///     .environment(Test)
+///     .key("foo")
+///     .secret("bar")
+///     .build()
+///     .unwrap();
+///
+/// let client = CachingClient::builder(Client::from_config(config))
+///     .time_to_live(Duration::from_secs(60 * 5))
+///     .max_capacity(500)
+///     .build();
+/// ```
 #[derive(Clone, Debug)]
 pub struct CachingClient {
     pub(crate) inner: Client,
     pub(crate) cache: moka::future::Cache<Url, (Bytes, StatusCode)>,
+    /// Incremented by every invalidation, so that loads which overlap one are not cached
+    generation: Arc<AtomicU64>,
 }
 
 impl PartialEq for CachingClient {
@@ -32,6 +74,122 @@ impl PartialEq for CachingClient {
     }
 }
 
+impl CachingClient {
+    /// Construct a caching client wrapping the provided client, using the default cache settings.
+    ///
+    /// The default cache holds up to 10,000 responses, and never expires them based on time.
+    pub fn new(client: Client) -> Self {
+        Self::builder(client).build()
+    }
+
+    /// Construct a builder for configuring the cache of a caching client.
+    pub fn builder(client: Client) -> CachingClientBuilder {
+        CachingClientBuilder {
+            client,
+            max_capacity: DEFAULT_MAX_CAPACITY,
+            time_to_live: None,
+            time_to_idle: None,
+        }
+    }
+
+    /// Discard every response currently held in the cache
+    pub fn invalidate_all(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.cache.invalidate_all();
+    }
+
+    /// Evict all the cached responses belonging to the same resource as the provided URL.
+    ///
+    /// The resource is determined by the first path segment following the environment's entrypoint,
+    /// so `satellites/710` and `satellites?page=2` belong to the same resource.
+    fn invalidate_resource(&self, url: &Url) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+
+        let base = self.config().environment().freedom_entrypoint();
+        let Some(resource) = resource_root(&base, url) else {
+            return;
+        };
+
+        let predicate = move |key: &Url, _: &(Bytes, StatusCode)| {
+            resource_root(&base, key).as_deref() == Some(resource.as_str())
+        };
+
+        if let Err(error) = self.cache.invalidate_entries_if(predicate) {
+            tracing::warn!(%error, "Failed to register cache invalidation, clearing the cache");
+            self.cache.invalidate_all();
+        }
+    }
+}
+
+/// A builder for configuring the cache of a [`CachingClient`]
+#[derive(Clone, Debug)]
+pub struct CachingClientBuilder {
+    client: Client,
+    max_capacity: u64,
+    time_to_live: Option<Duration>,
+    time_to_idle: Option<Duration>,
+}
+
+impl CachingClientBuilder {
+    /// The maximum number of responses held in the cache at any one time
+    pub fn max_capacity(mut self, max_capacity: u64) -> Self {
+        self.max_capacity = max_capacity;
+        self
+    }
+
+    /// The duration a response remains in the cache after being fetched from Freedom
+    pub fn time_to_live(mut self, duration: Duration) -> Self {
+        self.time_to_live = Some(duration);
+        self
+    }
+
+    /// The duration a response remains in the cache after it was last read
+    pub fn time_to_idle(mut self, duration: Duration) -> Self {
+        self.time_to_idle = Some(duration);
+        self
+    }
+
+    pub fn build(self) -> CachingClient {
+        let mut cache = moka::future::Cache::builder()
+            .max_capacity(self.max_capacity)
+            .support_invalidation_closures();
+
+        if let Some(duration) = self.time_to_live {
+            cache = cache.time_to_live(duration);
+        }
+        if let Some(duration) = self.time_to_idle {
+            cache = cache.time_to_idle(duration);
+        }
+
+        CachingClient {
+            inner: self.client,
+            cache: cache.build(),
+            generation: Arc::default(),
+        }
+    }
+}
+
+/// The reasons a response is not placed into the cache
+enum Uncached {
+    Status(Bytes, StatusCode),
+    Stale(Bytes, StatusCode),
+    Error(Error),
+}
+
+fn resource_root(base: &Url, url: &Url) -> Option<String> {
+    if base.origin() != url.origin() {
+        return None;
+    }
+
+    let prefix = base.path().trim_end_matches('/');
+    let path = url.path().strip_prefix(prefix)?.trim_start_matches('/');
+
+    path.split('/')
+        .next()
+        .filter(|segment| !segment.is_empty())
+        .map(String::from)
+}
+
 impl<T: Value> Container<T> for Arc<T> {
     fn into_inner(self) -> T {
         std::sync::Arc::<T>::unwrap_or_clone(self)
@@ -42,32 +200,80 @@ impl Api for CachingClient {
     type Container<T: Value> = Arc<T>;
 
     async fn delete(&self, url: Url) -> Result<Response, Error> {
-        self.inner.delete(url).await
+        let response = self.inner.delete(url.clone()).await;
+        self.invalidate_resource(&url);
+
+        response
     }
 
     async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), Error> {
         let client = &self.inner;
         let url_clone = url.clone();
+        let generation = self.generation.load(Ordering::SeqCst);
+        let invalidated = || self.generation.load(Ordering::SeqCst) != generation;
 
         let fut = async {
-            let (body, status) = client.get(url_clone).await?;
-
-            Ok::<_, Error>((body, status))
+            match client.get(url_clone).await {
+                Ok((body, status)) if invalidated() => Err(Uncached::Stale(body, status)),
+                Ok((body, status)) if status.is_success() => Ok((body, status)),
+                Ok((body, status)) => Err(Uncached::Status(body, status)),
+                Err(error) => Err(Uncached::Error(error)),
+            }
         };
 
-        let (body, status) = match self.cache.get(&url).await {
-            Some(out) => out,
-            None => fut.await?,
-        };
+        // The cache's initialization future is not `Sync`, which is required of all `Api` futures
+        let cached = sync_wrapper::SyncFuture::new(self.cache.try_get_with(url.clone(), fut));
+        let cached = cached.await;
+
+        // An invalidation may have been issued between the load completing and its insertion
+        if invalidated() {
+            self.cache.invalidate(&url).await;
+        }
 
-        Ok((body, status))
+        match cached {
+            Ok(out) => Ok(out),
+            Err(uncached) => match &*uncached {
+                Uncached::Status(body, status) | Uncached::Stale(body, status) => {
+                    Ok((body.clone(), *status))
+                }
+                Uncached::Error(error) => Err(error.clone()),
+            },
+        }
+    }
+
+    async fn get_stream(&self, url: Url, offset: u64) -> Result<Response, Error> {
+        // Streamed responses are typically large files, which are not worth caching
+        self.inner.get_stream(url, offset).await
     }
 
     async fn post<S>(&self, url: Url, msg: S) -> Result<Response, Error>
     where
         S: serde::Serialize + Send + Sync,
     {
-        self.inner.post(url, msg).await
+        let response = self.inner.post(url.clone(), msg).await;
+        self.invalidate_resource(&url);
+
+        response
+    }
+
+    async fn put<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let response = self.inner.put(url.clone(), msg).await;
+        self.invalidate_resource(&url);
+
+        response
+    }
+
+    async fn patch<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let response = self.inner.patch(url.clone(), msg).await;
+        self.invalidate_resource(&url);
+
+        response
     }
 
     fn config(&self) -> &Config {
@@ -75,6 +281,256 @@ impl Api for CachingClient {
     }
 
     fn config_mut(&mut self) -> &mut Config {
+        self.invalidate_all();
         self.inner.config_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use freedom_config::{Env, Test};
+    use httpmock::{
+        Method::{DELETE, GET, PUT},
+        MockServer,
+    };
+
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct MockEnv(Url);
+
+    impl AsRef<str> for MockEnv {
+        fn as_ref(&self) -> &str {
+            "MockEnv"
+        }
+    }
+
+    impl Env for MockEnv {
+        fn from_str(_val: &str) -> Option<Self> {
+            None
+        }
+
+        fn fps_host(&self) -> &str {
+            "localhost"
+        }
+
+        fn freedom_entrypoint(&self) -> Url {
+            self.0.clone()
+        }
+    }
+
+    fn caching_client(server: &MockServer) -> CachingClient {
+        let url = Url::parse(&server.base_url()).unwrap();
+        let config = Config::builder()
+            .environment(MockEnv(url))
+            .key("foo")
+            .secret("bar")
+            .build()
+            .unwrap();
+
+        CachingClient::new(Client::from_config(config))
+    }
+
+    #[test]
+    fn resource_root_of_url() {
+        let base = Url::parse("https://example.com/api/").unwrap();
+        let root = |url: &str| resource_root(&base, &Url::parse(url).unwrap());
+
+        assert_eq!(
+            root("https://example.com/api/satellites/710").as_deref(),
+            Some("satellites")
+        );
+        assert_eq!(
+            root("https://example.com/api/satellites?page=2").as_deref(),
+            Some("satellites")
+        );
+        assert_eq!(root("https://example.com/api/"), None);
+        assert_eq!(root("https://other.com/api/satellites"), None);
+    }
+
+    #[tokio::test]
+    async fn successful_responses_are_cached() {
+        let server = MockServer::start();
+        let client = caching_client(&server);
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/satellites/710");
+            then.body(b"cached").status(200);
+        });
+
+        let url = client.path_to_url("satellites/710");
+        for _ in 0..3 {
+            let (body, status) = client.get(url.clone()).await.unwrap();
+            assert_eq!(body, "cached".as_bytes());
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn error_responses_are_not_cached() {
+        let server = MockServer::start();
+        let client = caching_client(&server);
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/satellites/710");
+            then.body(b"NOPE").status(404);
+        });
+
+        let url = client.path_to_url("satellites/710");
+        for _ in 0..2 {
+            let (body, status) = client.get(url.clone()).await.unwrap();
+            assert_eq!(body, "NOPE".as_bytes());
+            assert_eq!(status, StatusCode::NOT_FOUND);
+        }
+
+        mock.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_are_coalesced() {
+        let server = MockServer::start();
+        let client = caching_client(&server);
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/satellites");
+            then.body(b"[]")
+                .status(200)
+                .delay(Duration::from_millis(100));
+        });
+
+        let url = client.path_to_url("satellites");
+        let responses = futures::future::join_all((0..8).map(|_| client.get(url.clone()))).await;
+
+        assert!(responses.iter().all(Result::is_ok));
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn delete_invalidates_resource() {
+        let server = MockServer::start();
+        let client = caching_client(&server);
+        let item = server.mock(|when, then| {
+            when.method(GET).path("/satellites/710");
+            then.body(b"{}").status(200);
+        });
+        let listing = server.mock(|when, then| {
+            when.method(GET).path("/satellites");
+            then.body(b"[]").status(200);
+        });
+        let bands = server.mock(|when, then| {
+            when.method(GET).path("/satellite_bands");
+            then.body(b"[]").status(200);
+        });
+        server.mock(|when, then| {
+            when.method(DELETE).path("/satellites/710");
+            then.status(204);
+        });
+
+        let item_url = client.path_to_url("satellites/710");
+        let listing_url = client.path_to_url("satellites");
+        let bands_url = client.path_to_url("satellite_bands");
+        for url in [&item_url, &listing_url, &bands_url] {
+            client.get(url.clone()).await.unwrap();
+        }
+
+        client.delete_satellite(710).await.unwrap();
+
+        for url in [&item_url, &listing_url, &bands_url] {
+            client.get(url.clone()).await.unwrap();
+        }
+
+        item.assert_hits(2);
+        listing.assert_hits(2);
+        bands.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn put_invalidates_resource() {
+        let server = MockServer::start();
+        let client = caching_client(&server);
+        let item = server.mock(|when, then| {
+            when.method(GET).path("/satellites/710");
+            then.body(b"{}").status(200);
+        });
+        server.mock(|when, then| {
+            when.method(PUT).path("/satellites/710");
+            then.status(200);
+        });
+
+        let url = client.path_to_url("satellites/710");
+        client.get(url.clone()).await.unwrap();
+        client
+            .put(url.clone(), serde_json::json!({}))
+            .await
+            .unwrap();
+        client.get(url).await.unwrap();
+
+        item.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn loads_overlapping_a_write_are_not_cached() {
+        let server = MockServer::start();
+        let client = caching_client(&server);
+        let item = server.mock(|when, then| {
+            when.method(GET).path("/satellites/710");
+            then.body(b"{}")
+                .status(200)
+                .delay(Duration::from_millis(200));
+        });
+        server.mock(|when, then| {
+            when.method(DELETE).path("/satellites/710");
+            then.status(204);
+        });
+
+        let url = client.path_to_url("satellites/710");
+        let read = client.get(url.clone());
+        let write = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            client.delete_satellite(710).await
+        };
+        let (read, write) = tokio::join!(read, write);
+        read.unwrap();
+        write.unwrap();
+
+        client.get(url).await.unwrap();
+        item.assert_hits(2);
+    }
+
+    #[tokio::test]
+    async fn changing_the_config_clears_the_cache() {
+        let server = MockServer::start();
+        let mut client = caching_client(&server);
+        let item = server.mock(|when, then| {
+            when.method(GET).path("/satellites/710");
+            then.body(b"{}").status(200);
+        });
+
+        let url = client.path_to_url("satellites/710");
+        client.get(url.clone()).await.unwrap();
+        client.config_mut();
+        client.get(url).await.unwrap();
+
+        item.assert_hits(2);
+    }
+
+    #[test]
+    fn builder_applies_expiry() {
+        let config = Config::builder()
+            .environment(Test)
+            .key("foo")
+            .secret("bar")
+            .build()
+            .unwrap();
+
+        let client = CachingClient::builder(Client::from_config(config))
+            .time_to_live(Duration::from_secs(30))
+            .time_to_idle(Duration::from_secs(10))
+            .max_capacity(5)
+            .build();
+
+        let policy = client.cache.policy();
+        assert_eq!(policy.time_to_live(), Some(Duration::from_secs(30)));
+        assert_eq!(policy.time_to_idle(), Some(Duration::from_secs(10)));
+        assert_eq!(policy.max_capacity(), Some(5));
+    }
+}
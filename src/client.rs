@@ -1,18 +1,43 @@
+use std::{sync::Arc, time::Duration};
+
 use bytes::Bytes;
 use freedom_config::Config;
-use reqwest::{Response, StatusCode};
-use url::Url;
+use reqwest::{
+    header::{HeaderMap, ACCEPT, RANGE},
+    Certificate, Proxy, Response, StatusCode,
+};
+use url::{Origin, Url};
+
+use crate::{
+    api::{Api, Inner, Value},
+    error::Error,
+    rate_limit::{Permit, RateLimit, RateLimiter},
+    retry::{self, RetryPolicy},
+};
 
-use crate::api::{Api, Inner, Value};
+/// The user agent sent by clients unless another is configured
+const USER_AGENT: &str = concat!("freedom-api/", env!("CARGO_PKG_VERSION"));
 
 /// An asynchronous `Client` for interfacing with the ATLAS freedom API.
 ///
 /// The client is primarily defined based on it's [`Env`](crate::config::Env)
 /// and it's credentials (username and password).
+///
+/// Credentials are only ever sent to the origin of the environment's
+/// [`freedom_entrypoint`](crate::config::Env::freedom_entrypoint), and to any origin allowed with
+/// [`Client::with_allowed_origin`]. Requests to any other origin, such as a link in a response
+/// pointing elsewhere, fail with [`Error::CrossOrigin`] without being sent.
+///
+/// Timeouts, proxies, and other connection options are configured with [`Client::builder`].
 #[derive(Clone, Debug)]
 pub struct Client {
     pub(crate) config: Config,
     pub(crate) client: reqwest::Client,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) allowed_origins: Vec<Origin>,
+    pub(crate) limiter: Option<Arc<RateLimiter>>,
+    #[cfg(feature = "tower")]
+    pub(crate) service: Option<crate::middleware::BoxService>,
 }
 
 impl PartialEq for Client {
@@ -38,13 +63,152 @@ impl Client {
     ///
     /// assert_eq!(client.config().key(), "foo");
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the TLS backend cannot be initialized, as with [`reqwest::Client::new`]
     pub fn from_config(config: Config) -> Self {
-        Self {
+        Self::builder(config)
+            .build()
+            .expect("The default HTTP client is always valid")
+    }
+
+    /// Construct a builder for a client of the provided Freedom config, for configuring
+    /// timeouts, proxies, and other connection options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::{sync::Arc, time::Duration};
+    /// # use freedom_api::prelude::*;
+    /// # let config = Config::builder()
+    /// #     .environment(Test)
+    /// #     .key("foo")
+    /// #     .secret("bar")
+    /// #     .build()
+    /// #     .unwrap();
+    /// let client = Client::builder(config)
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .timeout(Duration::from_secs(60))
+    ///     .proxy(reqwest::Proxy::https("http://proxy.example.com:3128")?)
+    ///     .user_agent("ground-ops/1.2.0")
+    ///     .build()?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn builder(config: Config) -> ClientBuilder {
+        ClientBuilder {
             config,
-            client: reqwest::Client::new(),
+            client: None,
+            connect_timeout: None,
+            read_timeout: None,
+            timeout: None,
+            proxies: Vec::new(),
+            root_certificates: Vec::new(),
+            default_headers: HeaderMap::new(),
+            user_agent: None,
+            retry: RetryPolicy::default(),
+            rate_limit: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+
+    /// Construct an API client which sends its requests through the provided
+    /// [`tower::Service`].
+    ///
+    /// Requests are passed to the service as is: credentials, retries, and timeouts are left to
+    /// its layers, such as those of the [`middleware`](crate::middleware) module, so the client's
+    /// [`RetryPolicy`] and allowed origins have no effect. A [`RateLimit`] still applies.
+    ///
+    /// The service returns each response with its body already collected into [`Bytes`], so
+    /// downloads made with [`get_stream`](Api::get_stream) and the methods built on it, such as
+    /// [`download_file_by_task_id_and_name`](Api::download_file_by_task_id_and_name), hold the
+    /// entire file in memory before the first chunk is returned. Use a client which sends its
+    /// requests with `reqwest`, such as one built with [`Client::builder`], to stream large files.
+    ///
+    /// See the [`middleware`](crate::middleware) module for an example.
+    #[cfg(feature = "tower")]
+    pub fn from_service<S>(config: Config, service: S) -> Self
+    where
+        S: tower::Service<http::Request<Bytes>, Response = http::Response<Bytes>>
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+        S::Error: Into<crate::middleware::BoxError>,
+        S::Future: Send + 'static,
+    {
+        use tower::ServiceExt;
+
+        let service = service.map_err(Into::into);
+        Self {
+            service: Some(tower::util::BoxCloneSyncService::new(service)),
+            ..Self::from_config(config)
         }
     }
 
+    /// Replace the policy used to retry requests which failed due to transient errors.
+    ///
+    /// By default, clients use [`RetryPolicy::default`].
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Returns the policy used to retry requests which failed due to transient errors.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Limit the requests sent by the client and all of its future clones.
+    ///
+    /// Clones made before the limit was set are not affected. By default, requests are not
+    /// limited.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.limiter = Some(Arc::new(RateLimiter::new(limit)));
+        self
+    }
+
+    /// Returns the limit placed on the requests sent by the client, if any.
+    pub fn rate_limit(&self) -> Option<&RateLimit> {
+        self.limiter.as_deref().map(RateLimiter::limit)
+    }
+
+    /// Allow credentials to be sent to the origin of the provided URL, in addition to the origin
+    /// of the environment.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use freedom_api::prelude::*;
+    /// # let config = Config::builder()
+    /// #     .environment(Test)
+    /// #     .key("foo")
+    /// #     .secret("bar")
+    /// #     .build()
+    /// #     .unwrap();
+    /// let mirror = "https://mirror.example.com/api/".parse()?;
+    /// let client = Client::from_config(config).with_allowed_origin(&mirror);
+    ///
+    /// assert_eq!(client.allowed_origins(), [mirror.origin()]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_allowed_origin(mut self, url: &Url) -> Self {
+        self.allowed_origins.push(url.origin());
+        self
+    }
+
+    /// Returns the origins to which credentials may be sent, besides that of the environment.
+    pub fn allowed_origins(&self) -> &[Origin] {
+        &self.allowed_origins
+    }
+
+    /// Whether credentials may be sent to the URL
+    fn is_trusted(&self, url: &Url) -> bool {
+        let origin = url.origin();
+        origin == self.config.environment().freedom_entrypoint().origin()
+            || self.allowed_origins.contains(&origin)
+    }
+
     /// A convenience method for constructing an FPS client from environment variables.
     ///
     /// This function expects the following environment variables:
@@ -56,18 +220,249 @@ impl Client {
         let config = Config::from_env()?;
         Ok(Self::from_config(config))
     }
+
+    /// Wait until the rate limit, if any, allows another request to be sent
+    async fn wait_for_permit(&self, method: &reqwest::Method, url: &Url) -> Option<Permit> {
+        let limiter = self.limiter.as_deref()?;
+        let (permit, waited) = limiter.acquire().await;
+        if let Some(waited) = waited {
+            tracing::debug!(%method, %url, ?waited, "Waited for the rate limit");
+        }
+
+        Some(permit)
+    }
+
+    /// Executes the request, retrying transient failures according to the client's
+    /// [`RetryPolicy`].
+    async fn execute(&self, request: reqwest::RequestBuilder) -> Result<Response, Error> {
+        let request = request.build()?;
+        let method = request.method().clone();
+        let url = request.url().clone();
+
+        #[cfg(feature = "tower")]
+        if let Some(service) = &self.service {
+            let _permit = self.wait_for_permit(&method, &url).await;
+            return crate::middleware::send(service.clone(), request).await;
+        }
+
+        if !self.is_trusted(&url) {
+            tracing::warn!(%method, %url, "Refusing request outside the configured environment");
+            return Err(Error::CrossOrigin(url.to_string()));
+        }
+        let request = reqwest::RequestBuilder::from_parts(self.client.clone(), request)
+            .basic_auth(self.config.key(), Some(self.config.expose_secret()))
+            .build()?;
+
+        let mut attempt = 1;
+        loop {
+            // Bodies are always buffered JSON, so the request can always be cloned
+            let Some(current) = request.try_clone() else {
+                let permit = self.wait_for_permit(&method, &url).await;
+                let resp = self.client.execute(request).await?;
+                return Ok(hold(permit, resp));
+            };
+
+            let permit = self.wait_for_permit(&method, &url).await;
+            let result = self.client.execute(current).await;
+
+            let (delay, reason) = match result {
+                Ok(resp) if retry::is_transient_status(resp.status()) => {
+                    let retry_after = retry::retry_after(resp.headers());
+                    match self.retry.next_delay(&method, attempt, retry_after) {
+                        Some(delay) => (delay, resp.status().to_string()),
+                        None => return Ok(hold(permit, resp)),
+                    }
+                }
+                Ok(resp) => return Ok(hold(permit, resp)),
+                Err(error) if retry::is_transient_error(&error) => {
+                    match self.retry.next_delay(&method, attempt, None) {
+                        Some(delay) => (delay, error.to_string()),
+                        None => return Err(error.into()),
+                    }
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            // The request is no longer in flight while backing off
+            drop(permit);
+            tracing::warn!(%method, %url, attempt, ?delay, %reason, "Retrying failed request");
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Keep the permit, if any, until the body of the response has been read
+fn hold(permit: Option<Permit>, response: Response) -> Response {
+    match permit {
+        Some(permit) => permit.hold(response),
+        None => response,
+    }
+}
+
+/// A builder for a [`Client`], constructed with [`Client::builder`].
+///
+/// Options which are not set are left to the defaults of [`reqwest`], which has no timeouts and
+/// reads proxies from the system's environment.
+#[derive(Debug)]
+pub struct ClientBuilder {
+    config: Config,
+    client: Option<reqwest::Client>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    root_certificates: Vec<Certificate>,
+    default_headers: HeaderMap,
+    user_agent: Option<String>,
+    retry: RetryPolicy,
+    rate_limit: Option<RateLimit>,
+    allowed_origins: Vec<Origin>,
+}
+
+impl ClientBuilder {
+    /// The time allowed for connecting to Freedom
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// The time allowed between reads of a response, which is reset after each successful read
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// The time allowed for each attempt of a request, from connecting until the response body
+    /// has been read
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send requests through the provided proxy, in addition to any previously added
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trust the provided root certificate, in addition to the system's
+    pub fn root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Send the provided headers with every request, replacing any previously added headers of the
+    /// same name
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// The user agent sent with every request, which defaults to `freedom-api/<version>`.
+    ///
+    /// Building the client fails if the user agent is not a valid header value.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Send requests with the provided HTTP client.
+    ///
+    /// The client is used as is, so the timeouts, proxies, root certificates, default headers,
+    /// and user agent of this builder are ignored.
+    pub fn reqwest_client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// The policy used to retry requests which failed due to transient errors.
+    ///
+    /// See [`Client::with_retry_policy`]
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// The limit placed on the requests sent by the client and its clones.
+    ///
+    /// See [`Client::with_rate_limit`]
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Allow credentials to be sent to the origin of the provided URL.
+    ///
+    /// See [`Client::with_allowed_origin`]
+    pub fn allowed_origin(mut self, url: &Url) -> Self {
+        self.allowed_origins.push(url.origin());
+        self
+    }
+
+    /// Build the client, failing if the HTTP client could not be constructed from the options
+    pub fn build(mut self) -> Result<Client, Error> {
+        let client = match self.client.take() {
+            Some(client) => {
+                if self.has_connection_options() {
+                    tracing::warn!("Ignoring connection options in favor of the provided client");
+                }
+                client
+            }
+            None => {
+                let mut builder = reqwest::Client::builder()
+                    .user_agent(self.user_agent.unwrap_or_else(|| USER_AGENT.to_owned()))
+                    .default_headers(self.default_headers);
+
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.read_timeout {
+                    builder = builder.read_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                for certificate in self.root_certificates {
+                    builder = builder.add_root_certificate(certificate);
+                }
+
+                builder.build()?
+            }
+        };
+
+        Ok(Client {
+            config: self.config,
+            client,
+            retry: self.retry,
+            allowed_origins: self.allowed_origins,
+            limiter: self
+                .rate_limit
+                .map(|limit| Arc::new(RateLimiter::new(limit))),
+            #[cfg(feature = "tower")]
+            service: None,
+        })
+    }
+
+    fn has_connection_options(&self) -> bool {
+        self.connect_timeout.is_some()
+            || self.read_timeout.is_some()
+            || self.timeout.is_some()
+            || !self.proxies.is_empty()
+            || !self.root_certificates.is_empty()
+            || !self.default_headers.is_empty()
+            || self.user_agent.is_some()
+    }
 }
 
 impl Api for Client {
     type Container<T: Value> = Inner<T>;
 
-    async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), crate::error::Error> {
-        let resp = self
-            .client
-            .get(url)
-            .basic_auth(self.config.key(), Some(&self.config.expose_secret()))
-            .send()
-            .await?;
+    async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), Error> {
+        let resp = self.execute(self.client.get(url)).await?;
 
         let status = resp.status();
         let body = resp.bytes().await?;
@@ -75,26 +470,41 @@ impl Api for Client {
         Ok((body, status))
     }
 
-    async fn delete(&self, url: Url) -> Result<Response, crate::error::Error> {
-        self.client
-            .delete(url)
-            .basic_auth(self.config.key(), Some(self.config.expose_secret()))
-            .send()
-            .await
-            .map_err(From::from)
+    async fn get_stream(&self, url: Url, offset: u64) -> Result<Response, Error> {
+        let mut request = self.client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={offset}-"));
+        }
+
+        self.execute(request).await
+    }
+
+    async fn delete(&self, url: Url) -> Result<Response, Error> {
+        self.execute(self.client.delete(url)).await
     }
 
-    async fn post<S>(&self, url: Url, msg: S) -> Result<Response, crate::error::Error>
+    async fn post<S>(&self, url: Url, msg: S) -> Result<Response, Error>
     where
         S: serde::Serialize + Sync + Send,
     {
-        self.client
-            .post(url)
-            .basic_auth(self.config.key(), Some(self.config.expose_secret()))
-            .json(&msg)
-            .send()
-            .await
-            .map_err(From::from)
+        let request = self.client.post(url).header(ACCEPT, "application/json");
+        self.execute(request.json(&msg)).await
+    }
+
+    async fn put<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Sync + Send,
+    {
+        let request = self.client.put(url).header(ACCEPT, "application/json");
+        self.execute(request.json(&msg)).await
+    }
+
+    async fn patch<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Sync + Send,
+    {
+        let request = self.client.patch(url).header(ACCEPT, "application/json");
+        self.execute(request.json(&msg)).await
     }
 
     fn config(&self) -> &Config {
@@ -110,7 +520,7 @@ impl Api for Client {
 mod tests {
     use freedom_config::Test;
     use httpmock::{
-        Method::{GET, POST},
+        Method::{GET, PATCH, POST, PUT},
         MockServer,
     };
 
@@ -118,15 +528,18 @@ mod tests {
 
     use super::*;
 
-    fn default_client() -> Client {
-        let config = Config::builder()
-            .environment(Test)
-            .key("foo")
-            .secret("bar")
-            .build()
-            .unwrap();
+    /// A client for the test environment, which may also send requests to the mock server
+    fn default_client(server: &MockServer) -> Client {
+        let server = Url::parse(&server.base_url()).unwrap();
+        Client::from_config(test_config()).with_allowed_origin(&server)
+    }
+
+    fn retrying_client(server: &MockServer) -> Client {
+        let policy = RetryPolicy::default()
+            .max_attempts(3)
+            .initial_backoff(std::time::Duration::from_millis(1));
 
-        Client::from_config(config)
+        default_client(server).with_retry_policy(policy)
     }
 
     #[test]
@@ -167,8 +580,8 @@ mod tests {
     #[tokio::test]
     async fn get_ok_response() {
         const RESPONSE: &str = "it's working";
-        let client = default_client();
         let server = MockServer::start();
+        let client = default_client(&server);
         let addr = server.address();
         let mock = server.mock(|when, then| {
             when.method(GET).path("/testing");
@@ -185,8 +598,8 @@ mod tests {
     #[tokio::test]
     async fn get_err_response() {
         const RESPONSE: &str = "NOPE";
-        let client = default_client();
         let server = MockServer::start();
+        let client = default_client(&server);
         let addr = server.address();
         let mock = server.mock(|when, then| {
             when.method(GET).path("/testing");
@@ -202,8 +615,8 @@ mod tests {
 
     #[tokio::test]
     async fn post_json() {
-        let client = default_client();
         let server = MockServer::start();
+        let client = default_client(&server);
         let addr = server.address();
         let json = serde_json::json!({
             "name": "foo",
@@ -219,4 +632,257 @@ mod tests {
 
         mock.assert_hits(1);
     }
+
+    #[tokio::test]
+    async fn get_retries_transient_failures() {
+        let server = MockServer::start();
+        let client = retrying_client(&server);
+        let addr = server.address();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/testing");
+            then.status(503);
+        });
+        let url = Url::parse(&format!("http://{}/testing", addr)).unwrap();
+        let (_, status) = client.get(url).await.unwrap();
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        mock.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn get_does_not_retry_client_errors() {
+        let server = MockServer::start();
+        let client = retrying_client(&server);
+        let addr = server.address();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/testing");
+            then.status(404);
+        });
+        let url = Url::parse(&format!("http://{}/testing", addr)).unwrap();
+        client.get(url).await.unwrap();
+
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn requests_are_in_flight_until_their_body_is_read() {
+        let server = MockServer::start();
+        let client = default_client(&server).with_rate_limit(RateLimit::default().max_in_flight(1));
+        server.mock(|when, then| {
+            when.method(GET).path("/testing");
+            then.body("it's working");
+        });
+        let url = Url::parse(&server.url("/testing")).unwrap();
+
+        let response = client.get_stream(url.clone(), 0).await.unwrap();
+        assert_eq!(response.url(), &url);
+        let second = tokio::time::timeout(Duration::from_millis(20), client.get(url.clone()));
+        assert!(second.await.is_err());
+
+        assert_eq!(response.bytes().await.unwrap(), "it's working");
+        let (body, _) = client.get(url).await.unwrap();
+        assert_eq!(body, "it's working");
+    }
+
+    #[tokio::test]
+    async fn post_is_only_retried_when_enabled() {
+        let server = MockServer::start();
+        let addr = server.address();
+        let mock = server.mock(|when, then| {
+            when.method(POST).path("/testing");
+            then.status(429);
+        });
+        let url = Url::parse(&format!("http://{}/testing", addr)).unwrap();
+
+        let client = retrying_client(&server);
+        client.post(url.clone(), "foo").await.unwrap();
+        mock.assert_hits(1);
+
+        let policy = client.retry_policy().clone().retry_posts(true);
+        let client = client.with_retry_policy(policy);
+        client.post(url, "foo").await.unwrap();
+        mock.assert_hits(4);
+    }
+
+    #[tokio::test]
+    async fn put_and_patch_json() {
+        let server = MockServer::start();
+        let client = default_client(&server);
+        let addr = server.address();
+        let json = serde_json::json!({ "name": "foo" });
+        let json_clone = json.clone();
+        let put = server.mock(|when, then| {
+            when.method(PUT).path("/testing").json_body(json_clone);
+            then.status(200);
+        });
+        let json_clone = json.clone();
+        let patch = server.mock(|when, then| {
+            when.method(PATCH).path("/testing").json_body(json_clone);
+            then.status(200);
+        });
+        let url = Url::parse(&format!("http://{}/testing", addr)).unwrap();
+        client.put(url.clone(), &json).await.unwrap();
+        client.patch(url, &json).await.unwrap();
+
+        put.assert_hits(1);
+        patch.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn credentials_are_not_sent_to_other_origins() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/testing");
+            then.status(200);
+        });
+        let url = Url::parse(&format!("http://{}/testing", server.address())).unwrap();
+
+        let config = Config::builder()
+            .environment(Test)
+            .key("foo")
+            .secret("bar")
+            .build()
+            .unwrap();
+        let client = Client::from_config(config);
+        let error = client.get(url.clone()).await.unwrap_err();
+
+        assert_eq!(error, Error::CrossOrigin(url.to_string()));
+        mock.assert_hits(0);
+
+        let client = default_client(&server);
+        client.get(url).await.unwrap();
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn credentials_are_sent_to_the_environment() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/testing")
+                .header("authorization", "Basic Zm9vOmJhcg==");
+            then.status(200);
+        });
+        let url = Url::parse(&format!("http://{}/testing", server.address())).unwrap();
+
+        let (_, status) = default_client(&server).get(url).await.unwrap();
+        assert_eq!(status, StatusCode::OK);
+        mock.assert_hits(1);
+    }
+
+    fn test_config() -> Config {
+        Config::builder()
+            .environment(Test)
+            .key("foo")
+            .secret("bar")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn builder_sends_user_agent_and_default_headers() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/testing")
+                .header("user-agent", USER_AGENT)
+                .header("x-request-source", "nightly");
+            then.status(200);
+        });
+        let url = Url::parse(&server.url("/testing")).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-source", "nightly".parse().unwrap());
+        let client = Client::builder(test_config())
+            .default_headers(headers)
+            .allowed_origin(&url)
+            .build()
+            .unwrap();
+
+        let (_, status) = client.get(url).await.unwrap();
+        assert_eq!(status, StatusCode::OK);
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn builder_applies_timeout() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/testing");
+            then.status(200)
+                .delay(std::time::Duration::from_millis(500));
+        });
+        let url = Url::parse(&server.url("/testing")).unwrap();
+
+        let client = Client::builder(test_config())
+            .timeout(Duration::from_millis(50))
+            .retry_policy(RetryPolicy::default().max_attempts(1))
+            .allowed_origin(&url)
+            .build()
+            .unwrap();
+
+        let error = client.get(url).await.unwrap_err();
+        assert!(matches!(error, Error::Response(_)));
+        mock.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn builder_uses_provided_reqwest_client() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/testing")
+                .header("user-agent", "injected");
+            then.status(200);
+        });
+        let url = Url::parse(&server.url("/testing")).unwrap();
+
+        let injected = reqwest::Client::builder()
+            .user_agent("injected")
+            .build()
+            .unwrap();
+        let client = Client::builder(test_config())
+            .user_agent("ignored")
+            .reqwest_client(injected)
+            .allowed_origin(&url)
+            .build()
+            .unwrap();
+
+        client.get(url).await.unwrap();
+        mock.assert_hits(1);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_user_agent() {
+        let result = Client::builder(test_config())
+            .user_agent("line\nbreak")
+            .build();
+
+        assert!(matches!(result, Err(Error::Response(_))));
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn rate_limit_is_shared_between_clones() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/testing");
+            then.status(200);
+        });
+        let url = Url::parse(&server.url("/testing")).unwrap();
+
+        let limit = RateLimit::default().rate(1, Duration::from_millis(200));
+        let client = default_client(&server).with_rate_limit(limit.clone());
+        let clone = client.clone();
+        assert_eq!(clone.rate_limit(), Some(&limit));
+
+        let start = std::time::Instant::now();
+        client.get(url.clone()).await.unwrap();
+        assert!(!logs_contain("Waited for the rate limit"));
+        clone.get(url).await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert!(logs_contain("Waited for the rate limit"));
+        mock.assert_hits(2);
+    }
 }
@@ -1,5 +1,7 @@
 //! Error and Result types for Freedom API
-use serde::Serialize;
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
 
 /// Result type for the API
 pub type Result<T> = std::result::Result<T, Error>;
@@ -10,9 +12,76 @@ pub enum Error {
     #[error("Failed to get valid response from server: {0}")]
     Response(String),
 
+    /// Freedom responded with a non-success status which has no more specific variant
+    #[error(
+        "{method} {url} failed with status {status}{}",
+        display_message(freedom_message)
+    )]
+    Http {
+        status: u16,
+        method: String,
+        url: String,
+        body: String,
+        freedom_message: Option<String>,
+    },
+
+    /// Freedom responded with `404 Not Found`
+    #[error("Resource not found: {url}{}", display_message(freedom_message))]
+    NotFound {
+        url: String,
+        freedom_message: Option<String>,
+    },
+
+    /// Freedom responded with `401 Unauthorized`, usually due to an invalid key or secret
+    #[error("Unauthorized request to {url}{}", display_message(freedom_message))]
+    Unauthorized {
+        url: String,
+        freedom_message: Option<String>,
+    },
+
+    /// Freedom responded with `403 Forbidden`
+    #[error("Forbidden request to {url}{}", display_message(freedom_message))]
+    Forbidden {
+        url: String,
+        freedom_message: Option<String>,
+    },
+
+    /// Freedom responded with `409 Conflict`
+    #[error("Conflicting request to {url}{}", display_message(freedom_message))]
+    Conflict {
+        url: String,
+        freedom_message: Option<String>,
+    },
+
+    /// Freedom rejected the request body with a client error status, listing the offending fields
+    #[error("Validation failed for {url}: {}", display_field_errors(field_errors))]
+    Validation {
+        status: u16,
+        url: String,
+        field_errors: Vec<FieldError>,
+        freedom_message: Option<String>,
+    },
+
+    /// The request failed client-side validation, listing the offending fields. The request was not
+    /// sent
+    #[error("Invalid request: {}", display_field_errors(.0))]
+    InvalidRequest(Vec<FieldError>),
+
     #[error("Failed to deserialize the response: {0}")]
     Deserialization(String),
 
+    /// Writing a value in a format such as CSV, JSON, or YAML failed
+    #[error("Failed to serialize: {0}")]
+    Serialization(String),
+
+    /// Freedom accepted a write, but returned neither the resource nor its location
+    #[error("{method} {url} succeeded with status {status}, but returned no content")]
+    EmptyResponse {
+        status: u16,
+        method: String,
+        url: String,
+    },
+
     #[error("Paginated item failed deserialized: {0}")]
     PaginationItemDeserialization(String),
 
@@ -21,14 +90,59 @@ pub enum Error {
     #[error("Time parsing error: {0}")]
     TimeFormatError(String),
 
+    /// A time window was created with a start which is not before its end
+    #[error("The start of a time window must be before its end, found {start} to {end}")]
+    InvalidTimeWindow { start: String, end: String },
+
     #[error("Failed to parse item into valid URI: {0}")]
     InvalidUri(String),
 
     #[error("Failed to retrieve the HATEOAS URI: {0}")]
-    MissingUri(&'static str),
+    MissingUri(String),
 
     #[error("Failed to parse the final segment of the path as an ID.")]
     InvalidId,
+
+    /// A request was made to a URL outside the origin of the configured environment, which has
+    /// not been allowed on the client. The request is not sent, so that credentials are not leaked
+    #[error("Refusing to send credentials to {0}, which is outside the configured environment")]
+    CrossOrigin(String),
+
+    /// The client does not implement requests with the HTTP method
+    #[error("{0} requests are not supported by this client")]
+    UnsupportedMethod(String),
+
+    /// Freedom has no search endpoint for the combination of criteria in a query
+    #[error("Freedom has no search for {0}")]
+    UnsupportedQuery(String),
+
+    // Like time errors, std::io::Error is stored as a string to keep the error Clone and Eq
+    /// Writing a download to its destination failed
+    #[error("Failed to write the download: {0}")]
+    Io(String),
+
+    /// The downloaded file was not of the expected size
+    #[error("Downloaded {actual} bytes, expected {expected}")]
+    SizeMismatch { expected: u64, actual: u64 },
+
+    /// The checksum of the downloaded file did not match the expected checksum
+    #[error("Downloaded file has checksum {actual:#x}, expected {expected:#x}")]
+    ChecksumMismatch { expected: u64, actual: u64 },
+}
+
+/// A single field rejected by Freedom's validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// The name of the rejected field, e.g. `targetDate`
+    pub field: String,
+    /// The reason the field was rejected
+    pub message: String,
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
 }
 
 impl Error {
@@ -36,6 +150,163 @@ impl Error {
     pub(crate) fn pag_item(s: String) -> Self {
         Self::PaginationItemDeserialization(s)
     }
+
+    /// Construct the error for a non-success response from Freedom.
+    ///
+    /// The body is parsed as a Spring error payload where possible, to extract the message and any
+    /// field errors reported by Freedom.
+    pub(crate) fn from_response(
+        method: &Method,
+        url: &Url,
+        status: StatusCode,
+        body: &[u8],
+    ) -> Self {
+        let payload = serde_json::from_slice::<SpringError>(body).unwrap_or_default();
+        let freedom_message = payload.message.or(payload.error);
+        let url = url.to_string();
+
+        match status {
+            StatusCode::NOT_FOUND => Self::NotFound {
+                url,
+                freedom_message,
+            },
+            StatusCode::UNAUTHORIZED => Self::Unauthorized {
+                url,
+                freedom_message,
+            },
+            StatusCode::FORBIDDEN => Self::Forbidden {
+                url,
+                freedom_message,
+            },
+            StatusCode::CONFLICT => Self::Conflict {
+                url,
+                freedom_message,
+            },
+            _ if status.is_client_error() && !payload.errors.is_empty() => Self::Validation {
+                status: status.as_u16(),
+                url,
+                field_errors: payload.errors.into_iter().map(From::from).collect(),
+                freedom_message,
+            },
+            _ => Self::Http {
+                status: status.as_u16(),
+                method: method.to_string(),
+                url,
+                body: String::from_utf8_lossy(body).into_owned(),
+                freedom_message,
+            },
+        }
+    }
+
+    /// The HTTP status code of the response which produced the error, if any
+    pub fn status(&self) -> Option<StatusCode> {
+        let status = match self {
+            Self::Http { status, .. } | Self::Validation { status, .. } => {
+                return StatusCode::from_u16(*status).ok()
+            }
+            Self::NotFound { .. } => StatusCode::NOT_FOUND,
+            Self::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            Self::Forbidden { .. } => StatusCode::FORBIDDEN,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+            _ => return None,
+        };
+
+        Some(status)
+    }
+
+    /// The message Freedom provided alongside the error response, if any
+    pub fn freedom_message(&self) -> Option<&str> {
+        match self {
+            Self::Http {
+                freedom_message, ..
+            }
+            | Self::NotFound {
+                freedom_message, ..
+            }
+            | Self::Unauthorized {
+                freedom_message, ..
+            }
+            | Self::Forbidden {
+                freedom_message, ..
+            }
+            | Self::Conflict {
+                freedom_message, ..
+            }
+            | Self::Validation {
+                freedom_message, ..
+            } => freedom_message.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Check the status of a raw response, such as those returned by the `delete_*` and `send_raw`
+/// methods of the [`Api`](crate::Api), producing the same errors as its other methods.
+///
+/// Responses with a success status are returned unchanged.
+pub async fn check_response(
+    method: &Method,
+    response: reqwest::Response,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let url = response.url().clone();
+    let body = response.bytes().await?;
+
+    Err(Error::from_response(method, &url, status, &body))
+}
+
+/// The subset of the Spring error payloads used by Freedom which we are interested in
+#[derive(Debug, Default, Deserialize)]
+struct SpringError {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    errors: Vec<SpringFieldError>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpringFieldError {
+    #[serde(alias = "field")]
+    property: String,
+    #[serde(alias = "defaultMessage")]
+    message: String,
+}
+
+impl From<SpringFieldError> for FieldError {
+    fn from(value: SpringFieldError) -> Self {
+        Self {
+            field: value.property,
+            message: value.message,
+        }
+    }
+}
+
+fn display_message(message: &Option<String>) -> String {
+    match message {
+        Some(message) => format!(": {message}"),
+        None => String::new(),
+    }
+}
+
+fn display_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl From<std::convert::Infallible> for Error {
+    fn from(value: std::convert::Infallible) -> Self {
+        match value {}
+    }
 }
 
 impl From<reqwest::Error> for Error {
@@ -62,8 +333,137 @@ impl From<time::error::Format> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(value.to_string())
+    }
+}
+
 impl From<url::ParseError> for Error {
     fn from(value: url::ParseError) -> Self {
         Self::InvalidUri(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url() -> Url {
+        Url::parse("https://test-api.atlasground.com/api/requests").unwrap()
+    }
+
+    #[tokio::test]
+    async fn raw_responses_are_checked() {
+        let response = |status: StatusCode| {
+            let response = http::Response::builder()
+                .status(status)
+                .body(br#"{"status":404,"error":"Not Found"}"#.to_vec())
+                .unwrap();
+            reqwest::Response::from(response)
+        };
+
+        let checked = check_response(&Method::DELETE, response(StatusCode::NO_CONTENT)).await;
+        assert_eq!(checked.unwrap().status(), StatusCode::NO_CONTENT);
+
+        let error = check_response(&Method::DELETE, response(StatusCode::NOT_FOUND))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::NotFound { .. }));
+        assert_eq!(error.freedom_message(), Some("Not Found"));
+    }
+
+    #[test]
+    fn not_found_keeps_message() {
+        let body = br#"{"status":404,"error":"Not Found","path":"/api/requests/42"}"#;
+        let error = Error::from_response(&Method::GET, &url(), StatusCode::NOT_FOUND, body);
+
+        assert_eq!(
+            error,
+            Error::NotFound {
+                url: url().to_string(),
+                freedom_message: Some(String::from("Not Found")),
+            }
+        );
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn validation_errors_are_parsed() {
+        let body = br#"{
+            "errors": [
+                {
+                    "entity": "TaskRequest",
+                    "property": "targetDate",
+                    "invalidValue": "2020-01-01T00:00:00Z",
+                    "message": "must be in the future"
+                }
+            ]
+        }"#;
+        let error = Error::from_response(&Method::POST, &url(), StatusCode::BAD_REQUEST, body);
+
+        let Error::Validation { field_errors, .. } = &error else {
+            panic!("Expected validation error, found {error:?}");
+        };
+        assert_eq!(
+            field_errors,
+            &[FieldError {
+                field: String::from("targetDate"),
+                message: String::from("must be in the future"),
+            }]
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Validation failed for {}: targetDate: must be in the future",
+                url()
+            )
+        );
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        let error = Error::from_response(
+            &Method::POST,
+            &url(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+            body,
+        );
+        assert!(matches!(error, Error::Validation { status: 422, .. }));
+        assert_eq!(error.status(), Some(StatusCode::UNPROCESSABLE_ENTITY));
+    }
+
+    #[test]
+    fn other_statuses_keep_body() {
+        let body = br#"{"message":"Something broke"}"#;
+        let error = Error::from_response(
+            &Method::GET,
+            &url(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            body,
+        );
+
+        assert_eq!(
+            error,
+            Error::Http {
+                status: 500,
+                method: String::from("GET"),
+                url: url().to_string(),
+                body: String::from_utf8_lossy(body).into_owned(),
+                freedom_message: Some(String::from("Something broke")),
+            }
+        );
+        assert_eq!(error.freedom_message(), Some("Something broke"));
+    }
+
+    #[test]
+    fn non_json_body() {
+        let error = Error::from_response(&Method::DELETE, &url(), StatusCode::FORBIDDEN, b"NOPE");
+
+        assert_eq!(
+            error,
+            Error::Forbidden {
+                url: url().to_string(),
+                freedom_message: None,
+            }
+        );
+    }
+}
@@ -1,10 +1,15 @@
 use std::future::Future;
 
-use crate::{api::Api, error::Error};
-use freedom_models::{account::Account, satellite::Satellite, user::User};
+use crate::{api::Api, error::Error, ids::AccountId};
+use freedom_models::{
+    account::{Account, Tier},
+    satellite::Satellite,
+    site::SiteConfiguration,
+    user::User,
+};
 
 pub trait AccountExt {
-    fn get_id(&self) -> Result<i32, Error>;
+    fn get_id(&self) -> Result<AccountId, Error>;
 
     fn get_users<C>(
         &self,
@@ -22,7 +27,7 @@ pub trait AccountExt {
 }
 
 impl AccountExt for Account {
-    fn get_id(&self) -> Result<i32, Error> {
+    fn get_id(&self) -> Result<AccountId, Error> {
         super::get_id("self", &self.links)
     }
 
@@ -30,7 +35,7 @@ impl AccountExt for Account {
     where
         C: Api + Send,
     {
-        super::get_item("users", &self.links, client).await
+        super::get_embedded("users", &self.links, client).await
     }
 
     async fn get_satellites<C>(
@@ -43,3 +48,24 @@ impl AccountExt for Account {
         super::get_embedded("satellites", &self.links, client).await
     }
 }
+
+pub trait TierExt {
+    fn get_configuration<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<SiteConfiguration>, Error>> + Send
+    where
+        C: Api + Send;
+}
+
+impl TierExt for Tier {
+    async fn get_configuration<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<SiteConfiguration>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("configuration", &self.links, client).await
+    }
+}
@@ -1,12 +1,28 @@
-use crate::error::Error;
-use freedom_models::band::Band;
+use std::future::Future;
+
+use crate::{api::Api, error::Error, ids::BandId};
+use freedom_models::{account::Account, band::Band};
 
 pub trait BandExt {
-    fn get_id(&self) -> Result<i32, Error>;
+    fn get_id(&self) -> Result<BandId, Error>;
+
+    fn get_account<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Account>, Error>> + Send
+    where
+        C: Api + Send;
 }
 
 impl BandExt for Band {
-    fn get_id(&self) -> Result<i32, Error> {
+    fn get_id(&self) -> Result<BandId, Error> {
         super::get_id("self", &self.links)
     }
+
+    async fn get_account<C>(&self, client: &C) -> Result<<C as Api>::Container<Account>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("account", &self.links, client).await
+    }
 }
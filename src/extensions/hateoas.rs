@@ -0,0 +1,107 @@
+use std::future::Future;
+
+use freedom_models::Hateoas;
+
+use crate::{
+    api::{Api, PaginatedErr, PaginatedStream, Value},
+    error::Error,
+};
+
+/// Follow any link of a model, including those which have no typed method yet.
+///
+/// This is implemented for every [`Hateoas`] model. The caller chooses the type the linked resource
+/// is deserialized into, along with the way it is wrapped:
+///
+/// + [`follow`](Self::follow) for a single resource, whether or not it is wrapped in a `content`
+///   map
+/// + [`follow_embedded`](Self::follow_embedded) for a collection wrapped in an `_embedded` map
+/// + [`follow_paginated`](Self::follow_paginated) for a collection split across pages
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let satellite = client.get_satellite_by_id(710).await?;
+/// let orbit: serde_json::Value = satellite
+///     .follow::<serde_json::Value, _>("orbitInfo", &client)
+///     .await?
+///     .into_inner();
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub trait HateoasExt: Hateoas {
+    /// Fetch the single resource at the link with the provided name
+    fn follow<T, C>(
+        &self,
+        rel: &str,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<T>, Error>> + Send
+    where
+        C: Api + Send,
+        T: Value;
+
+    /// Fetch the collection of resources at the link with the provided name
+    fn follow_embedded<T, C>(
+        &self,
+        rel: &str,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Vec<T>>, Error>> + Send
+    where
+        C: Api + Send,
+        T: Value;
+
+    /// Stream the paginated collection of resources at the link with the provided name.
+    ///
+    /// See [`Api::get_paginated`] for more details about the process and return type
+    fn follow_paginated<'a, T, C>(
+        &self,
+        rel: &str,
+        client: &'a C,
+    ) -> PaginatedStream<'a, <C as Api>::Container<T>>
+    where
+        C: Api + Send,
+        T: 'static + Value;
+}
+
+impl<H> HateoasExt for H
+where
+    H: Hateoas + Sync,
+{
+    async fn follow<T, C>(&self, rel: &str, client: &C) -> Result<<C as Api>::Container<T>, Error>
+    where
+        C: Api + Send,
+        T: Value,
+    {
+        super::get_item(rel, self.get_links(), client).await
+    }
+
+    async fn follow_embedded<T, C>(
+        &self,
+        rel: &str,
+        client: &C,
+    ) -> Result<<C as Api>::Container<Vec<T>>, Error>
+    where
+        C: Api + Send,
+        T: Value,
+    {
+        super::get_embedded(rel, self.get_links(), client).await
+    }
+
+    fn follow_paginated<'a, T, C>(
+        &self,
+        rel: &str,
+        client: &'a C,
+    ) -> PaginatedStream<'a, <C as Api>::Container<T>>
+    where
+        C: Api + Send,
+        T: 'static + Value,
+    {
+        match self.get_links().get(rel) {
+            Some(url) => client.get_paginated(url.clone()),
+            None => Error::MissingUri(rel.to_string()).once_err(),
+        }
+    }
+}
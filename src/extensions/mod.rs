@@ -6,46 +6,87 @@
 //! These are implemented as traits to allow the `freedom_models` crate to remain extremely thin,
 //! so that when it is ingested by other crates which do not require this functionality, it does
 //! not contribute to the dependency graph.
+//!
+//! Links to resources which `freedom_models` does not model yet, such as a satellite's `orbitInfo`
+//! or a task's `metrics`, are returned as raw JSON. They may instead be fetched into a type of the
+//! caller's choosing with [`HateoasExt`].
 
 use std::collections::HashMap;
 
-use freedom_models::{utils::Content, Hateoas};
+use serde_json::Value as JsonValue;
 
 use crate::{api::Value, error, prelude::Api};
 mod account;
 mod band;
+mod hateoas;
 mod request;
+mod resolve;
 mod satellite;
+mod satellite_configuration;
 mod site;
 mod task;
 mod user;
 
 pub use {
-    account::AccountExt,
+    account::{AccountExt, TierExt},
     band::BandExt,
+    hateoas::HateoasExt,
     request::TaskRequestExt,
+    resolve::{ResolvedTaskRequest, TaskRequestResolver},
     satellite::SatelliteExt,
+    satellite_configuration::SatelliteConfigurationExt,
     site::{SiteConfigurationExt, SiteExt},
     task::TaskExt,
     user::UserExt,
 };
 
-fn get_id(reference: &'static str, links: &HashMap<String, url::Url>) -> Result<i32, error::Error> {
+fn get_id<I>(reference: &str, links: &HashMap<String, url::Url>) -> Result<I, error::Error>
+where
+    I: From<i32>,
+{
     let url = links
         .get(reference)
-        .ok_or(error::Error::MissingUri(reference))?;
+        .ok_or_else(|| error::Error::MissingUri(reference.to_string()))?;
 
     let id_str = url
         .path_segments()
         .ok_or(error::Error::InvalidUri("Missing Path".into()))?
-        .last()
+        .next_back()
         .unwrap();
 
-    id_str.parse().map_err(|_| error::Error::InvalidId)
+    id_str
+        .parse::<i32>()
+        .map(I::from)
+        .map_err(|_| error::Error::InvalidId)
 }
 
+/// Freedom returns a single related resource either as is, with its links inside the map, or
+/// wrapped in a "content" map, with its links on the outside of the map. The latter is flattened
+/// into the former, so that both deserialize into the resource directly.
+fn unwrap_content(mut value: JsonValue) -> JsonValue {
+    let Some(object) = value.as_object_mut() else {
+        return value;
+    };
+    let wrapped = object.contains_key("content")
+        && object.keys().all(|key| key == "content" || key == "_links");
+    if !wrapped {
+        return value;
+    }
+
+    match (object.remove("content"), object.remove("_links")) {
+        (Some(JsonValue::Object(mut content)), links) => {
+            if let Some(links) = links {
+                content.insert(String::from("_links"), links);
+            }
+            JsonValue::Object(content)
+        }
+        (content, _) => content.unwrap_or_default(),
+    }
+}
+
+/// Fetch a single related resource, whether or not it is wrapped in a "content" map
 async fn get_item<T, C>(
-    reference: &'static str,
+    reference: &str,
     links: &HashMap<String, url::Url>,
     client: &C,
 ) -> Result<T, error::Error>
@@ -55,17 +96,16 @@ where
 {
     let uri = links
         .get(reference)
-        .ok_or(error::Error::MissingUri(reference))?
+        .ok_or_else(|| error::Error::MissingUri(reference.to_string()))?
         .clone();
 
-    client.get_json_map(uri).await
+    let value = client.get_json_map::<JsonValue>(uri).await?;
+    serde_json::from_value(unwrap_content(value)).map_err(From::from)
 }
 
-// TODO: There are BOTH "_embedded" and "content" wrapping maps. However the former contains the
-// links within the map, the later contains the links on the outside of the map. We need to treat
-// these differently.
+/// Fetch a collection of related resources, which Freedom always wraps in an "_embedded" map
 async fn get_embedded<T, C>(
-    reference: &'static str,
+    reference: &str,
     links: &HashMap<String, url::Url>,
     client: &C,
 ) -> Result<<C as Api>::Container<T>, error::Error>
@@ -81,16 +121,30 @@ where
     Ok(wrapped.items)
 }
 
-async fn get_content<T, C>(
-    reference: &'static str,
-    links: &HashMap<String, url::Url>,
-    client: &C,
-) -> Result<T, error::Error>
-where
-    C: Api + Send,
-    T: Value + Hateoas,
-{
-    let wrapped = get_item::<Content<T>, C>(reference, links, client).await?;
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn content_is_flattened() {
+        let wrapped = json!({
+            "content": { "name": "LOAG" },
+            "_links": { "self": { "href": "http://localhost:8080/api/sites/14" } },
+        });
+        let plain = json!({
+            "name": "LOAG",
+            "_links": { "self": { "href": "http://localhost:8080/api/sites/14" } },
+        });
+
+        assert_eq!(unwrap_content(wrapped), plain);
+        assert_eq!(unwrap_content(plain.clone()), plain);
+    }
 
-    Ok(wrapped.inner)
+    #[test]
+    fn content_fields_are_kept() {
+        let value = json!({ "content": "bytes", "name": "file.bin" });
+        assert_eq!(unwrap_content(value.clone()), value);
+    }
 }
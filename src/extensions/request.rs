@@ -1,6 +1,7 @@
 use std::future::Future;
 
-use crate::{api::Api, error::Error};
+use super::resolve::{ResolvedTaskRequest, TaskRequestResolver};
+use crate::{api::Api, error::Error, ids::TaskRequestId};
 use freedom_models::{
     band::Band,
     satellite::Satellite,
@@ -10,7 +11,7 @@ use freedom_models::{
 };
 
 pub trait TaskRequestExt {
-    fn get_id(&self) -> Result<i32, Error>;
+    fn get_id(&self) -> Result<TaskRequestId, Error>;
 
     fn get_task<C>(
         &self,
@@ -44,10 +45,20 @@ pub trait TaskRequestExt {
     fn get_user<C>(&self, client: &C) -> impl Future<Output = Result<User, Error>> + Send
     where
         C: Api + Send;
+
+    /// Resolve every link of the task request at once.
+    ///
+    /// See [`TaskRequestResolver`] to resolve many task requests, sharing the lookups between them
+    fn resolve<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<ResolvedTaskRequest, Error>> + Send
+    where
+        C: Api + Send;
 }
 
 impl TaskRequestExt for TaskRequest {
-    fn get_id(&self) -> Result<i32, Error> {
+    fn get_id(&self) -> Result<TaskRequestId, Error> {
         super::get_id("self", &self.links)
     }
 
@@ -62,7 +73,7 @@ impl TaskRequestExt for TaskRequest {
     where
         C: Api + Send,
     {
-        super::get_content("site", &self.links, client).await
+        super::get_item("site", &self.links, client).await
     }
 
     async fn get_target_bands<C>(
@@ -80,20 +91,27 @@ impl TaskRequestExt for TaskRequest {
         C: Api + Send,
     {
         tracing::debug!(links = ?self.links, "Getting configuration");
-        super::get_content("configuration", &self.links, client).await
+        super::get_item("configuration", &self.links, client).await
     }
 
     async fn get_satellite<C>(&self, client: &C) -> Result<Satellite, Error>
     where
         C: Api + Send,
     {
-        super::get_content("satellite", &self.links, client).await
+        super::get_item("satellite", &self.links, client).await
     }
 
     async fn get_user<C>(&self, client: &C) -> Result<User, Error>
     where
         C: Api + Send,
     {
-        super::get_content("user", &self.links, client).await
+        super::get_item("user", &self.links, client).await
+    }
+
+    async fn resolve<C>(&self, client: &C) -> Result<ResolvedTaskRequest, Error>
+    where
+        C: Api + Send,
+    {
+        TaskRequestResolver::new(client).resolve(self.clone()).await
     }
 }
@@ -0,0 +1,269 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use freedom_models::{
+    band::Band,
+    satellite::Satellite,
+    site::{Site, SiteConfiguration},
+    task::{Task, TaskRequest},
+    user::User,
+    utils::Embedded,
+};
+use futures_core::Stream;
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::Value as JsonValue;
+use tokio::sync::{OnceCell, Semaphore};
+use url::Url;
+
+use crate::{
+    api::{Api, Container},
+    error::Error,
+};
+
+/// The default number of lookups a [`TaskRequestResolver`] makes at once
+const DEFAULT_MAX_CONCURRENT: usize = 4;
+
+type Shared = Arc<dyn Any + Send + Sync>;
+
+/// A task request along with every resource it links to.
+///
+/// The site, configuration, satellite, and bands shared between task requests resolved by the same
+/// [`TaskRequestResolver`] are held once and shared between them.
+#[derive(Debug, Clone)]
+pub struct ResolvedTaskRequest {
+    pub request: TaskRequest,
+    pub site: Arc<Site>,
+    pub configuration: Arc<SiteConfiguration>,
+    pub satellite: Arc<Satellite>,
+    pub target_bands: Vec<Arc<Band>>,
+    /// The user who made the request, if Freedom has one for it
+    pub user: Option<Arc<User>>,
+    /// The task scheduled from the request, once it has been scheduled
+    pub task: Option<Arc<Task>>,
+}
+
+/// Resolves the links of task requests into [`ResolvedTaskRequest`]s.
+///
+/// The links of each task request are looked up concurrently, with at most
+/// [`max_concurrent`](Self::max_concurrent) lookups in flight at once. The site, configuration,
+/// satellite, and bands are cached by URL, so those shared between task requests are only fetched
+/// once, and are shared by their `self` link. The user and task belong to a single task request, so
+/// they are fetched for each task request and are not kept by the resolver.
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use futures::StreamExt;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let resolver = TaskRequestResolver::new(&client).max_concurrent(8);
+/// let mut resolved = std::pin::pin!(resolver.resolve_all(client.get_requests()));
+/// while let Some(request) = resolved.next().await {
+///     let request = request?;
+///     println!("{} at {}", request.satellite.name, request.site.name);
+/// }
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+pub struct TaskRequestResolver<'a, C> {
+    client: &'a C,
+    max_concurrent: usize,
+    permits: Semaphore,
+    fetched: Mutex<HashMap<Url, Arc<OnceCell<JsonValue>>>>,
+    shared: Mutex<HashMap<Url, Shared>>,
+}
+
+impl<C> std::fmt::Debug for TaskRequestResolver<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskRequestResolver")
+            .field("permits", &self.permits.available_permits())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, C> TaskRequestResolver<'a, C>
+where
+    C: Api,
+{
+    pub fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            max_concurrent: DEFAULT_MAX_CONCURRENT,
+            permits: Semaphore::new(DEFAULT_MAX_CONCURRENT),
+            fetched: Mutex::default(),
+            shared: Mutex::default(),
+        }
+    }
+
+    /// Make at most the provided number of lookups at once, which defaults to 4.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is zero
+    pub fn max_concurrent(mut self, max: usize) -> Self {
+        assert!(max > 0, "At least one lookup must be allowed at once");
+        self.max_concurrent = max;
+        self.permits = Semaphore::new(max);
+        self
+    }
+
+    /// Resolve every link of the task request
+    pub async fn resolve(&self, request: TaskRequest) -> Result<ResolvedTaskRequest, Error> {
+        let links = &request.links;
+        let (site, configuration, satellite, target_bands, user, task) = tokio::try_join!(
+            self.fetch_one::<Site>(links, "site"),
+            self.fetch_one::<SiteConfiguration>(links, "configuration"),
+            self.fetch_one::<Satellite>(links, "satellite"),
+            self.fetch_many::<Band>(links, "targetBands"),
+            optional(self.fetch_own::<User>(links, "user")),
+            optional(self.fetch_own::<Task>(links, "task")),
+        )?;
+
+        Ok(ResolvedTaskRequest {
+            request,
+            site,
+            configuration,
+            satellite,
+            target_bands,
+            user,
+            task,
+        })
+    }
+
+    /// Resolve each task request of the stream, in order.
+    ///
+    /// Up to [`max_concurrent`](Self::max_concurrent) task requests are resolved at once, with
+    /// their lookups sharing the same limit. Errors in the provided stream are passed through, and
+    /// do not end the stream.
+    pub fn resolve_all<S, T>(
+        self,
+        requests: S,
+    ) -> impl Stream<Item = Result<ResolvedTaskRequest, Error>> + Send + 'a
+    where
+        S: Stream<Item = Result<T, Error>> + Send + 'a,
+        T: Container<TaskRequest>,
+    {
+        let max_concurrent = self.max_concurrent;
+        let resolver = Arc::new(self);
+
+        requests
+            .map(move |request| {
+                let resolver = Arc::clone(&resolver);
+                async move { resolver.resolve(request?.into_inner()).await }
+            })
+            .buffered(max_concurrent)
+    }
+
+    /// Fetch the body at the URL, or wait on the lookup already in flight for it
+    async fn fetch(&self, url: &Url) -> Result<JsonValue, Error> {
+        let cell = Arc::clone(self.fetched.lock().unwrap().entry(url.clone()).or_default());
+
+        cell.get_or_try_init(|| self.load(url)).await.cloned()
+    }
+
+    /// Fetch the body at the URL, without caching it
+    async fn load(&self, url: &Url) -> Result<JsonValue, Error> {
+        let _permit = self.permits.acquire().await.expect("Never closed");
+        self.client.get_json_map::<JsonValue>(url.clone()).await
+    }
+
+    async fn fetch_one<T>(
+        &self,
+        links: &HashMap<String, Url>,
+        reference: &str,
+    ) -> Result<Arc<T>, Error>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let url = links
+            .get(reference)
+            .ok_or_else(|| Error::MissingUri(reference.to_string()))?;
+        let value = super::unwrap_content(self.fetch(url).await?);
+
+        self.share(url, value)
+    }
+
+    /// Fetch a resource which belongs to a single task request, so is neither cached nor shared
+    async fn fetch_own<T>(
+        &self,
+        links: &HashMap<String, Url>,
+        reference: &str,
+    ) -> Result<Arc<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let url = links
+            .get(reference)
+            .ok_or_else(|| Error::MissingUri(reference.to_string()))?;
+        let value = super::unwrap_content(self.load(url).await?);
+
+        Ok(Arc::new(serde_json::from_value(value)?))
+    }
+
+    async fn fetch_many<T>(
+        &self,
+        links: &HashMap<String, Url>,
+        reference: &str,
+    ) -> Result<Vec<Arc<T>>, Error>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let url = links
+            .get(reference)
+            .ok_or_else(|| Error::MissingUri(reference.to_string()))?;
+        let embedded: Embedded<Vec<JsonValue>> = serde_json::from_value(self.fetch(url).await?)?;
+
+        embedded
+            .items
+            .into_iter()
+            .map(|value| self.share(url, value))
+            .collect()
+    }
+
+    /// Deserialize the resource, or reuse the copy already resolved under the same `self` link
+    fn share<T>(&self, url: &Url, value: JsonValue) -> Result<Arc<T>, Error>
+    where
+        T: DeserializeOwned + Send + Sync + 'static,
+    {
+        let key = value
+            .pointer("/_links/self/href")
+            .and_then(JsonValue::as_str)
+            .and_then(|href| Url::parse(href).ok());
+
+        let Some(key) = key else {
+            return Ok(Arc::new(serde_json::from_value(value)?));
+        };
+
+        if let Some(shared) = self.shared.lock().unwrap().get(&key) {
+            if let Ok(shared) = Arc::clone(shared).downcast::<T>() {
+                return Ok(shared);
+            }
+        }
+
+        tracing::trace!(%url, %key, "Resolved new resource");
+        let resolved = Arc::new(serde_json::from_value::<T>(value)?);
+        self.shared
+            .lock()
+            .unwrap()
+            .insert(key, Arc::clone(&resolved) as Shared);
+
+        Ok(resolved)
+    }
+}
+
+/// Treat a link which is missing, or which points at nothing, as absent
+async fn optional<T>(
+    lookup: impl std::future::Future<Output = Result<T, Error>>,
+) -> Result<Option<T>, Error> {
+    match lookup.await {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::MissingUri(_) | Error::NotFound { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
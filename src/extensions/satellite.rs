@@ -1,13 +1,86 @@
-use freedom_models::satellite::Satellite;
+use std::future::Future;
 
-use crate::error::Error;
+use freedom_models::{
+    account::Account, satellite::Satellite, satellite_configuration::SatelliteConfiguration,
+};
+use serde_json::Value as JsonValue;
+
+use crate::{api::Api, error::Error, ids::SatelliteId};
 
 pub trait SatelliteExt {
-    fn get_id(&self) -> Result<i32, Error>;
+    fn get_id(&self) -> Result<SatelliteId, Error>;
+
+    fn get_configuration<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<SatelliteConfiguration>, Error>> + Send
+    where
+        C: Api + Send;
+
+    fn get_account<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Account>, Error>> + Send
+    where
+        C: Api + Send;
+
+    /// The orbit information of the satellite.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_orbit_info<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
+
+    /// The upcoming visibilities of the satellite from Freedom's sites.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_upcoming_visibilities<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
 }
 
 impl SatelliteExt for Satellite {
-    fn get_id(&self) -> Result<i32, Error> {
+    fn get_id(&self) -> Result<SatelliteId, Error> {
         super::get_id("self", &self.links)
     }
+
+    async fn get_configuration<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<SatelliteConfiguration>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("configuration", &self.links, client).await
+    }
+
+    async fn get_account<C>(&self, client: &C) -> Result<<C as Api>::Container<Account>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("account", &self.links, client).await
+    }
+
+    async fn get_orbit_info<C>(&self, client: &C) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("orbitInfo", &self.links, client).await
+    }
+
+    async fn get_upcoming_visibilities<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("upcomingVisibilities", &self.links, client).await
+    }
 }
@@ -0,0 +1,47 @@
+use std::future::Future;
+
+use crate::{api::Api, error::Error, ids::SatelliteConfigurationId};
+use freedom_models::{
+    account::Account, band::Band, satellite_configuration::SatelliteConfiguration,
+};
+
+pub trait SatelliteConfigurationExt {
+    fn get_id(&self) -> Result<SatelliteConfigurationId, Error>;
+
+    fn get_band_details<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Vec<Band>>, Error>> + Send
+    where
+        C: Api + Send;
+
+    fn get_account<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Account>, Error>> + Send
+    where
+        C: Api + Send;
+}
+
+impl SatelliteConfigurationExt for SatelliteConfiguration {
+    fn get_id(&self) -> Result<SatelliteConfigurationId, Error> {
+        super::get_id("self", &self.links)
+    }
+
+    async fn get_band_details<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<Vec<Band>>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_embedded("bandDetails", &self.links, client).await
+    }
+
+    async fn get_account<C>(&self, client: &C) -> Result<<C as Api>::Container<Account>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("account", &self.links, client).await
+    }
+}
@@ -1,23 +1,60 @@
-use crate::error;
+use std::future::Future;
+
+use crate::{
+    api::Api,
+    error,
+    ids::{SiteConfigurationId, SiteId},
+};
 
 use freedom_models::site::{Site, SiteConfiguration};
 
 pub trait SiteConfigurationExt {
-    fn get_id(&self) -> Result<i32, error::Error>;
+    fn get_id(&self) -> Result<SiteConfigurationId, error::Error>;
+
+    fn get_site<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Site>, error::Error>> + Send
+    where
+        C: Api + Send;
 }
 
 impl SiteConfigurationExt for SiteConfiguration {
-    fn get_id(&self) -> Result<i32, error::Error> {
+    fn get_id(&self) -> Result<SiteConfigurationId, error::Error> {
         super::get_id("self", &self.links)
     }
+
+    async fn get_site<C>(&self, client: &C) -> Result<<C as Api>::Container<Site>, error::Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("site", &self.links, client).await
+    }
 }
 
 pub trait SiteExt {
-    fn get_id(&self) -> Result<i32, error::Error>;
+    fn get_id(&self) -> Result<SiteId, error::Error>;
+
+    fn get_configurations<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<Vec<SiteConfiguration>>, error::Error>> + Send
+    where
+        C: Api + Send;
 }
 
 impl SiteExt for Site {
-    fn get_id(&self) -> Result<i32, error::Error> {
+    fn get_id(&self) -> Result<SiteId, error::Error> {
         super::get_id("self", &self.links)
     }
+
+    async fn get_configurations<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<Vec<SiteConfiguration>>, error::Error>
+    where
+        C: Api + Send,
+    {
+        super::get_embedded("configurations", &self.links, client).await
+    }
 }
@@ -1,14 +1,15 @@
 use std::future::Future;
 
-use crate::{api::Api, error::Error};
+use crate::{api::Api, error::Error, ids::TaskId};
 use freedom_models::{
     azel::AzEl,
     site::SiteConfiguration,
     task::{Task, TaskRequest},
 };
+use serde_json::Value as JsonValue;
 
 pub trait TaskExt {
-    fn get_id(&self) -> Result<i32, Error>;
+    fn get_id(&self) -> Result<TaskId, Error>;
 
     fn get_task_request<C>(
         &self,
@@ -30,10 +31,60 @@ pub trait TaskExt {
     ) -> impl Future<Output = Result<<C as Api>::Container<AzEl>, Error>> + Send
     where
         C: Api + Send;
+
+    /// The doppler shift of the pass.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_doppler<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
+
+    /// The files produced by the task.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_file_results<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
+
+    /// The commands sent to the site during the task.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_ground_commands<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
+
+    /// The metrics recorded during the pass.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_metrics<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
+
+    /// The visibility of the satellite from the site during the pass.
+    ///
+    /// This is returned as raw JSON, since `freedom_models` does not model it yet.
+    fn get_visibility<C>(
+        &self,
+        client: &C,
+    ) -> impl Future<Output = Result<<C as Api>::Container<JsonValue>, Error>> + Send
+    where
+        C: Api + Send;
 }
 
 impl TaskExt for Task {
-    fn get_id(&self) -> Result<i32, Error> {
+    fn get_id(&self) -> Result<TaskId, Error> {
         super::get_id("self", &self.links)
     }
 
@@ -63,4 +114,45 @@ impl TaskExt for Task {
     {
         super::get_item("azel", &self.links, client).await
     }
+
+    async fn get_doppler<C>(&self, client: &C) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("doppler", &self.links, client).await
+    }
+
+    async fn get_file_results<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("fileResults", &self.links, client).await
+    }
+
+    async fn get_ground_commands<C>(
+        &self,
+        client: &C,
+    ) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("groundCommands", &self.links, client).await
+    }
+
+    async fn get_metrics<C>(&self, client: &C) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("metrics", &self.links, client).await
+    }
+
+    async fn get_visibility<C>(&self, client: &C) -> Result<<C as Api>::Container<JsonValue>, Error>
+    where
+        C: Api + Send,
+    {
+        super::get_item("visibility", &self.links, client).await
+    }
 }
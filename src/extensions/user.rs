@@ -1,10 +1,10 @@
 use std::future::Future;
 
-use crate::{api::Api, error::Error};
+use crate::{api::Api, error::Error, ids::UserId};
 use freedom_models::{account::Account, user::User};
 
 pub trait UserExt {
-    fn get_id(&self) -> Result<i32, Error>;
+    fn get_id(&self) -> Result<UserId, Error>;
 
     fn get_account<C>(&self, client: &C) -> impl Future<Output = Result<Account, Error>> + Send
     where
@@ -12,7 +12,7 @@ pub trait UserExt {
 }
 
 impl UserExt for User {
-    fn get_id(&self) -> Result<i32, Error> {
+    fn get_id(&self) -> Result<UserId, Error> {
         super::get_id("self", &self.links)
     }
 
@@ -20,6 +20,6 @@ impl UserExt for User {
     where
         C: Api + Send,
     {
-        super::get_content("account", &self.links, client).await
+        super::get_item("account", &self.links, client).await
     }
 }
@@ -0,0 +1,103 @@
+//! # Resource IDs
+//!
+//! Freedom identifies every resource with an integer. Each kind of resource has its own ID type,
+//! so that the ID of one kind of resource cannot be passed where another is expected.
+//!
+//! Every ID converts from and into an `i32`, so untyped IDs may still be used where an ID is
+//! expected.
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+macro_rules! ids {
+    ($($(#[$meta:meta])* $name:ident),* $(,)?) => {$(
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(i32);
+
+        impl $name {
+            pub const fn new(id: i32) -> Self {
+                Self(id)
+            }
+
+            /// Returns the underlying integer of the ID
+            pub const fn get(self) -> i32 {
+                self.0
+            }
+        }
+
+        impl From<i32> for $name {
+            fn from(id: i32) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+    )*};
+}
+
+ids! {
+    /// The ID of an [`Account`](freedom_models::account::Account)
+    AccountId,
+    /// The ID of a [`Band`](freedom_models::band::Band)
+    BandId,
+    /// The ID of an override
+    OverrideId,
+    /// The ID of a [`Satellite`](freedom_models::satellite::Satellite)
+    SatelliteId,
+    /// The ID of a [`SatelliteConfiguration`](freedom_models::satellite_configuration::SatelliteConfiguration)
+    SatelliteConfigurationId,
+    /// The ID of a [`Site`](freedom_models::site::Site)
+    SiteId,
+    /// The ID of a [`SiteConfiguration`](freedom_models::site::SiteConfiguration)
+    SiteConfigurationId,
+    /// The ID of a [`Task`](freedom_models::task::Task)
+    TaskId,
+    /// The ID of a [`TaskRequest`](freedom_models::task::TaskRequest)
+    TaskRequestId,
+    /// The ID of a [`User`](freedom_models::user::User)
+    UserId,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_convert_to_and_from_integers() {
+        let id = SatelliteId::from(710);
+        assert_eq!(id, SatelliteId::new(710));
+        assert_eq!(i32::from(id), 710);
+        assert_eq!(id.to_string(), "710");
+        assert_eq!("710".parse::<SatelliteId>().unwrap(), id);
+        assert!("FooBar".parse::<SatelliteId>().is_err());
+    }
+
+    #[test]
+    fn ids_serialize_as_integers() {
+        let id = BandId::new(1573);
+        assert_eq!(serde_json::to_string(&id).unwrap(), "1573");
+        assert_eq!(serde_json::from_str::<BandId>("1573").unwrap(), id);
+    }
+}
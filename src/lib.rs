@@ -6,29 +6,78 @@ mod caching_client;
 mod client;
 pub mod error;
 pub mod extensions;
+mod ids;
+#[cfg(feature = "tower")]
+pub mod middleware;
+#[cfg(feature = "plans")]
+pub mod plan;
+mod rate_limit;
+mod retry;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod utils;
+mod window;
 
+#[cfg(feature = "caching")]
+pub use self::caching_client::{CachingClient, CachingClientBuilder};
 pub use self::{
-    api::{Api, Container, Inner, PaginatedStream, Value},
-    client::Client,
+    api::{
+        download::{Download, Progress},
+        pagination::{Direction, PaginatedStream, PaginationOptions},
+        post::{
+            request::TaskRequest as TaskRequestPayload, BatchReport, BatchResult, BatchSubmission,
+            TaskRequestBatch,
+        },
+        query::{RequestQuery, TaskQuery},
+        update::{
+            BandDetailsUpdateBuilder, SatelliteConfigurationUpdateBuilder, SatelliteUpdateBuilder,
+            UserUpdateBuilder,
+        },
+        watch::{RequestEvent, RequestWatch, StatusChange},
+        Api, Container, Inner, Value,
+    },
+    client::{Client, ClientBuilder},
+    ids::{
+        AccountId, BandId, OverrideId, SatelliteConfigurationId, SatelliteId, SiteConfigurationId,
+        SiteId, TaskId, TaskRequestId, UserId,
+    },
+    rate_limit::RateLimit,
+    retry::RetryPolicy,
+    window::TimeWindow,
 };
 
 /// Contains the client, data models, and traits necessary for queries
 pub mod prelude {
     #[cfg(feature = "caching")]
-    pub use crate::caching_client::CachingClient;
+    pub use crate::caching_client::{CachingClient, CachingClientBuilder};
     pub use crate::{
         api::{
+            download::{Download, Progress},
+            pagination::{Direction, PaginatedStream, PaginationOptions},
             post::{
-                BandDetailsBuilder, OverrideBuilder, SatelliteBuilder,
-                SatelliteConfigurationBuilder, UserBuilder,
+                request::TaskRequest as TaskRequestPayload, BandDetailsBuilder, BatchReport,
+                BatchResult, BatchSubmission, OverrideBuilder, SatelliteBuilder,
+                SatelliteConfigurationBuilder, TaskRequestBatch, UserBuilder,
             },
-            Api, Container, Inner, PaginatedStream, Value,
+            query::{RequestQuery, TaskQuery},
+            update::{
+                BandDetailsUpdateBuilder, SatelliteConfigurationUpdateBuilder,
+                SatelliteUpdateBuilder, UserUpdateBuilder,
+            },
+            watch::{RequestEvent, RequestWatch, StatusChange},
+            Api, Container, Inner, Value,
         },
-        client::Client,
+        client::{Client, ClientBuilder},
         config::*,
         extensions::*,
+        ids::{
+            AccountId, BandId, OverrideId, SatelliteConfigurationId, SatelliteId,
+            SiteConfigurationId, SiteId, TaskId, TaskRequestId, UserId,
+        },
         models::*,
+        rate_limit::RateLimit,
+        retry::RetryPolicy,
+        window::TimeWindow,
     };
 }
 
@@ -0,0 +1,612 @@
+//! # Tower Middleware
+//!
+//! With the `tower` feature enabled, a [`Client`](crate::Client) may send its requests through any
+//! [`tower::Service`] which takes an [`http::Request`] and returns an [`http::Response`], with
+//! [`Client::from_service`](crate::Client::from_service).
+//!
+//! Such a client sends each request through the service as is, so the behaviour the client
+//! otherwise provides is added with the layers of this module instead: [`AuthLayer`] attaches
+//! credentials, [`RetryLayer`] retries transient failures, [`TimeoutLayer`] bounds the duration of
+//! each attempt, and [`TraceLayer`] records each request with `tracing`. Requests reach Freedom
+//! through [`HttpService`] at the bottom of the stack, or through a mock service in tests.
+//!
+//! # Example
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use freedom_api::{middleware::*, prelude::*};
+//! # let config = Config::builder()
+//! #     .environment(Test)
+//! #     .key("foo")
+//! #     .secret("bar")
+//! #     .build()
+//! #     .unwrap();
+//! let service = tower::ServiceBuilder::new()
+//!     .layer(TraceLayer::new())
+//!     .layer(RetryLayer::new(RetryPolicy::default()))
+//!     .layer(TimeoutLayer::new(Duration::from_secs(30)))
+//!     .layer(AuthLayer::new(&config))
+//!     .service(HttpService::new(reqwest::Client::new()));
+//!
+//! let client = Client::from_service(config, service);
+//! ```
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use freedom_config::Config;
+use http::{header::AUTHORIZATION, HeaderValue, Request, Response};
+use tower::{util::BoxCloneSyncService, Layer, Service, ServiceExt};
+use tracing::Instrument;
+use url::{Origin, Url};
+
+use crate::{
+    error::Error,
+    retry::{self, RetryPolicy},
+};
+
+/// The error type of the services in this module
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The future returned by the services in this module
+pub type ResponseFuture =
+    Pin<Box<dyn Future<Output = Result<Response<Bytes>, BoxError>> + Send + 'static>>;
+
+/// The service through which a client built with [`Client::from_service`](crate::Client::from_service)
+/// sends its requests
+pub(crate) type BoxService = BoxCloneSyncService<Request<Bytes>, Response<Bytes>, BoxError>;
+
+/// Send the request through the service, converting between the types of `reqwest` and `http`
+pub(crate) async fn send(
+    service: BoxService,
+    request: reqwest::Request,
+) -> Result<reqwest::Response, Error> {
+    let body = request
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .map(Bytes::copy_from_slice)
+        .unwrap_or_default();
+
+    let mut http_request = Request::new(body);
+    *http_request.method_mut() = request.method().clone();
+    *http_request.uri_mut() = request
+        .url()
+        .as_str()
+        .parse()
+        .map_err(|_| Error::InvalidUri(request.url().to_string()))?;
+    *http_request.headers_mut() = request.headers().clone();
+
+    // Services only return futures which are `Send`, while all `Api` futures must be `Sync`
+    let response = sync_wrapper::SyncFuture::new(service.oneshot(http_request))
+        .await
+        .map_err(into_error)?;
+
+    Ok(reqwest::Response::from(response))
+}
+
+/// Recover the client's error from the error of a service
+fn into_error(error: BoxError) -> Error {
+    let error = match error.downcast::<Error>() {
+        Ok(error) => return *error,
+        Err(error) => error,
+    };
+
+    match error.downcast::<reqwest::Error>() {
+        Ok(error) => Error::from(*error),
+        Err(error) => Error::Response(error.to_string()),
+    }
+}
+
+/// The URL of the request, which `http` holds as a URI
+fn request_url(request: &Request<Bytes>) -> Result<Url, Error> {
+    let uri = request.uri().to_string();
+    Url::parse(&uri).map_err(|_| Error::InvalidUri(uri))
+}
+
+/// The service at the bottom of a stack, sending requests to Freedom with a [`reqwest::Client`]
+#[derive(Debug, Clone, Default)]
+pub struct HttpService {
+    client: reqwest::Client,
+}
+
+impl HttpService {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Service<Request<Bytes>> for HttpService {
+    type Response = Response<Bytes>;
+    type Error = BoxError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        let client = self.client.clone();
+
+        Box::pin(async move {
+            let request = reqwest::Request::try_from(request)?;
+            let response = client.execute(request).await?;
+
+            let status = response.status();
+            let version = response.version();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+
+            let mut response = Response::new(body);
+            *response.status_mut() = status;
+            *response.version_mut() = version;
+            *response.headers_mut() = headers;
+
+            Ok(response)
+        })
+    }
+}
+
+/// Attaches the credentials of a config to requests.
+///
+/// As with [`Client`](crate::Client), credentials are only sent to the origin of the environment's
+/// entrypoint and to any origin allowed with [`Self::allowed_origin`]. Requests to any other
+/// origin fail with [`Error::CrossOrigin`] without reaching the inner service.
+#[derive(Debug, Clone)]
+pub struct AuthLayer {
+    authorization: HeaderValue,
+    trusted: Vec<Origin>,
+}
+
+impl AuthLayer {
+    pub fn new(config: &Config) -> Self {
+        let credentials = format!("{}:{}", config.key(), config.expose_secret());
+        let mut authorization =
+            HeaderValue::try_from(format!("Basic {}", STANDARD.encode(credentials)))
+                .expect("Base64 is always a valid header value");
+        authorization.set_sensitive(true);
+
+        Self {
+            authorization,
+            trusted: vec![config.environment().freedom_entrypoint().origin()],
+        }
+    }
+
+    /// Allow credentials to be sent to the origin of the provided URL, in addition to the origin
+    /// of the environment.
+    pub fn allowed_origin(mut self, url: &Url) -> Self {
+        self.trusted.push(url.origin());
+        self
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = Auth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Auth {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// The service produced by [`AuthLayer`]
+#[derive(Debug, Clone)]
+pub struct Auth<S> {
+    inner: S,
+    layer: AuthLayer,
+}
+
+impl<S> Service<Request<Bytes>> for Auth<S>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Bytes>;
+    type Error = BoxError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, mut request: Request<Bytes>) -> Self::Future {
+        let url = match request_url(&request) {
+            Ok(url) => url,
+            Err(error) => return Box::pin(std::future::ready(Err(error.into()))),
+        };
+        if !self.layer.trusted.contains(&url.origin()) {
+            let method = request.method();
+            tracing::warn!(%method, %url, "Refusing request outside the configured environment");
+            let error = Error::CrossOrigin(url.to_string());
+            return Box::pin(std::future::ready(Err(error.into())));
+        }
+
+        request
+            .headers_mut()
+            .insert(AUTHORIZATION, self.layer.authorization.clone());
+        let response = self.inner.call(request);
+
+        Box::pin(async move { response.await.map_err(Into::into) })
+    }
+}
+
+/// Retries requests which failed due to transient errors, according to a [`RetryPolicy`]
+#[derive(Debug, Clone)]
+pub struct RetryLayer {
+    policy: RetryPolicy,
+}
+
+impl RetryLayer {
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S> Layer<S> for RetryLayer {
+    type Service = Retry<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Retry {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+/// The service produced by [`RetryLayer`]
+#[derive(Debug, Clone)]
+pub struct Retry<S> {
+    inner: S,
+    policy: RetryPolicy,
+}
+
+impl<S> Service<Request<Bytes>> for Retry<S>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Bytes>;
+    type Error = BoxError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        // Take the service which was driven to readiness, leaving a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let policy = self.policy.clone();
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+
+            let mut attempt = 1;
+            loop {
+                let mut current = Request::new(body.clone());
+                *current.method_mut() = parts.method.clone();
+                *current.uri_mut() = parts.uri.clone();
+                *current.version_mut() = parts.version;
+                *current.headers_mut() = parts.headers.clone();
+
+                let result = match inner.ready().await.map_err(Into::into) {
+                    Ok(inner) => inner.call(current).await.map_err(Into::into),
+                    Err(error) => Err(error),
+                };
+                let (delay, reason) = match result {
+                    Ok(resp) if retry::is_transient_status(resp.status()) => {
+                        let retry_after = retry::retry_after(resp.headers());
+                        match policy.next_delay(&parts.method, attempt, retry_after) {
+                            Some(delay) => (delay, resp.status().to_string()),
+                            None => return Ok(resp),
+                        }
+                    }
+                    Ok(resp) => return Ok(resp),
+                    Err(error) if is_transient_error(&*error) => {
+                        match policy.next_delay(&parts.method, attempt, None) {
+                            Some(delay) => (delay, error.to_string()),
+                            None => return Err(error),
+                        }
+                    }
+                    Err(error) => return Err(error),
+                };
+
+                let method = &parts.method;
+                let uri = &parts.uri;
+                tracing::warn!(%method, %uri, attempt, ?delay, %reason, "Retrying failed request");
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        })
+    }
+}
+
+/// Whether the error of a service indicates a failure which may succeed if retried
+fn is_transient_error(error: &(dyn std::error::Error + Send + Sync + 'static)) -> bool {
+    if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+        return retry::is_transient_error(error);
+    }
+
+    error.is::<tokio::time::error::Elapsed>()
+}
+
+/// Fails requests which take longer than the provided duration
+#[derive(Debug, Clone)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+impl TimeoutLayer {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S> Layer<S> for TimeoutLayer {
+    type Service = Timeout<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Timeout {
+            inner,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// The service produced by [`TimeoutLayer`]
+#[derive(Debug, Clone)]
+pub struct Timeout<S> {
+    inner: S,
+    timeout: Duration,
+}
+
+impl<S> Service<Request<Bytes>> for Timeout<S>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Bytes>;
+    type Error = BoxError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        let response = tokio::time::timeout(self.timeout, self.inner.call(request));
+
+        Box::pin(async move { response.await?.map_err(Into::into) })
+    }
+}
+
+/// Records each request, along with its outcome and duration, with `tracing`
+#[derive(Debug, Clone, Default)]
+pub struct TraceLayer {
+    _priv: (),
+}
+
+impl TraceLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for TraceLayer {
+    type Service = Trace<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Trace { inner }
+    }
+}
+
+/// The service produced by [`TraceLayer`]
+#[derive(Debug, Clone)]
+pub struct Trace<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Bytes>> for Trace<S>
+where
+    S: Service<Request<Bytes>, Response = Response<Bytes>>,
+    S::Error: Into<BoxError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Bytes>;
+    type Error = BoxError;
+    type Future = ResponseFuture;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request<Bytes>) -> Self::Future {
+        let method = request.method().clone();
+        let uri = request.uri().clone();
+        let span = tracing::debug_span!("freedom_request", %method, %uri);
+        let response = span.in_scope(|| self.inner.call(request));
+
+        Box::pin(
+            async move {
+                let start = tokio::time::Instant::now();
+                let result = response.await.map_err(Into::into);
+                let elapsed = start.elapsed();
+                match &result {
+                    Ok(response) => {
+                        let status = response.status().as_u16();
+                        tracing::debug!(status, ?elapsed, "Received response");
+                    }
+                    Err(error) => tracing::warn!(%error, ?elapsed, "Request failed"),
+                }
+
+                result
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use freedom_config::Test;
+    use httpmock::{Method::GET, MockServer};
+    use reqwest::StatusCode;
+    use tower::{service_fn, ServiceBuilder};
+
+    use crate::{Api, Client};
+
+    use super::*;
+
+    fn test_config() -> Config {
+        Config::builder()
+            .environment(Test)
+            .key("foo")
+            .secret("bar")
+            .build()
+            .unwrap()
+    }
+
+    fn entrypoint(path: &str) -> Url {
+        test_config()
+            .environment()
+            .freedom_entrypoint()
+            .join(path)
+            .unwrap()
+    }
+
+    fn respond(status: u16, body: &'static str) -> Response<Bytes> {
+        let mut response = Response::new(Bytes::from_static(body.as_bytes()));
+        *response.status_mut() = StatusCode::from_u16(status).unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn client_sends_requests_through_the_service() {
+        let service = service_fn(|request: Request<Bytes>| async move {
+            assert_eq!(request.method(), http::Method::POST);
+            assert_eq!(request.body(), r#"{"name":"foo"}"#);
+            Ok::<_, BoxError>(respond(201, "created"))
+        });
+        let client = Client::from_service(test_config(), service);
+
+        let response = client
+            .post(
+                entrypoint("satellites"),
+                serde_json::json!({ "name": "foo" }),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.text().await.unwrap(), "created");
+    }
+
+    #[tokio::test]
+    async fn auth_layer_only_sends_credentials_to_the_environment() {
+        let service = ServiceBuilder::new()
+            .layer(AuthLayer::new(&test_config()))
+            .service_fn(|request: Request<Bytes>| async move {
+                let authorization = request.headers().get(AUTHORIZATION).unwrap();
+                assert_eq!(authorization, "Basic Zm9vOmJhcg==");
+                assert!(authorization.is_sensitive());
+                Ok::<_, BoxError>(respond(200, ""))
+            });
+        let client = Client::from_service(test_config(), service);
+
+        let (_, status) = client.get(entrypoint("satellites")).await.unwrap();
+        assert_eq!(status, StatusCode::OK);
+
+        let elsewhere = Url::parse("https://example.com/satellites").unwrap();
+        let error = client.get(elsewhere.clone()).await.unwrap_err();
+        assert_eq!(error, Error::CrossOrigin(elsewhere.to_string()));
+    }
+
+    #[tokio::test]
+    async fn retry_layer_retries_transient_failures() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let policy = RetryPolicy::default()
+            .max_attempts(3)
+            .initial_backoff(Duration::from_millis(1));
+        let service = ServiceBuilder::new()
+            .layer(RetryLayer::new(policy))
+            .service_fn(move |_: Request<Bytes>| {
+                let attempt = counter.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match attempt {
+                        0 => Ok::<_, BoxError>(respond(503, "")),
+                        _ => Ok(respond(200, "done")),
+                    }
+                }
+            });
+        let client = Client::from_service(test_config(), service);
+
+        let (body, status) = client.get(entrypoint("satellites")).await.unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "done");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn timeout_layer_fails_slow_requests() {
+        let service = ServiceBuilder::new()
+            .layer(TimeoutLayer::new(Duration::from_millis(20)))
+            .service_fn(|_: Request<Bytes>| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                Ok::<_, BoxError>(respond(200, ""))
+            });
+        let client = Client::from_service(test_config(), service);
+
+        let error = client.get(entrypoint("satellites")).await.unwrap_err();
+        assert!(matches!(error, Error::Response(_)));
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn trace_layer_records_responses() {
+        let service = ServiceBuilder::new()
+            .layer(TraceLayer::new())
+            .service_fn(|_: Request<Bytes>| async { Ok::<_, BoxError>(respond(404, "")) });
+        let client = Client::from_service(test_config(), service);
+
+        client.get(entrypoint("satellites")).await.unwrap();
+        assert!(logs_contain("Received response"));
+        assert!(logs_contain("status=404"));
+    }
+
+    #[tokio::test]
+    async fn http_service_reaches_freedom() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/satellites")
+                .header("authorization", "Basic Zm9vOmJhcg==");
+            then.status(200).header("x-freedom", "yes").body("ok");
+        });
+        let url = Url::parse(&server.url("/satellites")).unwrap();
+
+        let service = ServiceBuilder::new()
+            .layer(AuthLayer::new(&test_config()).allowed_origin(&url))
+            .service(HttpService::default());
+        let client = Client::from_service(test_config(), service);
+
+        let response = client.get_stream(url, 0).await.unwrap();
+        assert_eq!(response.headers()["x-freedom"], "yes");
+        assert_eq!(response.text().await.unwrap(), "ok");
+        mock.assert_hits(1);
+    }
+}
@@ -0,0 +1,770 @@
+//! # Task Request Plans
+//!
+//! A plan is a list of task requests kept in a file, so that it may be reviewed and replayed. The
+//! resources of each request are referred to by name or by ID, rather than by URL, so that a plan
+//! is readable and may be used against any environment. IDs are written with a leading `#`, see
+//! [`Reference`].
+//!
+//! Plans are read and written as CSV, JSON, or YAML. In JSON and YAML, a plan is a list of
+//! requests, while in CSV each row is a request and the bands are separated by semicolons:
+//!
+//! ```csv
+//! type,satellite,site,configuration,bands,targetDate,duration,minimumDuration,hoursOfFlex,testFile,override
+//! EXACT,FooBar 6,LOAG,LOAG S-Band,#1573;S-Band Downlink,2030-01-01T12:00:00Z,600,,,,#42
+//! ```
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{Read, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use freedom_models::task::TaskType;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime};
+use url::Url;
+
+use crate::{
+    api::{
+        post::request::{FlexTaskKind, NoTime, TaskInner, TaskRequest, TaskRequestBuilder},
+        Api,
+    },
+    error::{Error, FieldError},
+    extensions::{BandExt, SatelliteExt, SiteConfigurationExt, SiteExt},
+    ids::{BandId, OverrideId, SatelliteId, SiteConfigurationId, SiteId},
+};
+
+/// The separator of the bands of a request in a CSV plan
+const CSV_BAND_SEPARATOR: char = ';';
+
+/// A resource referred to by a plan, either by its ID or by its name.
+///
+/// In every format, an ID is written with a leading `#`, e.g. `#710`, so that names made only of
+/// digits, such as `2017`, are not mistaken for IDs. A name which itself starts with `#` is written
+/// with a second `#`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Reference {
+    Id(i32),
+    Name(String),
+}
+
+impl From<i32> for Reference {
+    fn from(id: i32) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<&str> for Reference {
+    fn from(name: &str) -> Self {
+        Self::Name(name.to_string())
+    }
+}
+
+impl From<String> for Reference {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+impl FromStr for Reference {
+    type Err = std::convert::Infallible;
+
+    /// Parse an ID if the text is an integer following a `#`, and a name otherwise
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(name) = s.strip_prefix("##") {
+            return Ok(Self::Name(format!("#{name}")));
+        }
+
+        match s.strip_prefix('#').map(str::parse) {
+            Some(Ok(id)) => Ok(Self::Id(id)),
+            _ => Ok(Self::from(s)),
+        }
+    }
+}
+
+impl fmt::Display for Reference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Id(id) => write!(f, "#{id}"),
+            Self::Name(name) if name.starts_with('#') => write!(f, "#{name}"),
+            Self::Name(name) => name.fmt(f),
+        }
+    }
+}
+
+impl Serialize for Reference {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Reference {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Ok(text.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+/// A single task request of a [`Plan`].
+///
+/// The payload of a task request builder may be exported into a planned request with
+/// [`TryFrom`], referring to each resource by its ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlannedRequest {
+    #[serde(rename = "type")]
+    pub task_type: TaskType,
+    pub satellite: Reference,
+    pub site: Reference,
+    /// The site configuration, which when referred to by name is looked up among those of the site
+    pub configuration: Reference,
+    pub bands: Vec<Reference>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub target_date: OffsetDateTime,
+    /// The duration of the task, in seconds
+    pub duration: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_duration: Option<u64>,
+    /// Required by `BEFORE`, `AFTER`, and `AROUND` tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours_of_flex: Option<u8>,
+    /// Required by `TEST` tasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_file: Option<String>,
+    /// The override applied to the task, which may only be referred to by ID
+    #[serde(default, rename = "override", skip_serializing_if = "Option::is_none")]
+    pub with_override: Option<Reference>,
+}
+
+impl TryFrom<&TaskRequest> for PlannedRequest {
+    type Error = Error;
+
+    fn try_from(payload: &TaskRequest) -> Result<Self, Self::Error> {
+        let format = format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]Z");
+        let target_date = PrimitiveDateTime::parse(payload.target_date(), format)
+            .map_err(|error| Error::TimeFormatError(error.to_string()))?
+            .assume_utc();
+        let bands = payload
+            .target_bands()
+            .iter()
+            .map(|band| id_of(band))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            task_type: payload.task_type(),
+            satellite: id_of(payload.satellite())?,
+            site: id_of(payload.site())?,
+            configuration: id_of(payload.configuration())?,
+            bands,
+            target_date,
+            duration: payload.duration(),
+            minimum_duration: payload.minimum_duration(),
+            hours_of_flex: payload.hours_of_flex(),
+            test_file: payload.test_file().map(String::from),
+            with_override: payload.override_url().map(id_of).transpose()?,
+        })
+    }
+}
+
+/// Refer to the resource at the URL by the ID at the end of its path
+fn id_of(url: &str) -> Result<Reference, Error> {
+    let parsed = Url::parse(url).map_err(|_| Error::InvalidUri(url.to_string()))?;
+
+    parsed
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|id| id.parse().ok())
+        .map(Reference::Id)
+        .ok_or(Error::InvalidId)
+}
+
+/// The flat representation of a [`PlannedRequest`] used by CSV, which has no lists
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CsvRow {
+    #[serde(rename = "type")]
+    task_type: TaskType,
+    satellite: String,
+    site: String,
+    configuration: String,
+    bands: String,
+    #[serde(with = "time::serde::rfc3339")]
+    target_date: OffsetDateTime,
+    duration: u64,
+    minimum_duration: Option<u64>,
+    hours_of_flex: Option<u8>,
+    test_file: Option<String>,
+    #[serde(default, rename = "override")]
+    with_override: Option<String>,
+}
+
+impl From<&PlannedRequest> for CsvRow {
+    fn from(request: &PlannedRequest) -> Self {
+        let bands: Vec<_> = request.bands.iter().map(ToString::to_string).collect();
+
+        Self {
+            task_type: request.task_type,
+            satellite: request.satellite.to_string(),
+            site: request.site.to_string(),
+            configuration: request.configuration.to_string(),
+            bands: bands.join(&CSV_BAND_SEPARATOR.to_string()),
+            target_date: request.target_date,
+            duration: request.duration,
+            minimum_duration: request.minimum_duration,
+            hours_of_flex: request.hours_of_flex,
+            test_file: request.test_file.clone(),
+            with_override: request.with_override.as_ref().map(ToString::to_string),
+        }
+    }
+}
+
+impl From<CsvRow> for PlannedRequest {
+    fn from(row: CsvRow) -> Self {
+        let reference = |s: &str| Reference::from_str(s).unwrap_or_else(|never| match never {});
+        let bands = row
+            .bands
+            .split(CSV_BAND_SEPARATOR)
+            .filter(|band| !band.trim().is_empty())
+            .map(reference)
+            .collect();
+
+        Self {
+            task_type: row.task_type,
+            satellite: reference(&row.satellite),
+            site: reference(&row.site),
+            configuration: reference(&row.configuration),
+            bands,
+            target_date: row.target_date,
+            duration: row.duration,
+            minimum_duration: row.minimum_duration,
+            hours_of_flex: row.hours_of_flex,
+            test_file: row.test_file.filter(|file| !file.is_empty()),
+            with_override: row
+                .with_override
+                .filter(|with_override| !with_override.trim().is_empty())
+                .map(|with_override| reference(&with_override)),
+        }
+    }
+}
+
+/// The file format of a [`Plan`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlanFormat {
+    Csv,
+    Json,
+    Yaml,
+}
+
+impl PlanFormat {
+    /// The format matching the extension of the path, if any
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            _ => None,
+        }
+    }
+}
+
+/// A list of task requests to be made, which may be kept in a file.
+///
+/// See the [module](self) documentation for the file formats.
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::{plan::Plan, prelude::*};
+/// # use futures::StreamExt;
+/// # tokio_test::block_on(async {
+/// let client = Client::from_env()?;
+///
+/// let plan = Plan::open("campaign.csv")?;
+/// let requests = plan.resolve(&client).await?;
+///
+/// let report = TaskRequestBatch::new(&client)
+///     .requests(requests)
+///     .submit()
+///     .into_report()
+///     .await;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Plan {
+    pub requests: Vec<PlannedRequest>,
+}
+
+impl Plan {
+    pub fn new(requests: impl IntoIterator<Item = PlannedRequest>) -> Self {
+        Self {
+            requests: requests.into_iter().collect(),
+        }
+    }
+
+    /// Read a plan in the provided format
+    pub fn from_reader(reader: impl Read, format: PlanFormat) -> Result<Self, Error> {
+        match format {
+            PlanFormat::Csv => {
+                let requests = csv::Reader::from_reader(reader)
+                    .deserialize::<CsvRow>()
+                    .map(|row| row.map(PlannedRequest::from))
+                    .collect::<Result<_, _>>()
+                    .map_err(|error| Error::Deserialization(error.to_string()))?;
+
+                Ok(Self { requests })
+            }
+            PlanFormat::Json => serde_json::from_reader(reader).map_err(From::from),
+            PlanFormat::Yaml => serde_norway::from_reader(reader)
+                .map_err(|error| Error::Deserialization(error.to_string())),
+        }
+    }
+
+    /// Write the plan in the provided format
+    pub fn to_writer(&self, writer: impl Write, format: PlanFormat) -> Result<(), Error> {
+        match format {
+            PlanFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(writer);
+                for request in &self.requests {
+                    writer
+                        .serialize(CsvRow::from(request))
+                        .map_err(|error| Error::Serialization(error.to_string()))?;
+                }
+
+                writer.flush().map_err(From::from)
+            }
+            PlanFormat::Json => serde_json::to_writer_pretty(writer, self)
+                .map_err(|error| Error::Serialization(error.to_string())),
+            PlanFormat::Yaml => serde_norway::to_writer(writer, self)
+                .map_err(|error| Error::Serialization(error.to_string())),
+        }
+    }
+
+    /// Read the plan at the path, in the format matching its extension
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let format = format_of(path.as_ref())?;
+        let file = std::fs::File::open(path)?;
+
+        Self::from_reader(std::io::BufReader::new(file), format)
+    }
+
+    /// Write the plan to the path, in the format matching its extension
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let format = format_of(path.as_ref())?;
+        let file = std::fs::File::create(path)?;
+
+        self.to_writer(std::io::BufWriter::new(file), format)
+    }
+
+    /// Look up the resources referred to by the plan, producing a finished task request builder for
+    /// each request, in order.
+    ///
+    /// Satellites, sites, and bands referred to by name are looked up by their name, while site
+    /// configurations are looked up among the configurations of their site. Each name is looked up
+    /// once, however many requests refer to it.
+    ///
+    /// Every reference which does not exist, and every request missing a field required by its type,
+    /// is returned at once as an [`Error::InvalidRequest`], with fields named after the position of
+    /// the request, e.g. `requests[2].satellite`. Failures to look up a resource for any other
+    /// reason are returned as is.
+    pub async fn resolve<'a, C>(
+        &self,
+        client: &'a C,
+    ) -> Result<Vec<TaskRequestBuilder<'a, C, TaskRequest>>, Error>
+    where
+        C: Api,
+    {
+        let mut resolver = Resolver {
+            client,
+            satellites: HashMap::new(),
+            sites: HashMap::new(),
+            configurations: HashMap::new(),
+            bands: HashMap::new(),
+        };
+        let mut problems = Vec::new();
+        let mut builders = Vec::new();
+
+        for (index, request) in self.requests.iter().enumerate() {
+            let mut problem = |field: &str, message: String| {
+                problems.push(FieldError {
+                    field: format!("requests[{index}].{field}"),
+                    message,
+                });
+            };
+
+            let satellite = resolver.satellite(&request.satellite).await?;
+            let site = resolver.site(&request.site).await?;
+            let configuration = match site {
+                Some(site) => resolver.configuration(site, &request.configuration).await?,
+                None => None,
+            };
+            let mut bands = Vec::new();
+            for band in &request.bands {
+                match resolver.band(band).await? {
+                    Some(id) => bands.push(id),
+                    None => problem("bands", format!("no band {band} exists")),
+                }
+            }
+
+            if satellite.is_none() {
+                problem(
+                    "satellite",
+                    format!("no satellite {} exists", request.satellite),
+                );
+            }
+            if site.is_none() {
+                problem("site", format!("no site {} exists", request.site));
+            }
+            if site.is_some() && configuration.is_none() {
+                let message = format!("the site has no configuration {}", request.configuration);
+                problem("configuration", message);
+            }
+            let with_override = match &request.with_override {
+                Some(Reference::Id(id)) => Some(OverrideId::new(*id)),
+                Some(Reference::Name(name)) => {
+                    let message = format!("overrides may only be referred to by ID, found {name}");
+                    problem("override", message);
+                    None
+                }
+                None => None,
+            };
+
+            let Some(kind) = kind(request, &mut problem) else {
+                continue;
+            };
+            let (Some(satellite), Some(site), Some(configuration)) =
+                (satellite, site, configuration)
+            else {
+                continue;
+            };
+            if bands.len() != request.bands.len() {
+                continue;
+            }
+
+            let resolved = Resolved {
+                satellite,
+                site,
+                configuration,
+                bands,
+            };
+            let builder = client.new_task_request();
+            let mut builder = match kind {
+                Kind::Exact => finish(builder.exact_task(), request, resolved),
+                Kind::Test(file) => finish(builder.test_task(file), request, resolved),
+                Kind::Flex(typ, hours) => finish(builder.flex_task(typ, hours), request, resolved),
+            };
+            if let Some(minimum) = request.minimum_duration {
+                builder = builder.task_minimum_duration(minimum);
+            }
+            if let Some(id) = with_override {
+                builder = builder.override_id(id);
+            }
+
+            builders.push(builder);
+        }
+
+        match problems.is_empty() {
+            true => Ok(builders),
+            false => Err(Error::InvalidRequest(problems)),
+        }
+    }
+}
+
+fn format_of(path: &Path) -> Result<PlanFormat, Error> {
+    PlanFormat::from_path(path).ok_or_else(|| {
+        Error::Io(format!(
+            "{} is not a CSV, JSON, or YAML plan",
+            path.display()
+        ))
+    })
+}
+
+enum Kind {
+    Exact,
+    Test(String),
+    Flex(FlexTaskKind, u8),
+}
+
+/// The kind of task of the request, or `None` if a field required by its type is missing
+fn kind(request: &PlannedRequest, problem: &mut impl FnMut(&str, String)) -> Option<Kind> {
+    let flex = match request.task_type {
+        TaskType::Exact => return Some(Kind::Exact),
+        TaskType::Test => {
+            let file = request.test_file.clone();
+            if file.is_none() {
+                problem("testFile", String::from("is required by TEST tasks"));
+            }
+            return file.map(Kind::Test);
+        }
+        TaskType::Before => FlexTaskKind::Before,
+        TaskType::After => FlexTaskKind::After,
+        TaskType::Around => FlexTaskKind::Around,
+        other => {
+            problem("type", format!("{other:?} tasks are not supported"));
+            return None;
+        }
+    };
+
+    match request.hours_of_flex {
+        Some(hours) => Some(Kind::Flex(flex, hours)),
+        None => {
+            problem("hoursOfFlex", String::from("is required by flexible tasks"));
+            None
+        }
+    }
+}
+
+/// The IDs of the resources of a request
+struct Resolved {
+    satellite: SatelliteId,
+    site: SiteId,
+    configuration: SiteConfigurationId,
+    bands: Vec<BandId>,
+}
+
+fn finish<'a, C, T>(
+    builder: TaskRequestBuilder<'a, C, NoTime<T>>,
+    request: &PlannedRequest,
+    resolved: Resolved,
+) -> TaskRequestBuilder<'a, C, TaskRequest>
+where
+    C: Api,
+    T: TaskInner,
+{
+    builder
+        .target_time_utc(request.target_date)
+        .task_duration(request.duration)
+        .satellite_id(resolved.satellite)
+        .site_id(resolved.site)
+        .site_configuration_id(resolved.configuration)
+        .band_ids(resolved.bands)
+}
+
+/// Looks up the references of a plan, remembering the ID of each
+struct Resolver<'a, C> {
+    client: &'a C,
+    satellites: HashMap<String, Option<SatelliteId>>,
+    sites: HashMap<String, Option<SiteId>>,
+    configurations: HashMap<SiteId, Vec<(String, SiteConfigurationId)>>,
+    bands: HashMap<String, Option<BandId>>,
+}
+
+impl<C: Api> Resolver<'_, C> {
+    async fn satellite(&mut self, reference: &Reference) -> Result<Option<SatelliteId>, Error> {
+        let name = match reference {
+            Reference::Id(id) => return Ok(Some(SatelliteId::new(*id))),
+            Reference::Name(name) => name,
+        };
+        if let Some(id) = self.satellites.get(name) {
+            return Ok(*id);
+        }
+
+        let found = found(self.client.get_satellite_by_name(name).await)?;
+        let id = found.map(|satellite| satellite.get_id()).transpose()?;
+        self.satellites.insert(name.clone(), id);
+
+        Ok(id)
+    }
+
+    async fn site(&mut self, reference: &Reference) -> Result<Option<SiteId>, Error> {
+        let name = match reference {
+            Reference::Id(id) => return Ok(Some(SiteId::new(*id))),
+            Reference::Name(name) => name,
+        };
+        if let Some(id) = self.sites.get(name) {
+            return Ok(*id);
+        }
+
+        let found = found(self.client.get_site_by_name(name).await)?;
+        let id = found.map(|site| site.get_id()).transpose()?;
+        self.sites.insert(name.clone(), id);
+
+        Ok(id)
+    }
+
+    async fn configuration(
+        &mut self,
+        site: SiteId,
+        reference: &Reference,
+    ) -> Result<Option<SiteConfigurationId>, Error> {
+        let name = match reference {
+            Reference::Id(id) => return Ok(Some(SiteConfigurationId::new(*id))),
+            Reference::Name(name) => name,
+        };
+
+        if !self.configurations.contains_key(&site) {
+            let mut configurations = Vec::new();
+            if let Some(site) = found(self.client.get_site_by_id(site).await)? {
+                for configuration in site.get_configurations(self.client).await?.iter() {
+                    configurations.push((configuration.name.clone(), configuration.get_id()?));
+                }
+            }
+            self.configurations.insert(site, configurations);
+        }
+
+        let id = self.configurations[&site]
+            .iter()
+            .find(|(configuration, _)| configuration == name)
+            .map(|(_, id)| *id);
+
+        Ok(id)
+    }
+
+    async fn band(&mut self, reference: &Reference) -> Result<Option<BandId>, Error> {
+        let name = match reference {
+            Reference::Id(id) => return Ok(Some(BandId::new(*id))),
+            Reference::Name(name) => name,
+        };
+        if let Some(id) = self.bands.get(name) {
+            return Ok(*id);
+        }
+
+        let found = found(self.client.get_satellite_band_by_name(name).await)?;
+        let id = found.map(|band| band.get_id()).transpose()?;
+        self.bands.insert(name.clone(), id);
+
+        Ok(id)
+    }
+}
+
+/// The item, or `None` if Freedom has no such item
+fn found<T>(result: Result<T, Error>) -> Result<Option<T>, Error> {
+    match result {
+        Ok(item) => Ok(Some(item)),
+        Err(Error::NotFound { .. }) => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn plan() -> Plan {
+        Plan::new([
+            PlannedRequest {
+                task_type: TaskType::Exact,
+                satellite: Reference::from("FooBar 6"),
+                site: Reference::from(14),
+                configuration: Reference::from("LOAG S-Band"),
+                bands: vec![Reference::from(1573), Reference::from("S-Band")],
+                target_date: datetime!(2030-01-01 12:00 UTC),
+                duration: 600,
+                minimum_duration: Some(300),
+                hours_of_flex: None,
+                test_file: None,
+                with_override: None,
+            },
+            PlannedRequest {
+                task_type: TaskType::Test,
+                satellite: Reference::from(710),
+                site: Reference::from("LOAG"),
+                configuration: Reference::from(47),
+                bands: vec![Reference::from(1573)],
+                target_date: datetime!(2030-01-02 05:00 -07:00),
+                duration: 120,
+                minimum_duration: None,
+                hours_of_flex: None,
+                test_file: Some(String::from("test_file.bin")),
+                with_override: Some(Reference::from(42)),
+            },
+        ])
+    }
+
+    #[test]
+    fn plans_round_trip_in_every_format() {
+        let plan = plan();
+
+        for format in [PlanFormat::Csv, PlanFormat::Json, PlanFormat::Yaml] {
+            let mut written = Vec::new();
+            plan.to_writer(&mut written, format).unwrap();
+
+            let read = Plan::from_reader(written.as_slice(), format).unwrap();
+            assert_eq!(read, plan, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn csv_bands_are_separated_by_semicolons() {
+        let mut written = Vec::new();
+        plan().to_writer(&mut written, PlanFormat::Csv).unwrap();
+        let written = String::from_utf8(written).unwrap();
+
+        let mut lines = written.lines();
+        assert_eq!(
+            lines.next(),
+            Some("type,satellite,site,configuration,bands,targetDate,duration,minimumDuration,hoursOfFlex,testFile,override")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("EXACT,FooBar 6,#14,LOAG S-Band,#1573;S-Band,2030-01-01T12:00:00Z,600,300,,,")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("TEST,#710,LOAG,#47,#1573,2030-01-02T05:00:00-07:00,120,,,test_file.bin,#42")
+        );
+    }
+
+    #[test]
+    fn ids_are_told_apart_from_numeric_names() {
+        let references = [
+            ("#2017", Reference::Id(2017)),
+            ("2017", Reference::from("2017")),
+            ("##2017", Reference::from("#2017")),
+            ("#FooBar", Reference::from("#FooBar")),
+        ];
+
+        for (text, reference) in references {
+            assert_eq!(text.parse::<Reference>().unwrap(), reference);
+            assert_eq!(
+                reference.to_string().parse::<Reference>().unwrap(),
+                reference
+            );
+        }
+        assert_eq!(Reference::from("#FooBar").to_string(), "##FooBar");
+
+        let yaml = serde_norway::to_string(&Reference::from("2017")).unwrap();
+        assert_eq!(
+            serde_norway::from_str::<Reference>(&yaml).unwrap(),
+            Reference::from("2017")
+        );
+    }
+
+    #[test]
+    fn payloads_are_exported_by_id() {
+        let payload: TaskRequest = serde_json::from_value(serde_json::json!({
+            "type": "TEST",
+            "site": "https://test-api.atlasground.com/api/sites/14",
+            "satellite": "https://test-api.atlasground.com/api/satellites/710",
+            "configuration": "https://test-api.atlasground.com/api/configurations/47",
+            "targetBands": ["https://test-api.atlasground.com/api/satellite_bands/1573"],
+            "targetDate": "2030-01-02T12:00:00Z",
+            "duration": 120,
+            "minimumDuration": null,
+            "hoursOfFlex": null,
+            "testFile": "test_file.bin",
+            "override": "https://test-api.atlasground.com/api/overrides/42",
+        }))
+        .unwrap();
+
+        let mut expected = plan().requests.remove(1);
+        expected.site = Reference::from(14);
+        expected.configuration = Reference::from(47);
+
+        assert_eq!(PlannedRequest::try_from(&payload).unwrap(), expected);
+    }
+
+    #[test]
+    fn formats_are_picked_by_extension() {
+        assert_eq!(PlanFormat::from_path("plan.CSV"), Some(PlanFormat::Csv));
+        assert_eq!(PlanFormat::from_path("plan.yml"), Some(PlanFormat::Yaml));
+        assert_eq!(PlanFormat::from_path("plan.txt"), None);
+    }
+}
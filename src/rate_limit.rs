@@ -0,0 +1,294 @@
+//! # Rate Limiting
+//!
+//! Freedom throttles clients which send too many requests at once. This module contains the limit
+//! a [`Client`](crate::Client) may place on its own requests to stay below Freedom's thresholds.
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use reqwest::ResponseBuilderExt;
+use tokio::{
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::Instant,
+};
+
+/// The limit placed on the requests sent by a client.
+///
+/// The rate is enforced with a token bucket: each request takes a token, tokens are replenished at
+/// the configured [`rate`](Self::rate), and at most [`burst`](Self::burst) tokens are held at once,
+/// so that a client which was idle may briefly exceed the rate. Requests which find the bucket
+/// empty wait for their token, in the order in which they arrived.
+///
+/// Independently of the rate, [`max_in_flight`](Self::max_in_flight) bounds the number of
+/// requests in flight at any one time. A request is in flight until the body of its response has
+/// been read, or the response has been dropped.
+///
+/// The limit is shared between a client and all of its clones, and applies to every attempt of a
+/// request, including retries and each page of a paginated stream. The time a request waited is
+/// recorded as a `tracing` event at the `DEBUG` level.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use freedom_api::prelude::*;
+/// let config = Config::builder()
+///     .environment(Test)
+///     .key("foo")
+///     .secret("bar")
+///     .build()
+///     .unwrap();
+///
+/// let limit = RateLimit::default()
+///     .rate(10, Duration::from_secs(1))
+///     .burst(20)
+///     .max_in_flight(4);
+///
+/// let client = Client::from_config(config).with_rate_limit(limit);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    rate: Option<(u32, Duration)>,
+    burst: Option<u32>,
+    max_in_flight: Option<usize>,
+}
+
+impl RateLimit {
+    /// Allow the provided number of requests per period.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either the number of requests or the period is zero
+    pub fn rate(mut self, requests: u32, per: Duration) -> Self {
+        assert!(
+            requests > 0 && !per.is_zero(),
+            "The rate must allow at least one request per non-zero period"
+        );
+        self.rate = Some((requests, per));
+        self
+    }
+
+    /// The number of requests which may be sent at once after the client was idle, which defaults
+    /// to the number of requests of the [`rate`](Self::rate).
+    ///
+    /// Values lower than one are treated as one.
+    pub fn burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst.max(1));
+        self
+    }
+
+    /// The number of requests which may be in flight at once, including the time spent reading the
+    /// body of their responses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number is zero
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        assert!(max > 0, "At least one request must be allowed in flight");
+        self.max_in_flight = Some(max);
+        self
+    }
+}
+
+/// The state of a [`RateLimit`], shared between the clones of a client
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    limit: RateLimit,
+    bucket: Option<Mutex<Bucket>>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// The tokens currently held, negative when requests are waiting on tokens not yet replenished
+    tokens: f64,
+    capacity: f64,
+    /// The tokens replenished per second
+    rate: f64,
+    updated: Instant,
+}
+
+/// Held for as long as a request is in flight
+#[derive(Debug)]
+pub(crate) struct Permit {
+    _in_flight: Option<OwnedSemaphorePermit>,
+}
+
+impl Permit {
+    /// Keep the permit until the body of the response has been read, or the response is dropped
+    pub(crate) fn hold(self, response: reqwest::Response) -> reqwest::Response {
+        let url = response.url().clone();
+        let (mut parts, body) = http::Response::from(response).into_parts();
+        let body = reqwest::Body::wrap(HeldBody {
+            body,
+            permit: Some(self),
+        });
+
+        // The URL of a response converted from `http` is only kept within its extensions
+        let (url_parts, ()) = http::Response::builder()
+            .url(url)
+            .body(())
+            .expect("An empty response is always valid")
+            .into_parts();
+        parts.extensions.extend(url_parts.extensions);
+
+        reqwest::Response::from(http::Response::from_parts(parts, body))
+    }
+}
+
+/// The body of a response, holding the permit of its request until it has been read
+struct HeldBody {
+    body: reqwest::Body,
+    permit: Option<Permit>,
+}
+
+impl Body for HeldBody {
+    type Data = Bytes;
+    type Error = reqwest::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = Pin::new(&mut self.body).poll_frame(cx);
+        if let Poll::Ready(None | Some(Err(_))) = frame {
+            self.permit = None;
+        }
+
+        frame
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        let bucket = limit.rate.map(|(requests, per)| {
+            let capacity = f64::from(limit.burst.unwrap_or(requests));
+            Mutex::new(Bucket {
+                tokens: capacity,
+                capacity,
+                rate: f64::from(requests) / per.as_secs_f64(),
+                updated: Instant::now(),
+            })
+        });
+        let in_flight = limit.max_in_flight.map(|max| Arc::new(Semaphore::new(max)));
+
+        Self {
+            limit,
+            bucket,
+            in_flight,
+        }
+    }
+
+    pub(crate) fn limit(&self) -> &RateLimit {
+        &self.limit
+    }
+
+    /// Wait until a request may be sent, returning the permit to hold while it is in flight along
+    /// with the time spent waiting, if the request could not be sent immediately
+    pub(crate) async fn acquire(&self) -> (Permit, Option<Duration>) {
+        let start = Instant::now();
+        let mut waited = false;
+
+        if let Some(delay) = self.reserve(start) {
+            waited = true;
+            tokio::time::sleep(delay).await;
+        }
+        let permit = match &self.in_flight {
+            Some(in_flight) => match Arc::clone(in_flight).try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    waited = true;
+                    let permit = Arc::clone(in_flight).acquire_owned().await;
+                    Some(permit.expect("Never closed"))
+                }
+            },
+            None => None,
+        };
+
+        let waited = waited.then(|| start.elapsed());
+        (Permit { _in_flight: permit }, waited)
+    }
+
+    /// Take a token from the bucket, returning how long to wait before it is available
+    fn reserve(&self, now: Instant) -> Option<Duration> {
+        let mut bucket = self.bucket.as_ref()?.lock().unwrap();
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * bucket.rate).min(bucket.capacity);
+        bucket.updated = now;
+        bucket.tokens -= 1.0;
+
+        (bucket.tokens < 0.0).then(|| Duration::from_secs_f64(-bucket.tokens / bucket.rate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_is_available_immediately() {
+        let limiter = RateLimiter::new(
+            RateLimit::default()
+                .rate(1, Duration::from_secs(1))
+                .burst(3),
+        );
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve(now), None);
+        assert_eq!(limiter.reserve(now), None);
+        assert_eq!(limiter.reserve(now), None);
+        assert_eq!(limiter.reserve(now), Some(Duration::from_secs(1)));
+        assert_eq!(limiter.reserve(now), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn tokens_are_replenished_up_to_the_burst() {
+        let limiter = RateLimiter::new(RateLimit::default().rate(10, Duration::from_secs(1)));
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert_eq!(limiter.reserve(now), None);
+        }
+        assert!(limiter.reserve(now).is_some());
+
+        let later = now + Duration::from_secs(60);
+        for _ in 0..10 {
+            assert_eq!(limiter.reserve(later), None);
+        }
+        assert!(limiter.reserve(later).is_some());
+    }
+
+    #[test]
+    fn no_rate_never_waits() {
+        let limiter = RateLimiter::new(RateLimit::default().max_in_flight(1));
+        assert_eq!(limiter.reserve(Instant::now()), None);
+    }
+
+    #[tokio::test]
+    async fn in_flight_requests_are_bounded() {
+        let limiter = RateLimiter::new(RateLimit::default().max_in_flight(1));
+        let (first, _) = limiter.acquire().await;
+
+        let second = limiter.acquire();
+        let second = tokio::time::timeout(Duration::from_millis(20), second).await;
+        assert!(second.is_err());
+
+        drop(first);
+        let (_, waited) = limiter.acquire().await;
+        assert_eq!(waited, None);
+    }
+}
@@ -0,0 +1,286 @@
+//! # Retry Policy
+//!
+//! Freedom, like any remote service, occasionally fails for reasons which resolve themselves. This
+//! module contains the policy the [`Client`](crate::Client) uses to decide whether, and when, a
+//! failed request is attempted again.
+use std::time::Duration;
+
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    Method, StatusCode,
+};
+use time::{format_description::well_known::Rfc2822, OffsetDateTime};
+
+/// The policy describing how transient failures are retried.
+///
+/// A failure is considered transient when the connection to Freedom could not be established or
+/// was interrupted, or when Freedom responds with `408 Request Timeout`, `429 Too Many Requests`,
+/// or any `5xx` status.
+///
+/// Between attempts the client waits according to an exponential backoff, starting at the
+/// [`initial_backoff`](Self::initial_backoff), doubling with each attempt, and capped at the
+/// [`max_backoff`](Self::max_backoff). When Freedom includes a `Retry-After` header, it is used
+/// instead of the computed backoff.
+///
+/// By default only idempotent requests (`GET`, `PUT`, `DELETE`) are retried, since retrying a
+/// `POST` may create the same resource twice. See [`Self::retry_posts`] to opt in.
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use freedom_api::prelude::*;
+/// let config = Config::builder()
+///     .environment(Test)
+///     .key("foo")
+///     .secret("bar")
+///     .build()
+///     .unwrap();
+///
+/// let policy = RetryPolicy::default()
+///     .max_attempts(5)
+///     .initial_backoff(Duration::from_millis(250));
+///
+/// let client = Client::from_config(config).with_retry_policy(policy);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_posts: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, backing off from 500 milliseconds up to 30 seconds with jitter
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_posts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy which never retries, each request is attempted exactly once.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// The total number of attempts made for a single request, including the first.
+    ///
+    /// Values lower than one are treated as one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// The delay before the first retry
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// The upper bound on the delay between any two attempts, including delays requested by
+    /// Freedom through `Retry-After`.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Whether the computed backoff is randomized.
+    ///
+    /// With jitter enabled, the delay is drawn uniformly between zero and the computed backoff, so
+    /// that many clients failing at once do not retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Whether `POST` requests are retried.
+    ///
+    /// # Note
+    ///
+    /// Only enable this if creating the same resource twice is acceptable, since a request which
+    /// failed from the client's perspective may still have been processed by Freedom.
+    pub fn retry_posts(mut self, retry_posts: bool) -> Self {
+        self.retry_posts = retry_posts;
+        self
+    }
+
+    /// Returns the delay before the next attempt, or `None` if the request should not be retried.
+    ///
+    /// The `attempt` is the one-based number of the attempt which just failed.
+    pub(crate) fn next_delay(
+        &self,
+        method: &Method,
+        attempt: u32,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable_method(method) {
+            return None;
+        }
+
+        let delay = match retry_after {
+            Some(delay) => delay,
+            None => self.backoff(attempt),
+        };
+
+        Some(delay.min(self.max_backoff))
+    }
+
+    fn is_retryable_method(&self, method: &Method) -> bool {
+        match *method {
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS => true,
+            Method::POST => self.retry_posts,
+            _ => false,
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+
+        match self.jitter {
+            true => backoff.mul_f64(fastrand::f64()),
+            false => backoff,
+        }
+    }
+}
+
+/// Whether the status code indicates a failure which may succeed if retried
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+        || status.is_server_error()
+}
+
+/// Whether the error indicates a failure which may succeed if retried
+pub(crate) fn is_transient_error(error: &reqwest::Error) -> bool {
+    error.is_connect() || error.is_timeout() || error.is_request()
+}
+
+/// Parses the `Retry-After` header of a response, which is either a number of seconds or an HTTP
+/// date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    let delay = date - OffsetDateTime::now_utc();
+
+    Some(delay.try_into().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::Response;
+
+    use super::*;
+
+    fn response_with_retry_after(value: &str) -> Response {
+        let response = http::Response::builder()
+            .status(503)
+            .header(RETRY_AFTER, value)
+            .body(Vec::new())
+            .unwrap();
+
+        Response::from(response)
+    }
+
+    #[test]
+    fn exponential_backoff_without_jitter() {
+        let policy = RetryPolicy::default()
+            .max_attempts(10)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500))
+            .jitter(false);
+
+        let delays: Vec<_> = (1..6)
+            .map(|attempt| policy.next_delay(&Method::GET, attempt, None).unwrap())
+            .collect();
+
+        assert_eq!(
+            delays,
+            [100, 200, 400, 500, 500]
+                .map(Duration::from_millis)
+                .to_vec()
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_backoff() {
+        let policy = RetryPolicy::default().initial_backoff(Duration::from_millis(100));
+
+        for _ in 0..100 {
+            let delay = policy.next_delay(&Method::GET, 1, None).unwrap();
+            assert!(delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        let policy = RetryPolicy::default().max_attempts(2);
+
+        assert!(policy.next_delay(&Method::GET, 1, None).is_some());
+        assert!(policy.next_delay(&Method::GET, 2, None).is_none());
+        assert!(RetryPolicy::none()
+            .next_delay(&Method::GET, 1, None)
+            .is_none());
+    }
+
+    #[test]
+    fn posts_require_opt_in() {
+        let policy = RetryPolicy::default();
+        assert!(policy.next_delay(&Method::POST, 1, None).is_none());
+        assert!(policy.next_delay(&Method::DELETE, 1, None).is_some());
+
+        let policy = policy.retry_posts(true);
+        assert!(policy.next_delay(&Method::POST, 1, None).is_some());
+    }
+
+    #[test]
+    fn retry_after_is_honoured_and_capped() {
+        let policy = RetryPolicy::default().max_backoff(Duration::from_secs(10));
+
+        let delay = policy.next_delay(&Method::GET, 1, Some(Duration::from_secs(4)));
+        assert_eq!(delay, Some(Duration::from_secs(4)));
+
+        let delay = policy.next_delay(&Method::GET, 1, Some(Duration::from_secs(60)));
+        assert_eq!(delay, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn parse_retry_after() {
+        let response = response_with_retry_after("7");
+        assert_eq!(
+            retry_after(response.headers()),
+            Some(Duration::from_secs(7))
+        );
+
+        let response = response_with_retry_after("Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(retry_after(response.headers()), Some(Duration::ZERO));
+
+        let response = response_with_retry_after("soon");
+        assert_eq!(retry_after(response.headers()), None);
+    }
+
+    #[test]
+    fn transient_statuses() {
+        assert!(is_transient_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_transient_status(StatusCode::REQUEST_TIMEOUT));
+        assert!(!is_transient_status(StatusCode::NOT_FOUND));
+        assert!(!is_transient_status(StatusCode::BAD_REQUEST));
+    }
+}
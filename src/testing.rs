@@ -0,0 +1,348 @@
+//! # Testing
+//!
+//! This module contains tools for testing code built on top of this crate without access to a
+//! Freedom environment:
+//!
+//! + [`FakeFreedom`], an in-memory implementation of the [`Api`] trait.
+//! + [`RecordingClient`] and [`ReplayClient`], which capture the traffic of a real client to a
+//!   cassette, and later serve it back offline.
+//!
+//! ## Fake
+//!
+//! The fake keeps its own store of resources, which it serves from the same routes, and in the
+//! same shapes, as Freedom. Creating, updating, or deleting resources through the fake changes
+//! its store, so that the effects are visible to subsequent queries.
+//!
+//! ### Example
+//!
+//! ```
+//! # use freedom_api::prelude::*;
+//! # use freedom_api::testing::FakeFreedom;
+//! # tokio_test::block_on(async {
+//! let fake = FakeFreedom::new();
+//! fake.load_fixture(include_str!("../resources/satellite_bands_find_one_1573.json"))?;
+//!
+//! let config = fake
+//!     .new_satellite_configuration()
+//!     .name("My Satellite Configuration")
+//!     .band_ids([1573])
+//!     .send()
+//!     .await?;
+//!
+//! let fetched = fake.get_satellite_configuration_by_name("My Satellite Configuration").await?;
+//! assert_eq!(fetched.name, config.name);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! # });
+//! ```
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bytes::Bytes;
+use freedom_config::{Config, Test};
+use freedom_models::{
+    account::Account,
+    band::Band,
+    satellite::Satellite,
+    satellite_configuration::SatelliteConfiguration,
+    site::{Site, SiteConfiguration},
+    task::{Task, TaskRequest},
+    user::User,
+    Hateoas,
+};
+use reqwest::{header::CONTENT_TYPE, Response, StatusCode};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use url::Url;
+
+use crate::{
+    api::{Api, Inner, Value},
+    error::Error,
+};
+
+mod cassette;
+mod routes;
+mod store;
+
+pub use self::{
+    cassette::{
+        Interaction, RecordingClient, ReplayClient, ResponseBody, MIN_SECRET_LEN, REDACTED,
+    },
+    store::Resource,
+};
+use self::{routes::Reply, store::Store};
+
+/// An in-memory fake of the Freedom API.
+///
+/// The fake holds accounts, satellites, bands, satellite configurations, sites, site
+/// configurations, task requests, tasks, users, and overrides, each of which is served with HAL
+/// `_links` generated from its ID, as Freedom would. It supports:
+///
+/// + Fetching single resources, and paginated listings through the `page` and `size` query
+///   parameters.
+/// + The `search` endpoints used by [`Api`], such as `findOneByName` or
+///   `findAllByTargetDateBetween`, which filter the stored resources.
+/// + The relations of each resource, e.g. `satellites/{id}/configuration`.
+/// + Creating resources with `POST`, as sent by the builders of [`Api`], including the validation
+///   of the resources they reference.
+/// + Updating resources with `PATCH` and `PUT`, and removing them with `DELETE`.
+///
+/// Clones of the fake share the same store.
+///
+/// # Seeding
+///
+/// The store may be seeded from Freedom's JSON responses with [`Self::load_fixture`], e.g. the
+/// fixtures found in the `resources/` directory of this repository, or from models with
+/// [`Self::insert`]. Since Freedom's responses only link to relations indirectly, relations between
+/// seeded resources are declared with [`Self::relate`].
+#[derive(Debug, Clone)]
+pub struct FakeFreedom {
+    config: Config,
+    store: Arc<Mutex<Store>>,
+}
+
+impl Default for FakeFreedom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FakeFreedom {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.store, &other.store)
+    }
+}
+
+/// The models which may be inserted into a [`FakeFreedom`]
+pub trait FakeResource: Serialize + Hateoas {
+    /// The kind of resource the model is stored as
+    const RESOURCE: Resource;
+}
+
+macro_rules! fake_resource {
+    ($($model:ty => $resource:ident),* $(,)?) => {
+        $(
+            impl FakeResource for $model {
+                const RESOURCE: Resource = Resource::$resource;
+            }
+        )*
+    };
+}
+
+fake_resource! {
+    Account => Account,
+    Satellite => Satellite,
+    Band => Band,
+    SatelliteConfiguration => SatelliteConfiguration,
+    Site => Site,
+    SiteConfiguration => SiteConfiguration,
+    TaskRequest => TaskRequest,
+    Task => Task,
+    User => User,
+}
+
+impl FakeFreedom {
+    /// Construct an empty fake, configured for the test environment.
+    ///
+    /// Resources created through the fake belong to the account named `ATLAS`, see
+    /// [`Self::with_account_name`].
+    pub fn new() -> Self {
+        let config = Config::new(Test, "fake-key", "fake-secret");
+
+        Self::with_config(config)
+    }
+
+    /// Construct an empty fake, using the provided configuration.
+    ///
+    /// The links produced by the fake are relative to the configuration's entrypoint.
+    pub fn with_config(config: Config) -> Self {
+        let mut store = Store::default();
+        store.account_name = String::from("ATLAS");
+
+        Self {
+            config,
+            store: Arc::new(Mutex::new(store)),
+        }
+    }
+
+    /// The name of the account which owns resources created through the fake.
+    ///
+    /// If an account with this name is stored, the created resources also link to it.
+    pub fn with_account_name(self, name: impl Into<String>) -> Self {
+        self.store().account_name = name.into();
+        self
+    }
+
+    /// Insert the model into the store, returning its ID.
+    ///
+    /// The ID is taken from the model's `self` link when it points at the same kind of resource,
+    /// otherwise the next free ID is used. Existing resources with the same ID are replaced.
+    pub fn insert<T: FakeResource>(&self, item: &T) -> Result<i32, Error> {
+        let base = self.base();
+        let value = serde_json::to_value(item)?;
+        let mut store = self.store();
+
+        let id = item
+            .get_links()
+            .get("self")
+            .and_then(|url| routes::parse_reference(&base, url.as_str()))
+            .filter(|(resource, _)| *resource == T::RESOURCE)
+            .map_or_else(|| store.next_id(T::RESOURCE), |(_, id)| id);
+
+        store.insert(T::RESOURCE, id, value);
+
+        Ok(id)
+    }
+
+    /// Load the resources of a Freedom JSON response into the store, returning the kind and ID of
+    /// each.
+    ///
+    /// The JSON may either be a single resource or an `_embedded` listing of resources. Each
+    /// resource must contain a `self` link, from which its kind and ID are determined.
+    pub fn load_fixture(&self, json: &str) -> Result<Vec<(Resource, i32)>, Error> {
+        let base = self.base();
+        let value: JsonValue = serde_json::from_str(json)?;
+
+        let items = match value.get("_embedded").and_then(JsonValue::as_object) {
+            Some(embedded) => embedded
+                .values()
+                .filter_map(JsonValue::as_array)
+                .flatten()
+                .cloned()
+                .collect(),
+            None => vec![value],
+        };
+
+        let mut store = self.store();
+        items
+            .into_iter()
+            .map(|item| {
+                let (resource, id) = item
+                    .pointer("/_links/self/href")
+                    .and_then(JsonValue::as_str)
+                    .and_then(|href| routes::parse_reference(&base, href))
+                    .ok_or_else(|| Error::MissingUri(String::from("self")))?;
+
+                store.insert(resource, id, item);
+                Ok((resource, id))
+            })
+            .collect()
+    }
+
+    /// Point the named relation of the resource at the provided targets, replacing any existing
+    /// targets.
+    ///
+    /// Relations which are the inverse of another, like the `satellites` of an account, are
+    /// derived from their counterpart (the `account` of each satellite), and are set through it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource has no stored relation with the provided name
+    pub fn relate(
+        &self,
+        resource: Resource,
+        id: i32,
+        relation: &str,
+        targets: impl IntoIterator<Item = i32>,
+    ) {
+        let relation = resource
+            .relation(relation)
+            .filter(|relation| matches!(relation.link, store::Link::Stored))
+            .unwrap_or_else(|| panic!("{resource:?} has no stored relation named {relation}"));
+
+        let targets = targets.into_iter().collect();
+        self.store()
+            .set_relation(resource, id, relation.name, targets);
+    }
+
+    /// Store a file, served at `downloads/{task_id}/{name}`
+    pub fn insert_file(&self, task_id: i32, name: impl Into<String>, data: impl Into<Bytes>) {
+        self.store().insert_file(task_id, name.into(), data.into());
+    }
+
+    /// Whether a resource of the kind with the provided ID is stored
+    pub fn contains(&self, resource: Resource, id: i32) -> bool {
+        self.store().contains(resource, id)
+    }
+
+    /// The number of stored resources of the kind
+    pub fn len(&self, resource: Resource) -> usize {
+        self.store().len(resource)
+    }
+
+    /// Whether no resources of the kind are stored
+    pub fn is_empty(&self, resource: Resource) -> bool {
+        self.len(resource) == 0
+    }
+
+    fn base(&self) -> Url {
+        self.config.environment().freedom_entrypoint()
+    }
+
+    fn store(&self) -> MutexGuard<'_, Store> {
+        // The store is never left in an inconsistent state, so a poisoned lock is still usable
+        self.store.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn into_response(reply: Reply) -> Response {
+    let response = http::Response::builder()
+        .status(reply.status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(reply.body)
+        .expect("Invalid response construction");
+
+    Response::from(response)
+}
+
+impl Api for FakeFreedom {
+    type Container<T: Value> = Inner<T>;
+
+    async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), Error> {
+        let reply = routes::get(&self.store(), &self.base(), &url);
+
+        Ok((reply.body, reply.status))
+    }
+
+    async fn delete(&self, url: Url) -> Result<Response, Error> {
+        let reply = routes::delete(&mut self.store(), &self.base(), &url);
+
+        Ok(into_response(reply))
+    }
+
+    async fn post<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        let reply = routes::post(&mut self.store(), &self.base(), &url, body);
+
+        Ok(into_response(reply))
+    }
+
+    async fn put<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        let reply = routes::update(&mut self.store(), &self.base(), &url, body, true);
+
+        Ok(into_response(reply))
+    }
+
+    async fn patch<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        let reply = routes::update(&mut self.store(), &self.base(), &url, body, false);
+
+        Ok(into_response(reply))
+    }
+
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+}
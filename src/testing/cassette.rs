@@ -0,0 +1,744 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    path::Path,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use bytes::Bytes;
+use freedom_config::Config;
+use reqwest::{Method, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use url::Url;
+
+use crate::{
+    api::{Api, Inner, Value},
+    error::Error,
+};
+
+/// The text which replaces the key and secret of the [`Config`] in a cassette
+pub const REDACTED: &str = "[REDACTED]";
+
+/// The length below which a key or secret is too likely to occur within unrelated text, such as
+/// the names of resources, for interactions to be recorded with it scrubbed
+pub const MIN_SECRET_LEN: usize = 8;
+
+/// A single request made to Freedom, and the response it produced.
+///
+/// Cassettes are stored as JSON lines, one interaction per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Interaction {
+    pub method: String,
+    pub url: String,
+    /// The JSON body of the request, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<JsonValue>,
+    pub status: u16,
+    pub response: ResponseBody,
+}
+
+/// The body of a recorded response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ResponseBody {
+    /// A body which is valid UTF-8, such as JSON
+    Text(String),
+    /// Any other body, such as a downloaded file
+    Binary(Vec<u8>),
+}
+
+impl ResponseBody {
+    fn into_bytes(self) -> Bytes {
+        match self {
+            ResponseBody::Text(text) => Bytes::from(text),
+            ResponseBody::Binary(data) => Bytes::from(data),
+        }
+    }
+}
+
+/// Replaces every occurrence of the key and secret of a configuration, including those within a
+/// longer word.
+///
+/// URLs are scrubbed segment by segment and parameter by parameter, and JSON key by key and value
+/// by value, so that neither the structure of a URL nor the escaping of JSON can hide a secret.
+#[derive(Debug, Clone)]
+struct Scrubber {
+    secrets: Vec<String>,
+}
+
+impl Scrubber {
+    fn new(config: &Config) -> Self {
+        let secrets = [config.expose_secret(), config.key()]
+            .into_iter()
+            .filter(|secret| !secret.is_empty())
+            .map(String::from)
+            .collect();
+
+        Self { secrets }
+    }
+
+    /// Whether every secret is long enough to be scrubbed without mangling unrelated text
+    fn is_reliable(&self) -> bool {
+        self.secrets
+            .iter()
+            .all(|secret| secret.chars().count() >= MIN_SECRET_LEN)
+    }
+
+    fn text(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_owned(), |text, secret| {
+            text.replace(secret.as_str(), REDACTED)
+        })
+    }
+
+    fn url(&self, url: &Url) -> String {
+        let mut scrubbed = url.clone();
+
+        let segments: Option<Vec<_>> = url
+            .path_segments()
+            .map(|segments| segments.map(|segment| self.text(segment)).collect());
+        if let (Some(segments), Ok(mut path)) = (segments, scrubbed.path_segments_mut()) {
+            path.clear().extend(segments);
+        }
+
+        if url.query().is_some() {
+            let pairs: Vec<_> = url
+                .query_pairs()
+                .map(|(name, value)| (name.into_owned(), self.text(&value)))
+                .collect();
+            scrubbed.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+
+        scrubbed.into()
+    }
+
+    fn json(&self, value: &JsonValue) -> JsonValue {
+        match value {
+            JsonValue::String(text) => JsonValue::String(self.text(text)),
+            JsonValue::Array(items) => items.iter().map(|item| self.json(item)).collect(),
+            JsonValue::Object(fields) => fields
+                .iter()
+                .map(|(name, field)| (self.text(name), self.json(field)))
+                .collect(),
+            other => other.clone(),
+        }
+    }
+
+    fn response(&self, body: &[u8]) -> Result<ResponseBody, Error> {
+        let Ok(text) = std::str::from_utf8(body) else {
+            return Ok(ResponseBody::Binary(body.to_vec()));
+        };
+
+        let Ok(value) = serde_json::from_str::<JsonValue>(text) else {
+            return Ok(ResponseBody::Text(self.text(text)));
+        };
+
+        // The body is only rewritten when a secret was found, so that it is otherwise recorded
+        // byte for byte
+        let scrubbed = self.json(&value);
+        match scrubbed == value {
+            true => Ok(ResponseBody::Text(text.to_owned())),
+            false => Ok(ResponseBody::Text(serde_json::to_string(&scrubbed)?)),
+        }
+    }
+}
+
+/// An [`Api`] which records every request made through it, and the response it produced, to a
+/// cassette which can later be served by a [`ReplayClient`].
+///
+/// The key and secret of the wrapped client's [`Config`] are replaced by [`REDACTED`] wherever they
+/// appear in the recorded URLs and bodies. Interactions which cannot be scrubbed are not recorded,
+/// nor is anything recorded when the key or secret is shorter than [`MIN_SECRET_LEN`]. Credentials
+/// sent through headers are never recorded.
+///
+/// Requests which fail without a response from Freedom, for instance due to a connection error,
+/// are not recorded. Neither are downloads made through [`Api::get_stream`], which are streamed
+/// from the wrapped client rather than buffered into the cassette.
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use freedom_api::testing::RecordingClient;
+/// # tokio_test::block_on(async {
+/// let client = RecordingClient::create(Client::from_env()?, "cassettes/satellite.jsonl")?;
+///
+/// client.get_satellite_by_id(710).await?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct RecordingClient<A> {
+    inner: A,
+    scrubber: Scrubber,
+    cassette: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl<A: std::fmt::Debug> std::fmt::Debug for RecordingClient<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordingClient")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: Api> RecordingClient<A> {
+    /// Wrap the client, writing the recorded interactions to the provided writer
+    pub fn new(inner: A, cassette: impl Write + Send + 'static) -> Self {
+        let scrubber = Scrubber::new(inner.config());
+
+        Self {
+            inner,
+            scrubber,
+            cassette: Arc::new(Mutex::new(Box::new(cassette))),
+        }
+    }
+
+    /// Wrap the client, writing the recorded interactions to the file at the provided path.
+    ///
+    /// The file is created if it does not exist, and truncated if it does.
+    pub fn create(inner: A, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::create(path)?;
+
+        Ok(Self::new(inner, file))
+    }
+
+    /// The wrapped client
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    fn record(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<&JsonValue>,
+        status: u16,
+        data: &[u8],
+    ) {
+        if !self.scrubber.is_reliable() {
+            tracing::warn!(
+                %url,
+                "The key or secret is shorter than {MIN_SECRET_LEN} characters, the interaction was not recorded"
+            );
+            return;
+        }
+        let response = match self.scrubber.response(data) {
+            Ok(response) => response,
+            Err(error) => {
+                tracing::warn!(%error, %url, "Failed to scrub interaction, it was not recorded");
+                return;
+            }
+        };
+        let interaction = Interaction {
+            method: method.to_string(),
+            url: self.scrubber.url(url),
+            body: body.map(|body| self.scrubber.json(body)),
+            status,
+            response,
+        };
+
+        let mut cassette = self.cassette.lock().unwrap_or_else(PoisonError::into_inner);
+        let written = serde_json::to_string(&interaction)
+            .map_err(std::io::Error::from)
+            .and_then(|line| writeln!(cassette, "{line}"))
+            .and_then(|_| cassette.flush());
+
+        if let Err(error) = written {
+            tracing::warn!(%error, %url, "Failed to record interaction");
+        }
+    }
+
+    /// Read the response of the wrapped client, record it, and rebuild an equivalent response
+    async fn record_response(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<&JsonValue>,
+        response: Response,
+    ) -> Result<Response, Error> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let data = response.bytes().await?;
+
+        self.record(method, url, body, status.as_u16(), &data);
+
+        let mut rebuilt = http::Response::new(data);
+        *rebuilt.status_mut() = status;
+        *rebuilt.headers_mut() = headers;
+
+        Ok(Response::from(rebuilt))
+    }
+}
+
+impl<A: Api> Api for RecordingClient<A> {
+    type Container<T: Value> = A::Container<T>;
+
+    async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), Error> {
+        let (data, status) = self.inner.get(url.clone()).await?;
+        self.record(Method::GET, &url, None, status.as_u16(), &data);
+
+        Ok((data, status))
+    }
+
+    async fn get_stream(&self, url: Url, offset: u64) -> Result<Response, Error> {
+        // Downloads are typically large files, which are not worth recording
+        self.inner.get_stream(url, offset).await
+    }
+
+    async fn delete(&self, url: Url) -> Result<Response, Error> {
+        let response = self.inner.delete(url.clone()).await?;
+
+        self.record_response(Method::DELETE, &url, None, response)
+            .await
+    }
+
+    async fn post<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        let response = self.inner.post(url.clone(), &body).await?;
+
+        self.record_response(Method::POST, &url, Some(&body), response)
+            .await
+    }
+
+    async fn put<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        let response = self.inner.put(url.clone(), &body).await?;
+
+        self.record_response(Method::PUT, &url, Some(&body), response)
+            .await
+    }
+
+    async fn patch<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        let response = self.inner.patch(url.clone(), &body).await?;
+
+        self.record_response(Method::PATCH, &url, Some(&body), response)
+            .await
+    }
+
+    fn config(&self) -> &Config {
+        self.inner.config()
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        self.inner.config_mut()
+    }
+}
+
+/// An [`Api`] which serves the responses of a cassette recorded by a [`RecordingClient`], without
+/// making any requests to Freedom.
+///
+/// Each request is matched against the recorded interactions by its method, URL, and body, after
+/// scrubbing the key and secret of the replay client's [`Config`]. Interactions are served in the
+/// order in which they were recorded, so the same request recorded several times produces each of
+/// its recorded responses in turn, after which the last of them is repeated.
+///
+/// Requests which match no recorded interaction result in an [`Error::Response`], as do all
+/// downloads made through [`Api::get_stream`], since they are never recorded.
+///
+/// # Example
+///
+/// ```no_run
+/// # use freedom_api::prelude::*;
+/// # use freedom_api::testing::ReplayClient;
+/// # tokio_test::block_on(async {
+/// let config = Config::new(Test, "key", "secret");
+/// let client = ReplayClient::open(config, "cassettes/satellite.jsonl")?;
+///
+/// let satellite = client.get_satellite_by_id(710).await?;
+/// assert!(client.is_exhausted());
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayClient {
+    config: Config,
+    scrubber: Scrubber,
+    interactions: Arc<Mutex<Vec<(Interaction, bool)>>>,
+}
+
+impl PartialEq for ReplayClient {
+    fn eq(&self, other: &Self) -> bool {
+        self.config == other.config && Arc::ptr_eq(&self.interactions, &other.interactions)
+    }
+}
+
+impl ReplayClient {
+    /// Construct a client serving the provided interactions
+    pub fn new(config: Config, interactions: impl IntoIterator<Item = Interaction>) -> Self {
+        let scrubber = Scrubber::new(&config);
+        let interactions = interactions
+            .into_iter()
+            .map(|interaction| (interaction, false))
+            .collect();
+
+        Self {
+            config,
+            scrubber,
+            interactions: Arc::new(Mutex::new(interactions)),
+        }
+    }
+
+    /// Construct a client serving the interactions of the cassette read from the reader
+    pub fn from_reader(config: Config, reader: impl std::io::Read) -> std::io::Result<Self> {
+        let interactions = BufReader::new(reader)
+            .lines()
+            .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+            .map(|line| serde_json::from_str(&line?).map_err(std::io::Error::from))
+            .collect::<Result<Vec<Interaction>, _>>()?;
+
+        Ok(Self::new(config, interactions))
+    }
+
+    /// Construct a client serving the interactions of the cassette at the provided path
+    pub fn open(config: Config, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+
+        Self::from_reader(config, file)
+    }
+
+    /// Whether every recorded interaction has been served at least once
+    pub fn is_exhausted(&self) -> bool {
+        self.interactions().iter().all(|(_, served)| *served)
+    }
+
+    /// The recorded interactions which have not been served yet
+    pub fn unserved(&self) -> Vec<Interaction> {
+        self.interactions()
+            .iter()
+            .filter(|(_, served)| !served)
+            .map(|(interaction, _)| interaction.clone())
+            .collect()
+    }
+
+    fn interactions(&self) -> MutexGuard<'_, Vec<(Interaction, bool)>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn replay(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<JsonValue>,
+    ) -> Result<(Bytes, StatusCode), Error> {
+        let method = method.to_string();
+        let scrubbed_url = self.scrubber.url(url);
+        let body = body.map(|body| self.scrubber.json(&body));
+
+        let mut interactions = self.interactions();
+        let matching: Vec<_> = interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, (interaction, _))| {
+                interaction.method == method
+                    && interaction.url == scrubbed_url
+                    && interaction.body == body
+            })
+            .map(|(index, (_, served))| (index, *served))
+            .collect();
+
+        let index = matching
+            .iter()
+            .find(|(_, served)| !served)
+            .or(matching.last())
+            .map(|(index, _)| *index)
+            .ok_or_else(|| {
+                Error::Response(format!("No recorded interaction for {method} {url}"))
+            })?;
+
+        let (interaction, served) = &mut interactions[index];
+        *served = true;
+
+        let status = StatusCode::from_u16(interaction.status)
+            .map_err(|error| Error::Response(error.to_string()))?;
+
+        Ok((interaction.response.clone().into_bytes(), status))
+    }
+
+    fn replay_response(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<JsonValue>,
+    ) -> Result<Response, Error> {
+        let (data, status) = self.replay(method, url, body)?;
+
+        let mut response = http::Response::new(data);
+        *response.status_mut() = status;
+
+        Ok(Response::from(response))
+    }
+}
+
+impl Api for ReplayClient {
+    type Container<T: Value> = Inner<T>;
+
+    async fn get(&self, url: Url) -> Result<(Bytes, StatusCode), Error> {
+        self.replay(Method::GET, &url, None)
+    }
+
+    async fn get_stream(&self, url: Url, _offset: u64) -> Result<Response, Error> {
+        Err(Error::Response(format!(
+            "Downloads are not recorded, so {url} cannot be replayed"
+        )))
+    }
+
+    async fn delete(&self, url: Url) -> Result<Response, Error> {
+        self.replay_response(Method::DELETE, &url, None)
+    }
+
+    async fn post<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        self.replay_response(Method::POST, &url, Some(body))
+    }
+
+    async fn put<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        self.replay_response(Method::PUT, &url, Some(body))
+    }
+
+    async fn patch<S>(&self, url: Url, msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        let body = serde_json::to_value(msg)?;
+        self.replay_response(Method::PATCH, &url, Some(body))
+    }
+
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use freedom_config::Test;
+
+    use super::*;
+    use crate::{api::Container, testing::FakeFreedom};
+
+    const KEY: &str = "recording-key";
+    const SECRET: &str = "recording-secret";
+
+    /// A writer whose contents remain accessible after being handed to the recording client
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SharedBuffer {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    fn fake() -> FakeFreedom {
+        let fake = FakeFreedom::with_config(Config::new(Test, KEY, SECRET));
+        fake.load_fixture(include_str!("../../resources/satellite_find_one_710.json"))
+            .unwrap();
+        fake.load_fixture(include_str!(
+            "../../resources/satellite_configurations_find_one_810.json"
+        ))
+        .unwrap();
+
+        fake
+    }
+
+    #[tokio::test]
+    async fn record_then_replay() {
+        let buffer = SharedBuffer::default();
+        let recorder = RecordingClient::new(fake(), buffer.clone());
+
+        let recorded = recorder.get_satellite_by_id(710).await.unwrap();
+        let updated = recorder
+            .update_satellite(710)
+            .description("Updated")
+            .send()
+            .await
+            .unwrap();
+        recorder.get_satellite_by_id(999).await.unwrap_err();
+
+        let cassette = buffer.contents();
+        assert_eq!(cassette.lines().count(), 3);
+
+        let replay =
+            ReplayClient::from_reader(Config::new(Test, KEY, SECRET), cassette.as_bytes()).unwrap();
+        assert_eq!(replay.unserved().len(), 3);
+
+        let replayed = replay.get_satellite_by_id(710).await.unwrap();
+        assert_eq!(replayed.into_inner(), recorded.into_inner());
+
+        let replayed = replay
+            .update_satellite(710)
+            .description("Updated")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(replayed.into_inner(), updated.into_inner());
+
+        let error = replay.get_satellite_by_id(999).await.unwrap_err();
+        assert!(matches!(error, Error::NotFound { .. }));
+        assert!(replay.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn unmatched_requests_error() {
+        let replay = ReplayClient::new(Config::new(Test, KEY, SECRET), []);
+
+        let error = replay.get_satellite_by_id(710).await.unwrap_err();
+        assert!(matches!(error, Error::Response(_)));
+
+        let error = replay
+            .update_satellite(710)
+            .description("Updated")
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Response(_)));
+    }
+
+    #[tokio::test]
+    async fn secrets_are_scrubbed() {
+        let buffer = SharedBuffer::default();
+        let recorder = RecordingClient::new(fake(), buffer.clone());
+
+        recorder.get_satellite_by_name(SECRET).await.unwrap_err();
+        recorder.get_satellite_band_by_name(KEY).await.unwrap_err();
+
+        let cassette = buffer.contents();
+        assert!(!cassette.contains(SECRET));
+        assert!(!cassette.contains(KEY));
+        // The URLs are percent-encoded, so the brackets of the replacement are escaped
+        assert!(cassette.contains("name=%5BREDACTED%5D"));
+
+        // Replaying with different credentials still matches the scrubbed requests
+        let replay =
+            ReplayClient::from_reader(Config::new(Test, "other", "creds"), cassette.as_bytes())
+                .unwrap();
+        let error = replay.get_satellite_by_name("creds").await.unwrap_err();
+        assert!(matches!(error, Error::NotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn downloads_are_not_recorded() {
+        let fake = fake();
+        fake.insert_file(42, "data.bin", Bytes::from_static(b"file contents"));
+        let url = fake.path_to_url("downloads/42/data.bin");
+
+        let buffer = SharedBuffer::default();
+        let recorder = RecordingClient::new(fake, buffer.clone());
+        let response = recorder.get_stream(url.clone(), 5).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.bytes().await.unwrap(), "contents".as_bytes());
+        assert!(buffer.contents().is_empty());
+
+        let replay = ReplayClient::new(Config::new(Test, KEY, SECRET), []);
+        let error = replay.get_stream(url, 0).await.unwrap_err();
+        assert!(matches!(error, Error::Response(_)));
+    }
+
+    #[test]
+    fn secrets_needing_escapes_are_scrubbed_from_json() {
+        let secret = r#"se"cr\et"#;
+        let scrubber = Scrubber::new(&Config::new(Test, KEY, secret));
+
+        let body = serde_json::json!({
+            "name": secret,
+            "nested": [{ "value": format!("Bearer {secret}") }],
+            "count": 3,
+        });
+        let scrubbed = scrubber.json(&body);
+        assert!(!scrubbed.to_string().contains("cr"));
+        assert_eq!(scrubbed["name"], REDACTED);
+        assert_eq!(scrubbed["nested"][0]["value"], format!("Bearer {REDACTED}"));
+        assert_eq!(scrubbed["count"], 3);
+
+        let response = scrubber.response(body.to_string().as_bytes()).unwrap();
+        let ResponseBody::Text(text) = response else {
+            panic!("Expected a text body, found {response:?}");
+        };
+        assert!(!text.contains("cr"));
+    }
+
+    #[test]
+    fn secrets_within_longer_words_are_scrubbed() {
+        let scrubber = Scrubber::new(&Config::new(Test, KEY, SECRET));
+
+        let url = Url::parse(
+            "https://example.com/api/satellites/my-recording-key?name=recording-keys&q=satellite",
+        )
+        .unwrap();
+        assert_eq!(
+            scrubber.url(&url),
+            "https://example.com/api/satellites/my-[REDACTED]?name=%5BREDACTED%5Ds&q=satellite"
+        );
+
+        let body = serde_json::json!({ "name": "satellite", "description": "xrecording-secretx" });
+        let scrubbed = scrubber.json(&body);
+        assert_eq!(scrubbed["name"], "satellite");
+        assert_eq!(scrubbed["description"], format!("x{REDACTED}x"));
+
+        // Bodies without a secret are recorded as they were received
+        let response = scrubber.response(br#"{ "name":  "satellite" }"#).unwrap();
+        assert_eq!(
+            response,
+            ResponseBody::Text(String::from(r#"{ "name":  "satellite" }"#))
+        );
+    }
+
+    #[test]
+    fn secrets_are_scrubbed_from_json_keys() {
+        let scrubber = Scrubber::new(&Config::new(Test, KEY, SECRET));
+
+        let body = serde_json::json!({ "recording-key": { "prefix-recording-secret": 1 } });
+        let scrubbed = scrubber.json(&body);
+        assert_eq!(
+            scrubbed,
+            serde_json::json!({ REDACTED: { "prefix-[REDACTED]": 1 } })
+        );
+    }
+
+    #[tokio::test]
+    async fn short_secrets_are_not_recorded() {
+        let fake = FakeFreedom::with_config(Config::new(Test, "sat", SECRET));
+        fake.load_fixture(include_str!("../../resources/satellite_find_one_710.json"))
+            .unwrap();
+
+        let buffer = SharedBuffer::default();
+        let recorder = RecordingClient::new(fake, buffer.clone());
+        let satellite = recorder.get_satellite_by_id(710).await.unwrap();
+
+        assert_eq!(satellite.name, "FooBar 6");
+        assert!(buffer.contents().is_empty());
+    }
+}
@@ -0,0 +1,708 @@
+//! The request handling of the [`FakeFreedom`](super::FakeFreedom), mirroring the routes of the
+//! Freedom API which are used by this crate.
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use reqwest::StatusCode;
+use serde_json::{json, Map, Value as JsonValue};
+use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime};
+use url::Url;
+
+use super::store::{Link, Resource, Store};
+
+/// The rejected fields of a request body, alongside the reason each was rejected
+type FieldErrors = Vec<(String, String)>;
+
+/// The response produced by the fake for a single request
+#[derive(Debug)]
+pub(crate) struct Reply {
+    pub status: StatusCode,
+    pub body: Bytes,
+}
+
+impl Reply {
+    fn json(status: StatusCode, value: &JsonValue) -> Self {
+        Self {
+            status,
+            body: Bytes::from(value.to_string()),
+        }
+    }
+
+    fn no_content() -> Self {
+        Self {
+            status: StatusCode::NO_CONTENT,
+            body: Bytes::new(),
+        }
+    }
+
+    /// A Spring style error payload, as produced by Freedom
+    fn error(status: StatusCode, url: &Url, message: impl Into<String>) -> Self {
+        let body = json!({
+            "timestamp": now(),
+            "status": status.as_u16(),
+            "error": status.canonical_reason(),
+            "message": message.into(),
+            "path": url.path(),
+        });
+
+        Self::json(status, &body)
+    }
+
+    fn not_found(url: &Url) -> Self {
+        Self::error(StatusCode::NOT_FOUND, url, "Not Found")
+    }
+
+    fn method_not_allowed(url: &Url) -> Self {
+        Self::error(StatusCode::METHOD_NOT_ALLOWED, url, "Method Not Allowed")
+    }
+
+    fn invalid(resource: Resource, errors: FieldErrors) -> Self {
+        let errors: Vec<_> = errors
+            .into_iter()
+            .map(|(property, message)| {
+                json!({
+                    "entity": resource.collection(),
+                    "property": property,
+                    "message": message,
+                })
+            })
+            .collect();
+
+        Self::json(StatusCode::BAD_REQUEST, &json!({ "errors": errors }))
+    }
+}
+
+pub(crate) fn get(store: &Store, base: &Url, url: &Url) -> Reply {
+    let Some(segments) = segments(base, url) else {
+        return Reply::not_found(url);
+    };
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    let reply = match segments.as_slice() {
+        ["downloads", task_id, name] => parse_id(task_id)
+            .and_then(|task_id| store.file(task_id, name))
+            .map(|data| Reply {
+                status: StatusCode::OK,
+                body: data.clone(),
+            }),
+        ["satellites", "findOneByName"] => Some(search(
+            store,
+            base,
+            url,
+            Resource::Satellite,
+            "findOneByName",
+        )),
+        [collection, "search", name] => {
+            Resource::from_collection(collection).map(|res| search(store, base, url, res, name))
+        }
+        [collection] => Resource::from_collection(collection).map(|resource| {
+            let ids: Vec<_> = store.iter(resource).map(|(id, _)| id).collect();
+            let page = store.render_page(base, url, resource, &ids, Some(20));
+            Reply::json(StatusCode::OK, &page)
+        }),
+        [collection, id] => lookup(store, collection, id).and_then(|(resource, id)| {
+            let item = store.render(base, resource, id)?;
+            Some(Reply::json(StatusCode::OK, &item))
+        }),
+        [collection, id, name] => lookup(store, collection, id).and_then(|(resource, id)| {
+            let relation = resource.relation(name)?;
+            let ids = store.related(resource, id, relation);
+
+            let value = match relation.many {
+                true => store.render_embedded(base, url, relation.target, &ids),
+                false => store.render(base, relation.target, *ids.first()?)?,
+            };
+
+            Some(Reply::json(StatusCode::OK, &value))
+        }),
+        _ => None,
+    };
+
+    reply.unwrap_or_else(|| Reply::not_found(url))
+}
+
+pub(crate) fn post(store: &mut Store, base: &Url, url: &Url, body: JsonValue) -> Reply {
+    let Some(segments) = segments(base, url) else {
+        return Reply::not_found(url);
+    };
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+    match segments.as_slice() {
+        ["fps"] => {
+            store.tokens += 1;
+            let token = format!("fake-token-{}", store.tokens);
+            Reply::json(StatusCode::OK, &json!({ "token": token }))
+        }
+        ["accounts", id, "newuser"] => match parse_id(id) {
+            Some(id) if store.contains(Resource::Account, id) => {
+                let mut body = body;
+                if let Some(fields) = body.as_object_mut() {
+                    let account = super::store::href(base, Resource::Account, id);
+                    fields.insert(String::from("account"), json!(account));
+                }
+                create(store, base, Resource::User, body)
+            }
+            _ => Reply::not_found(url),
+        },
+        [collection] => match Resource::from_collection(collection) {
+            Some(
+                resource @ (Resource::Band
+                | Resource::Satellite
+                | Resource::SatelliteConfiguration
+                | Resource::TaskRequest
+                | Resource::Override),
+            ) => create(store, base, resource, body),
+            Some(_) => Reply::method_not_allowed(url),
+            None => Reply::not_found(url),
+        },
+        _ => Reply::not_found(url),
+    }
+}
+
+/// Update the stored resource from the fields of the body, replacing any relations which are
+/// included in the body.
+///
+/// When `replace` is set, as for a `PUT`, fields absent from the body are removed from the
+/// resource, otherwise they are left unchanged.
+pub(crate) fn update(
+    store: &mut Store,
+    base: &Url,
+    url: &Url,
+    body: JsonValue,
+    replace: bool,
+) -> Reply {
+    let Some(segments) = segments(base, url) else {
+        return Reply::not_found(url);
+    };
+
+    let (resource, id) = match segments.as_slice() {
+        [collection, id] => match lookup(store, collection, id) {
+            Some(found) => found,
+            None => return Reply::not_found(url),
+        },
+        _ => return Reply::method_not_allowed(url),
+    };
+
+    let JsonValue::Object(mut fields) = body else {
+        let errors = vec![(String::new(), String::from("must be a JSON object"))];
+        return Reply::invalid(resource, errors);
+    };
+
+    let relations = match take_relations(store, base, resource, &mut fields, &[]) {
+        Ok(relations) => relations,
+        Err(errors) => return Reply::invalid(resource, errors),
+    };
+
+    if let Some(entry) = store.get_mut(resource, id) {
+        if replace {
+            let created = entry.value.remove("created");
+            entry.value.clear();
+            entry
+                .value
+                .extend(created.map(|created| (String::from("created"), created)));
+        }
+        entry.value.extend(fields);
+        entry
+            .value
+            .insert(String::from("modified"), JsonValue::String(now()));
+        entry.relations.extend(relations);
+    }
+
+    match store.render(base, resource, id) {
+        Some(item) => Reply::json(StatusCode::OK, &item),
+        None => Reply::not_found(url),
+    }
+}
+
+pub(crate) fn delete(store: &mut Store, base: &Url, url: &Url) -> Reply {
+    let Some(segments) = segments(base, url) else {
+        return Reply::not_found(url);
+    };
+
+    match segments.as_slice() {
+        [collection, id] => match lookup(store, collection, id) {
+            Some((resource, id)) => {
+                store.remove(resource, id);
+                Reply::no_content()
+            }
+            None => Reply::not_found(url),
+        },
+        _ => Reply::method_not_allowed(url),
+    }
+}
+
+/// Create a resource from the body of a POST, as the builders in [`crate::Api`] send them.
+fn create(store: &mut Store, base: &Url, resource: Resource, body: JsonValue) -> Reply {
+    let JsonValue::Object(body) = body else {
+        let errors = vec![(String::new(), String::from("must be a JSON object"))];
+        return Reply::invalid(resource, errors);
+    };
+
+    // Absent optional fields are sent as `null` by the builders
+    let mut fields: Map<String, JsonValue> = body
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .collect();
+
+    let (required_fields, required_relations): (&[&str], &[&str]) = match resource {
+        Resource::Band => (&["name", "type", "frequencyMghz"], &[]),
+        Resource::Satellite => (&["name"], &["configuration"]),
+        Resource::SatelliteConfiguration => (&["name"], &[]),
+        Resource::User => (&["firstName", "lastName", "email"], &["account"]),
+        Resource::Override => (&["name"], &["satellite", "configuration"]),
+        Resource::TaskRequest => (
+            &["type", "targetDate", "duration"],
+            &["site", "satellite", "configuration", "targetBands"],
+        ),
+        _ => (&[], &[]),
+    };
+
+    let mut errors: Vec<_> = required_fields
+        .iter()
+        .filter(|field| !fields.contains_key(**field))
+        .map(|field| (field.to_string(), String::from("must not be null")))
+        .collect();
+
+    let mut relations = match take_relations(store, base, resource, &mut fields, required_relations)
+    {
+        Ok(relations) => relations,
+        Err(relation_errors) => {
+            errors.extend(relation_errors);
+            HashMap::new()
+        }
+    };
+
+    if resource == Resource::TaskRequest {
+        if let Err(error) = task_request_fields(&mut fields) {
+            errors.push(error);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Reply::invalid(resource, errors);
+    }
+
+    let account = store
+        .iter(Resource::Account)
+        .find(|(_, entry)| entry.str_field("name") == Some(store.account_name.as_str()))
+        .map(|(id, _)| id);
+
+    let defaults = match resource {
+        Resource::Band => json!({ "accountName": store.account_name }),
+        Resource::Satellite => json!({ "description": "", "accountName": store.account_name }),
+        Resource::SatelliteConfiguration => json!({
+            "orbit": "",
+            "notes": "",
+            "pullTLE": false,
+            "accountName": store.account_name,
+        }),
+        Resource::User => json!({
+            "verified": false,
+            "apiAccessEnabled": fields.get("machineService").cloned().unwrap_or(json!(false)),
+            "preferences": {
+                "visibilityDays": 7,
+                "minElevation": 0.0,
+                "maxElevation": 90.0,
+                "minDuration": 0.0,
+                "elevationTolerance": 0.0,
+                "durationTolerance": 0.0,
+                "notifyViaEmail": false,
+                "notifyViaText": false,
+            },
+        }),
+        _ => json!({}),
+    };
+
+    if let JsonValue::Object(defaults) = defaults {
+        for (field, value) in defaults {
+            fields.entry(field).or_insert(value);
+        }
+    }
+    fields.insert(String::from("created"), JsonValue::String(now()));
+    fields.insert(String::from("modified"), JsonValue::Null);
+
+    if let Some(account) = account {
+        if resource.relation("account").is_some() {
+            relations.entry("account").or_insert(vec![account]);
+        }
+    }
+
+    let id = store.next_id(resource);
+    store.insert(resource, id, JsonValue::Object(fields));
+    for (relation, targets) in relations {
+        store.set_relation(resource, id, relation, targets);
+    }
+
+    match store.render(base, resource, id) {
+        Some(item) => Reply::json(StatusCode::CREATED, &item),
+        None => Reply::error(StatusCode::INTERNAL_SERVER_ERROR, base, "Failed to store"),
+    }
+}
+
+/// Fill in the fields Freedom derives when receiving a new task request
+fn task_request_fields(fields: &mut Map<String, JsonValue>) -> Result<(), (String, String)> {
+    let invalid = |field: &str| (field.to_owned(), String::from("is invalid"));
+
+    let target = fields
+        .get("targetDate")
+        .and_then(JsonValue::as_str)
+        .and_then(parse_time)
+        .ok_or_else(|| invalid("targetDate"))?;
+    let duration = fields
+        .get("duration")
+        .and_then(JsonValue::as_u64)
+        .ok_or_else(|| invalid("duration"))?;
+    let flex = fields
+        .get("hoursOfFlex")
+        .and_then(JsonValue::as_u64)
+        .unwrap_or(0);
+    let typ = fields.get("type").and_then(JsonValue::as_str).unwrap_or("");
+
+    let flex_duration = Duration::hours(flex as i64);
+    let (earliest, latest) = match typ {
+        "BEFORE" => (target - flex_duration, target),
+        "AFTER" => (target, target + flex_duration),
+        "AROUND" => (target - flex_duration, target + flex_duration),
+        "TEST" | "EXACT" => (target, target),
+        _ => return Err(invalid("type")),
+    };
+
+    let status = json!({
+        "created": now(),
+        "status": "RECEIVED",
+        "reason": "Saved to Database and awaiting scheduling",
+    });
+
+    let derived = json!({
+        "hoursOfFlex": flex,
+        "minimumDuration": duration,
+        "earliestStart": format_time(earliest),
+        "latestStart": format_time(latest),
+        "transmitting": false,
+        "statusChanges": [status],
+        "latestStatusChange": status,
+        "taskActive": true,
+        "taskRequestScheduled": false,
+        "taskRequestCancelled": false,
+        "flex": flex > 0,
+    });
+
+    if let JsonValue::Object(derived) = derived {
+        for (field, value) in derived {
+            fields.entry(field).or_insert(value);
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the fields naming relations of the resource from the body, resolving their URLs into
+/// IDs of existing resources.
+fn take_relations(
+    store: &Store,
+    base: &Url,
+    resource: Resource,
+    fields: &mut Map<String, JsonValue>,
+    required: &[&str],
+) -> Result<HashMap<&'static str, Vec<i32>>, FieldErrors> {
+    let mut relations = HashMap::new();
+    let mut errors = Vec::new();
+
+    for relation in resource.relations() {
+        if !matches!(relation.link, Link::Stored) {
+            continue;
+        }
+
+        let Some(value) = fields.remove(relation.name) else {
+            if required.contains(&relation.name) {
+                errors.push((relation.name.to_owned(), String::from("must not be null")));
+            }
+            continue;
+        };
+
+        let urls: Vec<_> = match &value {
+            JsonValue::String(url) => vec![url.as_str()],
+            JsonValue::Array(urls) => urls.iter().filter_map(JsonValue::as_str).collect(),
+            _ => Vec::new(),
+        };
+
+        let ids: Option<Vec<_>> = urls
+            .iter()
+            .map(|url| {
+                let (target, id) = parse_reference(base, url)?;
+                (target == relation.target && store.contains(target, id)).then_some(id)
+            })
+            .collect();
+
+        match ids {
+            Some(ids) if !(ids.is_empty() && required.contains(&relation.name)) => {
+                relations.insert(relation.name, ids);
+            }
+            _ => errors.push((
+                relation.name.to_owned(),
+                format!("must reference existing {}", relation.target.collection()),
+            )),
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(relations),
+        false => Err(errors),
+    }
+}
+
+fn search(store: &Store, base: &Url, url: &Url, resource: Resource, name: &str) -> Reply {
+    let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+    let param = |key: &str| query.get(key).map(String::as_str).unwrap_or_default();
+    let time = |key: &str| parse_time(param(key));
+
+    let entries = || store.iter(resource);
+    let field_time = |id: i32, field: &str| {
+        store
+            .get(resource, id)
+            .and_then(|entry| entry.str_field(field))
+            .and_then(parse_time)
+    };
+    let between = |id: i32, field: &str| match (field_time(id, field), time("start"), time("end")) {
+        (Some(value), Some(start), Some(end)) => start <= value && value <= end,
+        _ => false,
+    };
+    let satellite_named = |id: i32, name: &str| {
+        store
+            .related_one(resource, id, "satellite")
+            .and_then(|satellite| store.get(Resource::Satellite, satellite))
+            .is_some_and(|satellite| satellite.str_field("name") == Some(name))
+    };
+    let reference = |key: &str, expected: Resource| {
+        parse_reference(base, param(key))
+            .filter(|(target, _)| *target == expected)
+            .map(|(_, id)| id)
+    };
+    let configuration = || reference("configuration", Resource::SiteConfiguration);
+    let has_configuration = |id: i32| {
+        configuration().is_some()
+            && store.related_one(resource, id, "configuration") == configuration()
+    };
+    // Tasks are matched through the request they were scheduled from
+    let request_of = |id: i32| match resource {
+        Resource::Task => store.related_one(resource, id, "taskRequest"),
+        _ => Some(id),
+    };
+    let request_related = |id: i32, relation: &str| {
+        request_of(id)
+            .and_then(|request| store.related_one(Resource::TaskRequest, request, relation))
+    };
+    let in_account = |id: i32| {
+        let account = request_related(id, "satellite")
+            .and_then(|satellite| store.related_one(Resource::Satellite, satellite, "account"));
+        account.is_some() && account == reference("account", Resource::Account)
+    };
+    let has_band = |id: i32| {
+        let band = reference("band", Resource::Band);
+        request_of(id).is_some_and(|request| {
+            band.is_some_and(|band| {
+                store
+                    .related(
+                        Resource::TaskRequest,
+                        request,
+                        Resource::TaskRequest.relation("targetBands").unwrap(),
+                    )
+                    .contains(&band)
+            })
+        })
+    };
+    let status = |id: i32| {
+        store
+            .get(resource, id)
+            .and_then(|entry| entry.value.get("latestStatusChange"))
+            .and_then(|status| status.get("status"))
+            .and_then(JsonValue::as_str)
+            .map(String::from)
+    };
+    let overlapping = |id: i32, start: &str, end: &str| match (
+        field_time(id, start),
+        field_time(id, end),
+        time("start"),
+        time("end"),
+    ) {
+        (Some(start), Some(end), Some(from), Some(to)) => start <= to && from <= end,
+        _ => false,
+    };
+
+    let mut ids: Vec<i32> = match (resource, name) {
+        (_, "findOneByName") => {
+            let found = entries().find(|(_, entry)| entry.str_field("name") == Some(param("name")));
+
+            return match found.and_then(|(id, _)| store.render(base, resource, id)) {
+                Some(item) => Reply::json(StatusCode::OK, &item),
+                None => Reply::not_found(url),
+            };
+        }
+        (
+            Resource::Band | Resource::Satellite | Resource::SatelliteConfiguration,
+            "findAllByAccountName",
+        ) => entries()
+            .filter(|(_, entry)| entry.str_field("accountName") == Some(param("accountName")))
+            .map(|(id, _)| id)
+            .collect(),
+        (Resource::TaskRequest, "findAll") => entries().map(|(id, _)| id).collect(),
+        (Resource::TaskRequest, "findAllByIds") => param("ids")
+            .split(',')
+            .filter_map(parse_id)
+            .filter(|id| store.contains(resource, *id))
+            .collect(),
+        (Resource::TaskRequest, "findBySatelliteName") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| satellite_named(*id, param("name")))
+            .collect(),
+        (Resource::TaskRequest, "findByStatus") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| status(*id).as_deref() == Some(param("status")))
+            .collect(),
+        (Resource::TaskRequest, "findAllByStatusAndAccountAndTargetDateBetween") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| {
+                status(*id).as_deref() == Some(param("status"))
+                    && in_account(*id)
+                    && between(*id, "targetDate")
+            })
+            .collect(),
+        (Resource::TaskRequest, "findAllByAccountAndTargetDateBetween") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| in_account(*id) && between(*id, "targetDate"))
+            .collect(),
+        // Every stored request is treated as public
+        (Resource::TaskRequest, "findAllByOverlappingPublic") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| overlapping(*id, "earliestStart", "latestStart"))
+            .collect(),
+        (Resource::TaskRequest, "findAllByTargetDateBetween") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| between(*id, "targetDate"))
+            .collect(),
+        (Resource::TaskRequest, "findAllByTypeAndTargetDateBetween") => entries()
+            .filter(|(_, entry)| entry.str_field("type") == Some(param("type")))
+            .map(|(id, _)| id)
+            .filter(|id| between(*id, "targetDate"))
+            .collect(),
+        (Resource::TaskRequest, "findAllBySatelliteNameAndTargetDateBetween") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| satellite_named(*id, param("name")) && between(*id, "targetDate"))
+            .collect(),
+        (Resource::TaskRequest, "findAllByConfigurationOrderByCreatedAsc") => {
+            let mut ids: Vec<_> = entries()
+                .map(|(id, _)| id)
+                .filter(|id| has_configuration(*id))
+                .collect();
+            ids.sort_by_key(|id| field_time(*id, "created"));
+            ids
+        }
+        (Resource::TaskRequest, "findAllByConfigurationAndTargetDateBetween") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| has_configuration(*id) && between(*id, "targetDate"))
+            .collect(),
+        (Resource::TaskRequest, "findAllByConfigurationAndSatelliteNamesAndTargetDateBetween") => {
+            let names: Vec<&str> = param("satelliteNames").split(',').collect();
+            entries()
+                .map(|(id, _)| id)
+                .filter(|id| {
+                    has_configuration(*id)
+                        && names.iter().any(|name| satellite_named(*id, name))
+                        && between(*id, "targetDate")
+                })
+                .collect()
+        }
+        (Resource::Task, "findByOverlapping") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| overlapping(*id, "start", "end"))
+            .collect(),
+        (Resource::Task, "findByAccountAndPassOverlapping") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| in_account(*id) && overlapping(*id, "start", "end"))
+            .collect(),
+        (Resource::Task, "findByAccountAndSiteConfigurationAndBandAndPassOverlapping") => entries()
+            .map(|(id, _)| id)
+            .filter(|id| {
+                let configuration = reference("siteConfig", Resource::SiteConfiguration);
+                in_account(*id)
+                    && configuration.is_some()
+                    && request_related(*id, "configuration") == configuration
+                    && has_band(*id)
+                    && overlapping(*id, "start", "end")
+            })
+            .collect(),
+        (Resource::Task, "findByStartBetweenOrderByStartAsc") => {
+            let mut ids: Vec<_> = entries()
+                .map(|(id, _)| id)
+                .filter(|id| between(*id, "start"))
+                .collect();
+            ids.sort_by_key(|id| field_time(*id, "start"));
+            ids
+        }
+        _ => return Reply::not_found(url),
+    };
+
+    ids.dedup();
+    Reply::json(
+        StatusCode::OK,
+        &store.render_page(base, url, resource, &ids, None),
+    )
+}
+
+/// The path segments of the URL following the environment's entrypoint
+fn segments(base: &Url, url: &Url) -> Option<Vec<String>> {
+    if base.origin() != url.origin() {
+        return None;
+    }
+
+    let prefix = base.path().trim_end_matches('/');
+    let path = url.path().strip_prefix(prefix)?;
+
+    Some(
+        path.split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(String::from)
+            .collect(),
+    )
+}
+
+fn lookup(store: &Store, collection: &str, id: &str) -> Option<(Resource, i32)> {
+    let resource = Resource::from_collection(collection)?;
+    let id = parse_id(id)?;
+
+    store.contains(resource, id).then_some((resource, id))
+}
+
+fn parse_id(id: &str) -> Option<i32> {
+    id.trim().parse().ok()
+}
+
+/// Resolve a URL, absolute or relative to the entrypoint's origin, into the resource it names
+pub(crate) fn parse_reference(base: &Url, reference: &str) -> Option<(Resource, i32)> {
+    let url = Url::parse(reference)
+        .or_else(|_| base.join(reference))
+        .ok()?;
+    let mut segments = url.path_segments()?.rev().filter(|s| !s.is_empty());
+
+    let id = parse_id(segments.next()?)?;
+    let resource = Resource::from_collection(segments.next()?)?;
+
+    Some((resource, id))
+}
+
+/// Parse a timestamp from a query parameter.
+///
+/// Offsets such as `+01:00` reach the fake with the `+` decoded as a space when the caller did not
+/// percent-encode the query, so spaces are treated as `+`.
+fn parse_time(value: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(&value.replace(' ', "+"), &Iso8601::DEFAULT).ok()
+}
+
+fn format_time(time: OffsetDateTime) -> String {
+    time.format(&Iso8601::DEFAULT)
+        .expect("Failed to format timestamp")
+}
+
+fn now() -> String {
+    format_time(OffsetDateTime::now_utc())
+}
@@ -0,0 +1,464 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use bytes::Bytes;
+use serde_json::{json, Map, Value as JsonValue};
+use url::Url;
+
+/// The kinds of resources held by a [`FakeFreedom`](super::FakeFreedom)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Resource {
+    Account,
+    Satellite,
+    Band,
+    SatelliteConfiguration,
+    Site,
+    SiteConfiguration,
+    TaskRequest,
+    Task,
+    User,
+    Override,
+}
+
+/// Where the IDs of a relation are kept
+pub(crate) enum Link {
+    /// The IDs are stored alongside the resource itself
+    Stored,
+    /// The IDs are those of the target resources whose named relation points back at the resource
+    Inverse(&'static str),
+}
+
+/// A relation between two resources, served at `{collection}/{id}/{name}`
+pub(crate) struct Relation {
+    pub name: &'static str,
+    pub target: Resource,
+    pub many: bool,
+    pub link: Link,
+}
+
+const fn one(name: &'static str, target: Resource) -> Relation {
+    Relation {
+        name,
+        target,
+        many: false,
+        link: Link::Stored,
+    }
+}
+
+const fn many(name: &'static str, target: Resource) -> Relation {
+    Relation {
+        name,
+        target,
+        many: true,
+        link: Link::Stored,
+    }
+}
+
+const fn inverse(name: &'static str, target: Resource, by: &'static str, many: bool) -> Relation {
+    Relation {
+        name,
+        target,
+        many,
+        link: Link::Inverse(by),
+    }
+}
+
+mod relations {
+    use super::{inverse, many, one, Relation, Resource::*};
+
+    pub(super) const ACCOUNT: &[Relation] = &[
+        inverse("satellites", Satellite, "account", true),
+        inverse("users", User, "account", true),
+    ];
+    pub(super) const SATELLITE: &[Relation] = &[
+        one("configuration", SatelliteConfiguration),
+        one("account", Account),
+    ];
+    pub(super) const BAND: &[Relation] = &[one("account", Account)];
+    pub(super) const SATELLITE_CONFIGURATION: &[Relation] =
+        &[one("account", Account), many("bandDetails", Band)];
+    pub(super) const SITE: &[Relation] =
+        &[inverse("configurations", SiteConfiguration, "site", true)];
+    pub(super) const SITE_CONFIGURATION: &[Relation] = &[one("site", Site)];
+    pub(super) const TASK_REQUEST: &[Relation] = &[
+        one("site", Site),
+        one("satellite", Satellite),
+        one("configuration", SiteConfiguration),
+        many("targetBands", Band),
+        one("user", User),
+        one("override", Override),
+        inverse("task", Task, "taskRequest", false),
+    ];
+    pub(super) const TASK: &[Relation] = &[
+        one("taskRequest", TaskRequest),
+        one("config", SiteConfiguration),
+    ];
+    pub(super) const USER: &[Relation] = &[one("account", Account)];
+    pub(super) const OVERRIDE: &[Relation] = &[
+        one("satellite", Satellite),
+        one("configuration", SatelliteConfiguration),
+    ];
+}
+
+impl Resource {
+    pub(crate) const ALL: [Resource; 10] = [
+        Resource::Account,
+        Resource::Satellite,
+        Resource::Band,
+        Resource::SatelliteConfiguration,
+        Resource::Site,
+        Resource::SiteConfiguration,
+        Resource::TaskRequest,
+        Resource::Task,
+        Resource::User,
+        Resource::Override,
+    ];
+
+    /// The path segment under which the resource is served, e.g. `satellite_bands`
+    pub fn collection(&self) -> &'static str {
+        match self {
+            Resource::Account => "accounts",
+            Resource::Satellite => "satellites",
+            Resource::Band => "satellite_bands",
+            Resource::SatelliteConfiguration => "satellite_configurations",
+            Resource::Site => "sites",
+            Resource::SiteConfiguration => "configurations",
+            Resource::TaskRequest => "requests",
+            Resource::Task => "tasks",
+            Resource::User => "users",
+            Resource::Override => "overrides",
+        }
+    }
+
+    pub(crate) fn from_collection(collection: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|resource| resource.collection() == collection)
+    }
+
+    /// The name of the link which Freedom includes next to `self`, pointing at the same resource
+    fn alias(&self) -> &'static str {
+        match self {
+            Resource::Account => "account",
+            Resource::Satellite => "satellites",
+            Resource::Band => "bands",
+            Resource::SatelliteConfiguration => "configuration",
+            Resource::Site => "sites",
+            Resource::SiteConfiguration => "siteConfiguration",
+            Resource::TaskRequest => "taskRequest",
+            Resource::Task => "tasks",
+            Resource::User => "user",
+            Resource::Override => "override",
+        }
+    }
+
+    pub(crate) fn relations(&self) -> &'static [Relation] {
+        match self {
+            Resource::Account => relations::ACCOUNT,
+            Resource::Satellite => relations::SATELLITE,
+            Resource::Band => relations::BAND,
+            Resource::SatelliteConfiguration => relations::SATELLITE_CONFIGURATION,
+            Resource::Site => relations::SITE,
+            Resource::SiteConfiguration => relations::SITE_CONFIGURATION,
+            Resource::TaskRequest => relations::TASK_REQUEST,
+            Resource::Task => relations::TASK,
+            Resource::User => relations::USER,
+            Resource::Override => relations::OVERRIDE,
+        }
+    }
+
+    pub(crate) fn relation(&self, name: &str) -> Option<&'static Relation> {
+        self.relations()
+            .iter()
+            .find(|relation| relation.name == name)
+    }
+}
+
+/// A single stored resource
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Entry {
+    /// The fields of the resource, without its `_links`
+    pub value: Map<String, JsonValue>,
+    /// The IDs of the resources this one points at, keyed by relation name
+    pub relations: HashMap<&'static str, Vec<i32>>,
+}
+
+impl Entry {
+    pub(crate) fn str_field(&self, field: &str) -> Option<&str> {
+        self.value.get(field).and_then(JsonValue::as_str)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Store {
+    /// The name of the account which owns the resources created through the fake
+    pub account_name: String,
+    /// The number of FPS tokens handed out so far
+    pub tokens: u64,
+    entries: HashMap<Resource, BTreeMap<i32, Entry>>,
+    files: HashMap<(i32, String), Bytes>,
+}
+
+impl Store {
+    pub(crate) fn next_id(&self, resource: Resource) -> i32 {
+        self.entries
+            .get(&resource)
+            .and_then(|entries| entries.keys().next_back())
+            .map_or(1, |id| id + 1)
+    }
+
+    /// Store the value under the provided ID, replacing any existing entry but keeping its
+    /// relations.
+    pub(crate) fn insert(&mut self, resource: Resource, id: i32, mut value: JsonValue) {
+        let value = match value.as_object_mut() {
+            Some(object) => {
+                object.remove("_links");
+                std::mem::take(object)
+            }
+            None => Map::new(),
+        };
+
+        let entries = self.entries.entry(resource).or_default();
+        let entry = entries.entry(id).or_default();
+        entry.value = value;
+    }
+
+    pub(crate) fn get(&self, resource: Resource, id: i32) -> Option<&Entry> {
+        self.entries.get(&resource)?.get(&id)
+    }
+
+    pub(crate) fn get_mut(&mut self, resource: Resource, id: i32) -> Option<&mut Entry> {
+        self.entries.get_mut(&resource)?.get_mut(&id)
+    }
+
+    pub(crate) fn contains(&self, resource: Resource, id: i32) -> bool {
+        self.get(resource, id).is_some()
+    }
+
+    pub(crate) fn remove(&mut self, resource: Resource, id: i32) -> Option<Entry> {
+        self.entries.get_mut(&resource)?.remove(&id)
+    }
+
+    pub(crate) fn len(&self, resource: Resource) -> usize {
+        self.entries.get(&resource).map_or(0, BTreeMap::len)
+    }
+
+    /// Iterate over the stored resources of the given kind, in ascending ID order
+    pub(crate) fn iter(&self, resource: Resource) -> impl Iterator<Item = (i32, &Entry)> {
+        self.entries
+            .get(&resource)
+            .into_iter()
+            .flat_map(|entries| entries.iter().map(|(id, entry)| (*id, entry)))
+    }
+
+    pub(crate) fn set_relation(
+        &mut self,
+        resource: Resource,
+        id: i32,
+        relation: &'static str,
+        targets: Vec<i32>,
+    ) {
+        if let Some(entry) = self.get_mut(resource, id) {
+            entry.relations.insert(relation, targets);
+        }
+    }
+
+    /// The IDs of the existing resources the relation points at
+    pub(crate) fn related(&self, resource: Resource, id: i32, relation: &Relation) -> Vec<i32> {
+        match relation.link {
+            Link::Stored => self
+                .get(resource, id)
+                .and_then(|entry| entry.relations.get(relation.name))
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|target| self.contains(relation.target, *target))
+                .collect(),
+            Link::Inverse(by) => self
+                .iter(relation.target)
+                .filter(|(_, entry)| entry.relations.get(by).is_some_and(|ids| ids.contains(&id)))
+                .map(|(target, _)| target)
+                .collect(),
+        }
+    }
+
+    /// The first resource the relation points at, if any
+    pub(crate) fn related_one(&self, resource: Resource, id: i32, name: &str) -> Option<i32> {
+        let relation = resource.relation(name)?;
+        self.related(resource, id, relation).first().copied()
+    }
+
+    pub(crate) fn insert_file(&mut self, task_id: i32, name: String, data: Bytes) {
+        self.files.insert((task_id, name), data);
+    }
+
+    pub(crate) fn file(&self, task_id: i32, name: &str) -> Option<&Bytes> {
+        self.files.get(&(task_id, name.to_owned()))
+    }
+
+    /// Render the resource as Freedom would, including its `_links`
+    pub(crate) fn render(&self, base: &Url, resource: Resource, id: i32) -> Option<JsonValue> {
+        let entry = self.get(resource, id)?;
+        let own = href(base, resource, id);
+
+        let mut links = Map::new();
+        links.insert(String::from("self"), json!({ "href": own }));
+        links.insert(resource.alias().to_owned(), json!({ "href": own }));
+        for relation in resource.relations() {
+            let href = format!("{own}/{}", relation.name);
+            links.insert(relation.name.to_owned(), json!({ "href": href }));
+        }
+
+        let mut value = entry.value.clone();
+        value.insert(String::from("_links"), JsonValue::Object(links));
+
+        Some(JsonValue::Object(value))
+    }
+
+    /// Render the resources as a page of a paginated listing.
+    ///
+    /// The page is selected with the `page` and `size` query parameters of the URL. When no size
+    /// is requested, `default_size` is used, and when that is `None` all items fit on one page.
+    pub(crate) fn render_page(
+        &self,
+        base: &Url,
+        url: &Url,
+        resource: Resource,
+        ids: &[i32],
+        default_size: Option<usize>,
+    ) -> JsonValue {
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+        };
+
+        let mut ids = ids.to_vec();
+        self.sort(resource, &mut ids, url);
+
+        let total = ids.len();
+        let size = param("size").or(default_size).unwrap_or(total).max(1);
+        let number = param("page").unwrap_or(0);
+        let total_pages = total.div_ceil(size);
+
+        let items: Vec<_> = ids
+            .iter()
+            .skip(number.saturating_mul(size))
+            .take(size)
+            .filter_map(|id| self.render(base, resource, *id))
+            .collect();
+
+        let mut links = Map::new();
+        let mut link = |name: &str, number: usize| {
+            let href = page_url(url, number, size);
+            links.insert(name.to_owned(), json!({ "href": href }));
+        };
+        link("self", number);
+        if total_pages > 1 {
+            link("first", 0);
+            link("last", total_pages - 1);
+        }
+        if number + 1 < total_pages {
+            link("next", number + 1);
+        }
+        if number > 0 && number <= total_pages {
+            link("prev", number - 1);
+        }
+
+        json!({
+            "_embedded": { resource.collection(): items },
+            "_links": links,
+            "page": {
+                "size": size,
+                "totalElements": total,
+                "totalPages": total_pages,
+                "number": number,
+            }
+        })
+    }
+
+    /// Sort the IDs by the `sort` query parameters of the URL, e.g. `sort=name,desc`
+    fn sort(&self, resource: Resource, ids: &mut [i32], url: &Url) {
+        let sorts: Vec<_> = url
+            .query_pairs()
+            .filter(|(key, _)| key == "sort")
+            .map(|(_, value)| value.into_owned())
+            .collect();
+
+        // Sorting is stable, so sorting by the least significant field first preserves precedence
+        for sort in sorts.iter().rev() {
+            let (field, direction) = sort.split_once(',').unwrap_or((sort, "asc"));
+            let value = |id: &i32| {
+                self.get(resource, *id)
+                    .and_then(|entry| entry.value.get(field))
+                    .cloned()
+                    .unwrap_or(JsonValue::Null)
+            };
+
+            ids.sort_by(|a, b| {
+                let ordering = compare(&value(a), &value(b));
+                match direction.eq_ignore_ascii_case("desc") {
+                    true => ordering.reverse(),
+                    false => ordering,
+                }
+            });
+        }
+    }
+
+    /// Render the resources as an `_embedded` listing without pagination
+    pub(crate) fn render_embedded(
+        &self,
+        base: &Url,
+        url: &Url,
+        resource: Resource,
+        ids: &[i32],
+    ) -> JsonValue {
+        let items: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.render(base, resource, *id))
+            .collect();
+
+        json!({
+            "_embedded": { resource.collection(): items },
+            "_links": { "self": { "href": url } },
+        })
+    }
+}
+
+pub(crate) fn href(base: &Url, resource: Resource, id: i32) -> Url {
+    base.join(&format!("{}/{id}", resource.collection()))
+        .expect("Invalid URL construction")
+}
+
+/// Order JSON values of the same type by their value, and values of differing types arbitrarily
+fn compare(a: &JsonValue, b: &JsonValue) -> Ordering {
+    match (a, b) {
+        (JsonValue::Number(a), JsonValue::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (JsonValue::String(a), JsonValue::String(b)) => a.cmp(b),
+        (JsonValue::Bool(a), JsonValue::Bool(b)) => a.cmp(b),
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+fn page_url(url: &Url, number: usize, size: usize) -> Url {
+    let mut url = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "page" && key != "size")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair("page", &number.to_string())
+        .append_pair("size", &size.to_string());
+
+    url
+}
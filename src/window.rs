@@ -0,0 +1,192 @@
+//! # Time Windows
+//!
+//! Many of Freedom's searches are bounded by a span of time. This module contains the type used to
+//! describe such a span, so that it is checked once when created rather than by every search.
+use std::ops::Range;
+
+use time::{format_description::well_known::Iso8601, Duration, OffsetDateTime, UtcOffset};
+use url::Url;
+
+use crate::error::Error;
+
+/// A span of time from a start instant to a later end instant.
+///
+/// Both instants are normalised to UTC when the window is created, so that the same instant is sent
+/// to Freedom whichever offset it was provided in.
+///
+/// # Example
+///
+/// ```
+/// # use freedom_api::prelude::*;
+/// # use time::macros::datetime;
+/// let window = TimeWindow::new(
+///     datetime!(2024-01-01 12:00 -07:00),
+///     datetime!(2024-01-02 12:00 -07:00),
+/// )?;
+/// assert_eq!(window.start(), datetime!(2024-01-01 19:00 UTC));
+///
+/// let reversed = TimeWindow::new(window.end(), window.start());
+/// assert!(reversed.is_err());
+/// # Ok::<_, freedom_api::error::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeWindow {
+    start: OffsetDateTime,
+    end: OffsetDateTime,
+}
+
+impl TimeWindow {
+    /// Create the window from `start` to `end`.
+    ///
+    /// Fails with [`Error::InvalidTimeWindow`] unless the start is before the end and both are
+    /// representable in UTC, and with [`Error::TimeFormatError`] if either instant cannot be sent
+    /// to Freedom.
+    pub fn new(start: OffsetDateTime, end: OffsetDateTime) -> Result<Self, Error> {
+        let (Some(start), Some(end)) = (
+            start.checked_to_offset(UtcOffset::UTC),
+            end.checked_to_offset(UtcOffset::UTC),
+        ) else {
+            return Err(Error::InvalidTimeWindow {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        };
+
+        if start >= end {
+            return Err(Error::InvalidTimeWindow {
+                start: start.to_string(),
+                end: end.to_string(),
+            });
+        }
+        start.format(&Iso8601::DEFAULT)?;
+        end.format(&Iso8601::DEFAULT)?;
+
+        Ok(Self { start, end })
+    }
+
+    /// Create the window starting at `start` and lasting for `duration`
+    pub fn starting_at(start: OffsetDateTime, duration: Duration) -> Result<Self, Error> {
+        let end = start.checked_add(duration).ok_or_else(|| {
+            Error::TimeFormatError(format!("{start} + {duration} is out of range"))
+        })?;
+
+        Self::new(start, end)
+    }
+
+    /// The start of the window, in UTC
+    pub fn start(&self) -> OffsetDateTime {
+        self.start
+    }
+
+    /// The end of the window, in UTC
+    pub fn end(&self) -> OffsetDateTime {
+        self.end
+    }
+
+    /// The time between the start and the end of the window
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Whether the instant falls within the window, including its start and end
+    pub fn contains(&self, instant: OffsetDateTime) -> bool {
+        self.start <= instant && instant <= self.end
+    }
+
+    /// Append the `start` and `end` query parameters of the window to the URL
+    pub(crate) fn append_to(&self, url: &mut Url) {
+        let format = |instant: OffsetDateTime| {
+            instant
+                .format(&Iso8601::DEFAULT)
+                .expect("Checked when the window was created")
+        };
+
+        url.query_pairs_mut()
+            .append_pair("start", &format(self.start))
+            .append_pair("end", &format(self.end));
+    }
+}
+
+impl TryFrom<(OffsetDateTime, OffsetDateTime)> for TimeWindow {
+    type Error = Error;
+
+    fn try_from((start, end): (OffsetDateTime, OffsetDateTime)) -> Result<Self, Self::Error> {
+        Self::new(start, end)
+    }
+}
+
+impl TryFrom<Range<OffsetDateTime>> for TimeWindow {
+    type Error = Error;
+
+    fn try_from(range: Range<OffsetDateTime>) -> Result<Self, Self::Error> {
+        Self::new(range.start, range.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::{
+        macros::{datetime, offset, time},
+        Date,
+    };
+
+    use super::*;
+
+    #[test]
+    fn windows_are_normalised_to_utc() {
+        let window = TimeWindow::new(
+            datetime!(2024-01-01 12:00 +05:30),
+            datetime!(2024-01-02 12:00 -08:00),
+        )
+        .unwrap();
+        assert_eq!(window.start().offset(), UtcOffset::UTC);
+        assert_eq!(window.start(), datetime!(2024-01-01 06:30 UTC));
+        assert_eq!(window.end(), datetime!(2024-01-02 20:00 UTC));
+        assert_eq!(
+            window.duration(),
+            Duration::hours(37) + Duration::minutes(30)
+        );
+
+        let mut url = Url::parse("https://example.com/search").unwrap();
+        window.append_to(&mut url);
+        assert_eq!(
+            url.query(),
+            Some("start=2024-01-01T06%3A30%3A00.000000000Z&end=2024-01-02T20%3A00%3A00.000000000Z")
+        );
+    }
+
+    #[test]
+    fn start_must_be_before_end() {
+        let instant = datetime!(2024-01-01 12:00 UTC);
+        let error = TimeWindow::new(instant, instant).unwrap_err();
+        assert!(matches!(error, Error::InvalidTimeWindow { .. }));
+
+        // The same instant in different offsets is still empty
+        let error = TimeWindow::new(instant, datetime!(2024-01-01 05:00 -07:00)).unwrap_err();
+        assert!(matches!(error, Error::InvalidTimeWindow { .. }));
+
+        assert!(TimeWindow::try_from(instant..instant + Duration::SECOND).is_ok());
+    }
+
+    #[test]
+    fn instants_beyond_utc_are_rejected() {
+        let start = datetime!(2024-01-01 0:00 UTC);
+        let end = Date::MAX
+            .with_time(time!(23:00))
+            .assume_offset(offset!(-05:00));
+        assert!(matches!(
+            TimeWindow::new(start, end),
+            Err(Error::InvalidTimeWindow { .. })
+        ));
+    }
+
+    #[test]
+    fn unformattable_instants_are_rejected() {
+        let end = datetime!(2024-01-01 0:00 UTC);
+        let start = datetime!(-0005-01-01 0:00 UTC);
+        assert!(matches!(
+            TimeWindow::new(start, end),
+            Err(Error::TimeFormatError(_))
+        ));
+    }
+}
@@ -4,7 +4,7 @@ use std::{collections::HashMap, path::Path};
 
 use freedom_api::Client;
 use freedom_config::Config;
-use httpmock::{prelude::*, Mock};
+use httpmock::{prelude::*, Method, Mock};
 use url::Url;
 
 pub type TestResult = std::result::Result<(), Box<dyn std::error::Error + 'static + Send + Sync>>;
@@ -45,7 +45,7 @@ impl TestingEnv {
         path: &str,
         query: Vec<(&str, &str)>,
         file: impl AsRef<Path>,
-    ) -> Mock {
+    ) -> Mock<'_> {
         let file = std::fs::read(file).unwrap();
         let file = String::from_utf8(file).unwrap();
         let file = file.replace("localhost:8080", &format!("localhost:{}", self.port()));
@@ -61,6 +61,25 @@ impl TestingEnv {
                 .body(file);
         })
     }
+
+    pub fn respond_json_from_file(
+        &self,
+        method: Method,
+        path: &str,
+        status: u16,
+        file: impl AsRef<Path>,
+    ) -> Mock<'_> {
+        let file = std::fs::read(file).unwrap();
+        let file = String::from_utf8(file).unwrap();
+        let file = file.replace("localhost:8080", &format!("localhost:{}", self.port()));
+        self.mock(|when, then| {
+            when.method(method).path(path);
+
+            then.status(status)
+                .header("content-type", "application/json")
+                .body(file);
+        })
+    }
 }
 
 impl std::ops::Deref for TestingEnv {
@@ -0,0 +1,70 @@
+use bytes::Bytes;
+use freedom_api::{error::Error, prelude::*, Inner, Value};
+use reqwest::{Response, StatusCode};
+use url::Url;
+
+/// An implementor written against the required methods only
+struct ReadOnly {
+    config: Config,
+}
+
+impl Api for ReadOnly {
+    type Container<T: Value> = Inner<T>;
+
+    async fn get(&self, _url: Url) -> Result<(Bytes, StatusCode), Error> {
+        Ok((Bytes::new(), StatusCode::NOT_FOUND))
+    }
+
+    async fn delete(&self, url: Url) -> Result<Response, Error> {
+        Err(Error::UnsupportedMethod(format!("DELETE {url}")))
+    }
+
+    async fn post<S>(&self, url: Url, _msg: S) -> Result<Response, Error>
+    where
+        S: serde::Serialize + Send + Sync,
+    {
+        Err(Error::UnsupportedMethod(format!("POST {url}")))
+    }
+
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn config_mut(&mut self) -> &mut Config {
+        &mut self.config
+    }
+}
+
+#[tokio::test]
+async fn updates_are_unsupported_by_default() {
+    let config = Config::builder()
+        .environment(Test)
+        .key("foo")
+        .secret("bar")
+        .build()
+        .unwrap();
+    let client = ReadOnly { config };
+
+    let error = client
+        .update_satellite(710)
+        .description("FooBar 6 Demo Satellite")
+        .send()
+        .await
+        .unwrap_err();
+    assert_eq!(error, Error::UnsupportedMethod(String::from("PATCH")));
+}
+
+#[tokio::test]
+async fn puts_are_unsupported_by_default() {
+    let config = Config::builder()
+        .environment(Test)
+        .key("foo")
+        .secret("bar")
+        .build()
+        .unwrap();
+    let client = ReadOnly { config };
+
+    let url = client.path_to_url("satellites/710");
+    let error = client.put(url, serde_json::json!({})).await.unwrap_err();
+    assert_eq!(error, Error::UnsupportedMethod(String::from("PUT")));
+}
@@ -0,0 +1,728 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use freedom_api::{
+    error::Error,
+    prelude::*,
+    testing::{FakeFreedom, Resource},
+};
+use futures::{Stream, StreamExt, TryStreamExt};
+use time::macros::datetime;
+
+type TestResult = Result<(), Box<dyn std::error::Error>>;
+
+fn fixture(path: &str) -> String {
+    std::fs::read_to_string(format!("resources/{path}")).unwrap()
+}
+
+/// A fake seeded with a band, site, and site configuration, from which task requests can be made
+fn seeded() -> FakeFreedom {
+    let fake = FakeFreedom::new();
+    fake.load_fixture(&fixture("satellite_bands_find_one_1573.json"))
+        .unwrap();
+    fake.load_fixture(&fixture("satellite_configurations_find_one_810.json"))
+        .unwrap();
+    fake.load_fixture(&fixture("satellite_find_one_710.json"))
+        .unwrap();
+    fake.load_fixture(&fixture("sites_find_one_14.json"))
+        .unwrap();
+    fake.load_fixture(
+        r#"{
+            "created": "2024-01-01T00:00:00Z",
+            "name": "LOAG S-Band",
+            "configurationSeconds": 60,
+            "_links": { "self": { "href": "/api/configurations/47" } }
+        }"#,
+    )
+    .unwrap();
+    fake.relate(Resource::SiteConfiguration, 47, "site", [14]);
+
+    fake
+}
+
+#[tokio::test]
+async fn serves_fixtures_with_consistent_links() -> TestResult {
+    let fake = FakeFreedom::new();
+    let loaded = fake.load_fixture(&fixture("satellite_find_all.json"))?;
+    assert_eq!(loaded.len(), 14);
+
+    let satellites: Vec<_> = fake.get_satellites().try_collect().await?;
+    assert_eq!(satellites.len(), 14);
+
+    let satellite = fake.get_satellite_by_id(710).await?;
+    assert_eq!(satellite.name, "FooBar 6");
+    assert_eq!(satellite.get_id()?, SatelliteId::new(710));
+    assert_eq!(
+        satellite.links["configuration"],
+        fake.path_to_url("satellites/710/configuration")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn paginated_listing() -> TestResult {
+    let fake = FakeFreedom::new();
+    fake.load_fixture(&fixture("satellite_find_all.json"))?;
+
+    let url = fake.path_to_url("satellites?size=5");
+    let ids: Vec<_> = fake
+        .get_paginated::<Satellite>(url)
+        .map(|satellite| satellite.unwrap().get_id().unwrap())
+        .collect()
+        .await;
+    assert_eq!(ids.len(), 14);
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let url = fake.path_to_url("satellites?size=5&page=2");
+    let page: serde_json::Value = fake.get_json_map(url).await?;
+    assert_eq!(page["page"]["totalPages"], 3);
+    assert_eq!(page["_embedded"]["satellites"].as_array().unwrap().len(), 4);
+    assert!(page["_links"].get("next").is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn paginated_listing_with_options() -> TestResult {
+    let fake = FakeFreedom::new();
+    fake.load_fixture(&fixture("satellite_find_all.json"))?;
+
+    let options = PaginationOptions::new()
+        .size(5)
+        .start_page(1)
+        .sort("name", Direction::Descending);
+    let mut satellites = fake.get_satellites().with_options(options);
+    assert_eq!(satellites.total_elements(), None);
+    assert_eq!(satellites.size_hint(), (0, None));
+
+    let first = satellites.next().await.unwrap()?;
+    assert_eq!(satellites.total_elements(), Some(14));
+    assert_eq!(satellites.total_pages(), Some(3));
+    assert_eq!(satellites.size_hint(), (8, None));
+
+    let mut names = vec![first.name.clone()];
+    while let Some(satellite) = satellites.try_next().await? {
+        names.push(satellite.name.clone());
+    }
+    assert_eq!(names.len(), 9);
+    assert!(names.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn created_resources_are_linked() -> TestResult {
+    let fake = seeded();
+
+    let config = fake
+        .new_satellite_configuration()
+        .name("Created configuration")
+        .band_ids([BandId::new(1573)])
+        .send()
+        .await?;
+    let config_id = config.get_id()?;
+
+    let satellite = fake
+        .new_satellite()
+        .name("Created satellite")
+        .satellite_configuration_id(config_id)
+        .norad_id(3600)
+        .send()
+        .await?;
+    assert_eq!(satellite.description, "");
+    assert_eq!(satellite.account_name, "ATLAS");
+
+    let found = fake.get_satellite_by_name("Created satellite").await?;
+    assert_eq!(found.get_id()?, satellite.get_id()?);
+
+    let url = found.links["configuration"].clone();
+    let linked: SatelliteConfiguration = fake.get_json_map(url).await?;
+    assert_eq!(linked.name, "Created configuration");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn navigates_relations() -> TestResult {
+    let fake = seeded();
+    fake.load_fixture(&fixture("accounts.json"))?;
+    fake.relate(Resource::Satellite, 710, "account", [34]);
+    fake.relate(Resource::Satellite, 710, "configuration", [812]);
+    fake.relate(Resource::SatelliteConfiguration, 812, "account", [34]);
+    fake.relate(Resource::SatelliteConfiguration, 812, "bandDetails", [1573]);
+    fake.relate(Resource::Band, 1573, "account", [34]);
+
+    let satellite = fake.get_satellite_by_id(710).await?;
+    assert_eq!(satellite.get_account(&fake).await?.name, "ABC Space");
+
+    let configuration = satellite.get_configuration(&fake).await?;
+    assert_eq!(configuration.get_id()?, SatelliteConfigurationId::new(812));
+    assert_eq!(configuration.get_account(&fake).await?.name, "ABC Space");
+
+    let bands = configuration.get_band_details(&fake).await?;
+    assert_eq!(bands.len(), 1);
+    assert_eq!(bands[0].get_id()?, BandId::new(1573));
+    assert_eq!(bands[0].get_account(&fake).await?.name, "ABC Space");
+
+    let site = fake.get_site_by_id(14).await?;
+    let configurations = site.get_configurations(&fake).await?;
+    assert_eq!(configurations.len(), 1);
+    assert_eq!(configurations[0].get_id()?, SiteConfigurationId::new(47));
+    assert_eq!(configurations[0].get_site(&fake).await?.name, site.name);
+
+    let request = fake
+        .new_task_request()
+        .test_task("test_file.bin")
+        .target_time_utc(datetime!(2030-01-01 12:00 UTC))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(14)
+        .site_configuration_id(47)
+        .band_ids([1573])
+        .send()
+        .await?;
+    assert_eq!(request.get_site(&fake).await?.name, site.name);
+    assert_eq!(request.get_satellite(&fake).await?.name, satellite.name);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follows_arbitrary_links() -> TestResult {
+    let fake = seeded();
+    fake.load_fixture(&fixture("accounts.json"))?;
+    fake.relate(Resource::SatelliteConfiguration, 812, "bandDetails", [1573]);
+    fake.relate(Resource::Band, 1573, "account", [34]);
+
+    let band = fake.get_satellite_band_by_id(1573).await?;
+    let account = band.follow::<Account, _>("account", &fake).await?;
+    assert_eq!(account.name, "ABC Space");
+    let raw = band
+        .follow::<serde_json::Value, _>("account", &fake)
+        .await?;
+    assert_eq!(raw["name"], "ABC Space");
+
+    let configuration = fake.get_satellite_configuration_by_id(812).await?;
+    let bands = configuration
+        .follow_embedded::<Band, _>("bandDetails", &fake)
+        .await?;
+    assert_eq!(bands[0].get_id()?, BandId::new(1573));
+
+    let site = fake.get_site_by_id(14).await?;
+    let configurations: Vec<_> = site
+        .follow_paginated::<SiteConfiguration, _>("configurations", &fake)
+        .try_collect()
+        .await?;
+    assert_eq!(configurations.len(), 1);
+
+    let rel = String::from("account");
+    let missing = site.follow::<Account, _>(&rel, &fake).await;
+    assert!(matches!(missing, Err(Error::MissingUri(missing)) if missing == rel));
+    let mut missing = site.follow_paginated::<Account, _>(&format!("{rel}s"), &fake);
+    assert!(matches!(
+        missing.next().await,
+        Some(Err(Error::MissingUri(missing))) if missing == "accounts"
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolves_task_requests() -> TestResult {
+    let fake = seeded();
+
+    for target in [
+        datetime!(2030-01-01 12:00 UTC),
+        datetime!(2030-06-01 12:00 UTC),
+    ] {
+        fake.new_task_request()
+            .test_task("test_file.bin")
+            .target_time_utc(target)
+            .task_duration(120)
+            .satellite_id(710)
+            .site_id(14)
+            .site_configuration_id(47)
+            .band_ids([1573])
+            .send()
+            .await?;
+    }
+
+    let resolver = TaskRequestResolver::new(&fake).max_concurrent(2);
+    let resolved: Vec<_> = resolver
+        .resolve_all(fake.get_requests())
+        .try_collect()
+        .await?;
+    assert_eq!(resolved.len(), 2);
+
+    let (first, second) = (&resolved[0], &resolved[1]);
+    assert_eq!(first.site.name, "LOAG");
+    assert_eq!(first.satellite.name, "FooBar 6");
+    assert_eq!(first.configuration.name, "LOAG S-Band");
+    assert_eq!(first.target_bands[0].get_id()?, BandId::new(1573));
+    assert!(first.task.is_none());
+    assert!(std::sync::Arc::ptr_eq(&first.site, &second.site));
+    assert!(std::sync::Arc::ptr_eq(
+        &first.target_bands[0],
+        &second.target_bands[0]
+    ));
+
+    let single = first.request.resolve(&fake).await?;
+    assert_eq!(single.site.name, "LOAG");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn resolves_several_task_requests_at_once() -> TestResult {
+    let fake = seeded();
+
+    for day in 1..=3 {
+        fake.new_task_request()
+            .test_task("test_file.bin")
+            .target_time_utc(datetime!(2030-01-01 12:00 UTC) + time::Duration::days(day))
+            .task_duration(120)
+            .satellite_id(710)
+            .site_id(14)
+            .site_configuration_id(47)
+            .band_ids([1573])
+            .send()
+            .await?;
+    }
+
+    let pulled = AtomicUsize::new(0);
+    let requests = fake.get_requests().inspect(|_| {
+        pulled.fetch_add(1, Ordering::Relaxed);
+    });
+    let resolver = TaskRequestResolver::new(&fake).max_concurrent(2);
+    let mut resolved = std::pin::pin!(resolver.resolve_all(requests));
+
+    resolved.next().await.unwrap()?;
+    assert_eq!(pulled.load(Ordering::Relaxed), 2);
+    let rest: Vec<_> = resolved.try_collect().await?;
+    assert_eq!(rest.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn missing_references_are_rejected() -> TestResult {
+    let fake = seeded();
+
+    let error = fake
+        .new_satellite()
+        .name("Orphan")
+        .satellite_configuration_id(999)
+        .norad_id(3600)
+        .send()
+        .await
+        .unwrap_err();
+
+    let Error::Validation { field_errors, .. } = error else {
+        panic!("Expected a validation error, found {error:?}");
+    };
+    assert_eq!(field_errors[0].field, "configuration");
+    assert_eq!(fake.len(Resource::Satellite), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn delete_removes_resource() -> TestResult {
+    let fake = seeded();
+
+    let response = fake.delete_satellite(710).await?;
+    assert_eq!(response.status(), 204);
+
+    let error = fake.get_satellite_by_id(710).await.unwrap_err();
+    assert!(matches!(error, Error::NotFound { .. }));
+
+    let response = fake.delete_satellite(710).await?;
+    assert_eq!(response.status(), 404);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn patch_updates_fields() -> TestResult {
+    let fake = seeded();
+
+    let updated = fake
+        .update_satellite(710)
+        .description("Updated")
+        .send()
+        .await?;
+    assert_eq!(updated.description, "Updated");
+
+    let fetched = fake.get_satellite_by_id(710).await?;
+    assert_eq!(fetched.description, "Updated");
+    assert_eq!(fetched.name, "FooBar 6");
+
+    Ok(())
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn task_request_searches() -> TestResult {
+    let fake = seeded();
+
+    for target in [
+        datetime!(2030-01-01 12:00 UTC),
+        datetime!(2030-06-01 12:00 UTC),
+    ] {
+        fake.new_task_request()
+            .test_task("test_file.bin")
+            .target_time_utc(target)
+            .task_duration(120)
+            .satellite_id(710)
+            .site_id(14)
+            .site_configuration_id(47)
+            .band_ids([1573])
+            .send()
+            .await?;
+    }
+
+    let requests = fake
+        .get_requests_by_target_date_between(TimeWindow::new(
+            datetime!(2029-12-01 00:00 UTC),
+            datetime!(2030-02-01 00:00 UTC),
+        )?)
+        .await?;
+    assert_eq!(requests.len(), 1);
+
+    let received: Vec<_> = fake
+        .get_requests_by_status(TaskStatusType::Received)?
+        .try_collect()
+        .await?;
+    assert_eq!(received.len(), 2);
+
+    let by_satellite: Vec<_> = fake
+        .get_requests_by_satellite_name("FooBar 6")
+        .try_collect()
+        .await?;
+    assert_eq!(by_satellite.len(), 2);
+
+    let by_other: Vec<_> = fake
+        .get_requests_by_satellite_name("Other")
+        .try_collect()
+        .await?;
+    assert!(by_other.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn task_request_queries() -> TestResult {
+    let fake = seeded();
+    fake.load_fixture(&fixture("accounts.json"))?;
+    fake.relate(Resource::Satellite, 710, "account", [34]);
+
+    for target in [
+        datetime!(2030-01-01 12:00 UTC),
+        datetime!(2030-06-01 12:00 UTC),
+    ] {
+        fake.new_task_request()
+            .test_task("test_file.bin")
+            .target_time_utc(target)
+            .task_duration(120)
+            .satellite_id(710)
+            .site_id(14)
+            .site_configuration_id(47)
+            .band_ids([1573])
+            .send()
+            .await?;
+    }
+    let january = TimeWindow::new(
+        datetime!(2029-12-01 00:00 UTC),
+        datetime!(2030-02-01 00:00 UTC),
+    )?;
+
+    let query = RequestQuery::new()
+        .account_id(34)
+        .status(TaskStatusType::Received)
+        .target_date_between(january);
+    let requests: Vec<_> = fake.get_requests_matching(&query)?.try_collect().await?;
+    assert_eq!(requests.len(), 1);
+
+    let query = RequestQuery::new()
+        .account_id(35)
+        .target_date_between(january);
+    let requests: Vec<_> = fake.get_requests_matching(&query)?.try_collect().await?;
+    assert!(requests.is_empty());
+
+    let query = RequestQuery::new()
+        .configuration_id(47)
+        .satellite_names(["Other", "FooBar 6"])
+        .target_date_between(TimeWindow::new(
+            january.start(),
+            datetime!(2031-01-01 00:00 UTC),
+        )?);
+    let requests: Vec<_> = fake.get_requests_matching(&query)?.try_collect().await?;
+    assert_eq!(requests.len(), 2);
+
+    let query = RequestQuery::new()
+        .satellite_name("FooBar 6")
+        .task_type(TaskType::Test);
+    assert!(matches!(
+        fake.get_requests_matching(&query),
+        Err(Error::UnsupportedQuery(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn tasks_overlapping() -> TestResult {
+    let fake = FakeFreedom::new();
+    fake.load_fixture(&fixture("tasks_1/page_1.json"))?;
+    fake.load_fixture(&fixture("tasks_1/page_2.json"))?;
+
+    let query = TaskQuery::new().pass_overlapping(TimeWindow::new(
+        datetime!(2022-05-26 05:00 UTC),
+        datetime!(2022-06-03 06:05 UTC),
+    )?);
+    let tasks: Vec<_> = fake.get_tasks_matching(&query)?.try_collect().await?;
+    assert_eq!(tasks.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn unknown_routes_are_not_found() -> TestResult {
+    let fake = FakeFreedom::new();
+
+    let error = fake
+        .get_json_map::<serde_json::Value>(fake.path_to_url("unknown/1"))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(reqwest::StatusCode::NOT_FOUND));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn downloads_resume_from_offset() -> TestResult {
+    let fake = FakeFreedom::new();
+    fake.insert_file(42, "data.bin", &b"0123456789"[..]);
+
+    let mut out = b"0123".to_vec();
+    let size = fake
+        .download_file_by_task_id_and_name(42, "data.bin")
+        .resume_from(4)
+        .to_writer(&mut out)
+        .await?;
+    assert_eq!(size, 10);
+    assert_eq!(out, b"0123456789");
+
+    let size = fake
+        .download_file_by_task_id_and_name(42, "data.bin")
+        .resume_from(10)
+        .to_writer(&mut out)
+        .await?;
+    assert_eq!(size, 10);
+    assert_eq!(out, b"0123456789");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn watches_requests_until_terminal() -> TestResult {
+    let fake = seeded();
+    let (_, task_id) = fake.load_fixture(&fixture("tasks_1/page_1.json"))?[0];
+
+    let request = fake
+        .new_task_request()
+        .test_task("test_file.bin")
+        .target_time_utc(datetime!(2030-01-01 12:00 UTC))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(14)
+        .site_configuration_id(47)
+        .band_ids([1573])
+        .send()
+        .await?;
+    let request_id = request.get_id()?;
+    let url = fake.path_to_url(format!("requests/{request_id}"));
+    let set_status = |status: &str, created: &str| {
+        let change = serde_json::json!({ "created": created, "status": status, "reason": "" });
+        serde_json::json!({ "statusChanges": [change], "latestStatusChange": change })
+    };
+
+    let mut events = fake
+        .watch_request(request_id)
+        .poll_interval(std::time::Duration::from_millis(1));
+
+    let Some(RequestEvent::StatusChanged(change)) = events.try_next().await? else {
+        panic!("Expected the initial status");
+    };
+    assert_eq!(change.previous, None);
+    assert_eq!(change.status, TaskStatusType::Received);
+
+    fake.patch(url.clone(), set_status("SCHEDULED", "2100-01-01T00:00:00Z"))
+        .await?;
+
+    let Some(RequestEvent::StatusChanged(change)) = events.try_next().await? else {
+        panic!("Expected the request to be scheduled");
+    };
+    assert_eq!(change.previous, Some(TaskStatusType::Received));
+    assert_eq!(change.status, TaskStatusType::Scheduled);
+    assert_eq!(change.at, datetime!(2100-01-01 00:00 UTC));
+
+    fake.relate(Resource::Task, task_id, "taskRequest", [request_id.get()]);
+
+    let Some(RequestEvent::Task(task)) = events.try_next().await? else {
+        panic!("Expected the scheduled task");
+    };
+    assert_eq!(task.get_id()?, TaskId::new(task_id));
+
+    fake.patch(url, set_status("COMPLETED", "2100-01-02T00:00:00Z"))
+        .await?;
+
+    let Some(RequestEvent::StatusChanged(change)) = events.try_next().await? else {
+        panic!("Expected the request to be completed");
+    };
+    assert_eq!(change.previous, Some(TaskStatusType::Scheduled));
+    assert_eq!(change.status, TaskStatusType::Completed);
+    assert!(events.try_next().await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn task_requests_are_validated_against_the_server() -> TestResult {
+    let fake = seeded();
+    fake.relate(Resource::Satellite, 710, "configuration", [812]);
+    fake.relate(Resource::SatelliteConfiguration, 812, "bandDetails", [1573]);
+
+    fake.new_task_request()
+        .test_task("test_file.bin")
+        .target_time_utc(datetime!(2030-01-01 12:00 UTC))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(14)
+        .site_configuration_id(47)
+        .band_ids([1573])
+        .validate_with_server()
+        .await?;
+
+    let error = fake
+        .new_task_request()
+        .test_task("test_file.bin")
+        .target_time_utc(datetime!(2030-01-01 12:00 UTC))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(15)
+        .site_configuration_id(48)
+        .band_ids([1573, 1574])
+        .validate_with_server()
+        .await
+        .unwrap_err();
+
+    let Error::InvalidRequest(problems) = error else {
+        panic!("Expected an invalid request, found {error:?}");
+    };
+    let fields: Vec<_> = problems
+        .iter()
+        .map(|problem| problem.field.as_str())
+        .collect();
+    assert_eq!(fields, ["targetBands", "configuration"]);
+    assert_eq!(fake.len(Resource::TaskRequest), 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn batches_report_each_request() -> TestResult {
+    let fake = seeded();
+    let request = |configuration: i32| {
+        fake.new_task_request()
+            .test_task("test_file.bin")
+            .target_time_utc(datetime!(2030-01-01 12:00 UTC))
+            .task_duration(120)
+            .satellite_id(710)
+            .site_id(14)
+            .site_configuration_id(configuration)
+            .band_ids([1573])
+    };
+
+    let mut results: Vec<_> = TaskRequestBatch::new(&fake)
+        .max_concurrent(2)
+        .requests([request(47), request(999)])
+        .payload(request(47).into_payload())
+        .submit()
+        .collect()
+        .await;
+    results.sort_by_key(|result| result.index);
+    assert!(results[0].result.is_ok());
+    assert!(matches!(results[1].result, Err(Error::Validation { .. })));
+    assert!(results[2].result.is_ok());
+    assert_eq!(fake.len(Resource::TaskRequest), 2);
+
+    let report = TaskRequestBatch::new(&fake)
+        .max_concurrent(1)
+        .stop_on_failure(true)
+        .requests([request(47), request(999), request(47)])
+        .submit()
+        .into_report()
+        .await;
+    assert_eq!(report.total, 3);
+    assert_eq!(report.succeeded, 1);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, 1);
+    assert_eq!(report.not_submitted(), 1);
+    assert_eq!(fake.len(Resource::TaskRequest), 3);
+
+    Ok(())
+}
+
+#[cfg(feature = "plans")]
+#[tokio::test]
+async fn plans_resolve_names_to_builders() -> TestResult {
+    use freedom_api::plan::{Plan, PlanFormat};
+
+    let fake = seeded();
+    let csv = "\
+type,satellite,site,configuration,bands,targetDate,duration,minimumDuration,hoursOfFlex,testFile
+BEFORE,FooBar 6,LOAG,LOAG S-Band,FooBarBand1,2030-01-01T12:00:00Z,600,300,2,
+TEST,#710,#14,#47,#1573,2030-01-02T12:00:00Z,120,,,test_file.bin
+";
+    let plan = Plan::from_reader(csv.as_bytes(), PlanFormat::Csv)?;
+
+    let builders = plan.resolve(&fake).await?;
+    assert_eq!(builders.len(), 2);
+    let (first, second) = (builders[0].dry_run(), builders[1].dry_run());
+    assert_eq!(first["type"], "BEFORE");
+    assert_eq!(first["hoursOfFlex"], 2);
+    assert_eq!(first["minimumDuration"], 300);
+    assert_eq!(first["satellite"], second["satellite"]);
+    assert_eq!(first["configuration"], second["configuration"]);
+    assert_eq!(first["targetBands"], second["targetBands"]);
+
+    for builder in builders {
+        builder.send().await?;
+    }
+    assert_eq!(fake.len(Resource::TaskRequest), 2);
+
+    let csv = "\
+type,satellite,site,configuration,bands,targetDate,duration,minimumDuration,hoursOfFlex,testFile
+AROUND,Nothing,LOAG,Missing,FooBarBand1;Nope,2030-01-01T12:00:00Z,600,,,
+";
+    let plan = Plan::from_reader(csv.as_bytes(), PlanFormat::Csv)?;
+    let Err(Error::InvalidRequest(problems)) = plan.resolve(&fake).await else {
+        panic!("Expected the plan to be invalid");
+    };
+    let fields: Vec<_> = problems
+        .iter()
+        .map(|problem| problem.field.as_str())
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            "requests[0].bands",
+            "requests[0].satellite",
+            "requests[0].configuration",
+            "requests[0].hoursOfFlex",
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::{TestResult, TestingEnv};
+use freedom_api::prelude::*;
+use httpmock::Method::POST;
+
+#[tokio::test]
+async fn overrides_refer_to_satellite_configurations() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(POST)
+            .path("/overrides")
+            .body_contains("/satellites/710\"")
+            .body_contains("/satellite_configurations/812\"");
+        then.status(201)
+            .header("content-type", "application/json")
+            .body(r#"{ "name": "Bigger downlink" }"#);
+    });
+    let client = Client::from(env);
+
+    let created = client
+        .new_override()
+        .name("Bigger downlink")
+        .satellite_id(710)
+        .satellite_configuration_id(812)
+        .add_property("site.hardware.modulator.bitRate", 8096)
+        .send()
+        .await?;
+    assert_eq!(created.into_inner()["name"], "Bigger downlink");
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ mod common;
 use std::collections::HashMap;
 
 use common::{TestResult, TestingEnv};
-use freedom_api::prelude::*;
+use freedom_api::{error::Error, prelude::*};
 use freedom_models::band::{Band, BandType, IoConfiguration, IoHardware};
 use futures::StreamExt;
 use time::macros::datetime;
@@ -61,6 +61,62 @@ async fn find_all_bands() -> TestResult {
     Ok(())
 }
 
+#[tokio::test]
+async fn find_all_bands_without_page_metadata() -> TestResult {
+    let env = TestingEnv::new();
+
+    let file = std::fs::read_to_string("resources/satellite_bands_find_all.json")?;
+    let mut body: serde_json::Value = serde_json::from_str(&file)?;
+    body.as_object_mut().unwrap().remove("page");
+    env.mock(|when, then| {
+        when.method(httpmock::Method::GET).path("/satellite_bands");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(body);
+    });
+    let client = Client::from(env);
+
+    let mut stream = client.get_satellite_bands();
+    let first = stream.next().await.unwrap()?;
+    assert_eq!(first.name, "FooBarBand1");
+    assert_eq!(stream.total_elements(), Some(6));
+    assert_eq!(stream.total_pages(), Some(1));
+    assert_eq!(stream.count().await, 5);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn find_all_bands_refuses_cross_origin_next_page() -> TestResult {
+    let env = TestingEnv::new();
+    let elsewhere = httpmock::MockServer::start();
+    let next_page = elsewhere.mock(|when, then| {
+        when.method(httpmock::Method::GET).path("/satellite_bands");
+        then.status(200);
+    });
+
+    let file = std::fs::read_to_string("resources/satellite_bands_find_all.json")?;
+    let file = file.replace("localhost:8080", &format!("localhost:{}", env.port()));
+    let mut body: serde_json::Value = serde_json::from_str(&file)?;
+    body["_links"]["next"] =
+        serde_json::json!({ "href": elsewhere.url("/satellite_bands?page=1") });
+    env.mock(|when, then| {
+        when.method(httpmock::Method::GET).path("/satellite_bands");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(body);
+    });
+    let client = Client::from(env);
+
+    let results = client.get_satellite_bands().collect::<Vec<_>>().await;
+    assert_eq!(results.len(), 7);
+    assert!(results[..6].iter().all(Result::is_ok));
+    assert!(matches!(results[6], Err(Error::CrossOrigin(_))));
+    next_page.assert_hits(0);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn find_one_band_by_id() -> TestResult {
     let env = TestingEnv::new();
@@ -99,3 +155,60 @@ async fn find_one_band_by_name() -> TestResult {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn create_band() -> TestResult {
+    let env = TestingEnv::new();
+    let band = band(&env);
+
+    env.respond_json_from_file(
+        httpmock::Method::POST,
+        "/satellite_bands",
+        201,
+        "resources/satellite_bands_find_one_1573.json",
+    );
+    let client = Client::from(env);
+
+    let created = client
+        .new_band_details()
+        .name("FooBarBand1")
+        .band_type(BandType::Receive)
+        .frequency(1000.0)
+        .default_band_width(1000.0)
+        .io_hardware(IoHardware::Modem)
+        .send()
+        .await?;
+    assert_eq!(created.into_inner(), band);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn create_band_rejected() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(httpmock::Method::POST).path("/satellite_bands");
+        then.status(400)
+            .header("content-type", "application/json")
+            .body(r#"{"errors":[{"property":"frequencyMghz","message":"must be positive"}]}"#);
+    });
+    let client = Client::from(env);
+
+    let error = client
+        .new_band_details()
+        .name("FooBarBand1")
+        .band_type(BandType::Receive)
+        .frequency(-1.0)
+        .default_band_width(1000.0)
+        .io_hardware(IoHardware::Modem)
+        .send()
+        .await
+        .unwrap_err();
+
+    let freedom_api::error::Error::Validation { field_errors, .. } = error else {
+        panic!("Expected a validation error, found {error:?}");
+    };
+    assert_eq!(field_errors[0].field, "frequencyMghz");
+
+    Ok(())
+}
@@ -105,3 +105,150 @@ async fn find_one_satellite_by_name() -> TestResult {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn find_one_satellite_not_found() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(httpmock::Method::GET).path("/satellites/1");
+        then.status(404)
+            .header("content-type", "application/json")
+            .body(r#"{"status":404,"error":"Not Found","path":"/api/satellites/1"}"#);
+    });
+    let client = Client::from(env);
+
+    let error = client.get_satellite_by_id(1).await.unwrap_err();
+    assert!(matches!(error, freedom_api::error::Error::NotFound { .. }));
+    assert_eq!(error.freedom_message(), Some("Not Found"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn update_satellite_description() -> TestResult {
+    let env = TestingEnv::new();
+    let sat = sat(&env);
+
+    let file = std::fs::read_to_string("resources/satellite_find_one_710.json")?
+        .replace("localhost:8080", &format!("localhost:{}", env.port()));
+    env.mock(|when, then| {
+        when.method(httpmock::Method::PATCH)
+            .path("/satellites/710")
+            .json_body(serde_json::json!({ "description": "FooBar 6 Demo Satellite" }));
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(file);
+    });
+    let client = Client::from(env);
+
+    let satellite = client
+        .update_satellite(710)
+        .description("FooBar 6 Demo Satellite")
+        .send()
+        .await?;
+    assert_eq!(satellite.into_inner(), sat);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn created_satellites_are_fetched_from_their_location() -> TestResult {
+    let env = TestingEnv::new();
+    let sat = sat(&env);
+
+    env.mock(|when, then| {
+        when.method(httpmock::Method::POST)
+            .path("/satellites")
+            .header("accept", "application/json");
+        then.status(201)
+            .header("location", env.url("/satellites/710"));
+    });
+    env.get_json_from_file(
+        "/satellites/710",
+        Vec::new(),
+        "resources/satellite_find_one_710.json",
+    );
+    let client = Client::from(env);
+
+    let satellite = client
+        .new_satellite()
+        .name("FooBar 6")
+        .satellite_configuration_id(812)
+        .norad_id(3600)
+        .send()
+        .await?;
+    assert_eq!(satellite.into_inner(), sat);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn empty_creations_without_a_location_are_reported() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(httpmock::Method::POST).path("/satellites");
+        then.status(201);
+    });
+    let client = Client::from(env);
+
+    let error = client
+        .new_satellite()
+        .name("FooBar 6")
+        .satellite_configuration_id(812)
+        .norad_id(3600)
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        freedom_api::error::Error::EmptyResponse { status: 201, .. }
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_orbit_info() -> TestResult {
+    let env = TestingEnv::new();
+    let mut sat = sat(&env);
+    let link = url::Url::parse(&env.url("/api/satellites/710/orbitInfo"))?;
+    sat.links.insert(String::from("orbitInfo"), link);
+
+    let body = serde_json::json!({ "orbitInfoType": "AUTO_TLE" });
+    env.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/api/satellites/710/orbitInfo");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(body.clone());
+    });
+    let client = Client::from(env);
+
+    let orbit_info = sat.get_orbit_info(&client).await?.into_inner();
+    assert_eq!(orbit_info, body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_upcoming_visibilities() -> TestResult {
+    let env = TestingEnv::new();
+    let mut sat = sat(&env);
+    let link = url::Url::parse(&env.url("/api/satellites/710/upcomingVisibilities"))?;
+    sat.links.insert(String::from("upcomingVisibilities"), link);
+
+    let body = serde_json::json!([{ "siteId": 14, "start": "2022-05-26T04:57:26Z" }]);
+    env.mock(|when, then| {
+        when.method(httpmock::Method::GET)
+            .path("/api/satellites/710/upcomingVisibilities");
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(body.clone());
+    });
+    let client = Client::from(env);
+
+    let visibilities = sat.get_upcoming_visibilities(&client).await?.into_inner();
+    assert_eq!(visibilities, body);
+
+    Ok(())
+}
@@ -86,3 +86,23 @@ async fn find_one_site_by_name() -> TestResult {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn find_one_site_by_adversarial_name() -> TestResult {
+    for name in ["A&B", "Site #2", "North Pole", "1+1", "100%", "a=b;c"] {
+        let env = TestingEnv::new();
+        let site = site(&env);
+
+        env.get_json_from_file(
+            "/sites/search/findOneByName",
+            vec![("name", name)],
+            "resources/sites_find_one_14.json",
+        );
+        let client = Client::from(env);
+
+        let fetched = client.get_site_by_name(name).await?.into_inner();
+        assert_eq!(fetched, site);
+    }
+
+    Ok(())
+}
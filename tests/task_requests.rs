@@ -0,0 +1,239 @@
+mod common;
+
+use common::{TestResult, TestingEnv};
+use freedom_api::{error::Error, prelude::*};
+use freedom_models::task::TaskStatusType;
+use futures::{StreamExt, TryStreamExt};
+use httpmock::Method::GET;
+use time::macros::datetime;
+
+const EMPTY: &str = r#"{ "_embedded": { "taskRequests": [] }, "_links": {} }"#;
+
+const EMPTY_PAGE: &str = r#"{
+    "_embedded": { "taskRequests": [] },
+    "_links": {},
+    "page": { "size": 20, "totalElements": 0, "totalPages": 0, "number": 0 }
+}"#;
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn requests_by_target_date_are_unwrapped() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(GET)
+            .path("/requests/search/findAllByTargetDateBetween");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(EMPTY);
+    });
+    let client = Client::from(env);
+
+    let requests = client
+        .get_requests_by_target_date_between(TimeWindow::new(
+            datetime!(2024-01-01 00:00 UTC),
+            datetime!(2024-01-02 00:00 UTC),
+        )?)
+        .await?;
+    assert!(requests.into_inner().is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn requests_by_status_take_typed_statuses() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(GET)
+            .path("/requests/search/findByStatus")
+            .query_param("status", "RECEIVED");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(EMPTY_PAGE);
+    });
+    let client = Client::from(env);
+
+    let requests: Vec<_> = client
+        .get_requests_by_status(TaskStatusType::Received)?
+        .try_collect()
+        .await?;
+    assert!(requests.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn timestamps_with_offsets_are_sent_in_utc() -> TestResult {
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(GET)
+            .path("/requests/search/findAllByTargetDateBetween")
+            .query_param("start", "2024-01-01T06:30:00.000000000Z")
+            .query_param("end", "2024-01-02T20:00:00.000000000Z");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(EMPTY);
+    });
+    let client = Client::from(env);
+
+    let requests = client
+        .get_requests_by_target_date_between(TimeWindow::new(
+            datetime!(2024-01-01 12:00 +05:30),
+            datetime!(2024-01-02 12:00 -08:00),
+        )?)
+        .await?;
+    assert!(requests.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn adversarial_satellite_names_are_encoded() -> TestResult {
+    let name = "Foo & Bar #1+2";
+    let env = TestingEnv::new();
+    env.mock(|when, then| {
+        when.method(GET)
+            .path("/requests/search/findAllBySatelliteNameAndTargetDateBetween")
+            .query_param("name", name)
+            .query_param("start", "2023-12-31T23:00:00.000000000Z")
+            .query_param("end", "2024-01-01T23:00:00.000000000Z");
+        then.status(200)
+            .header("content-type", "application/json")
+            .body(EMPTY);
+    });
+    let client = Client::from(env);
+
+    let query = RequestQuery::new()
+        .satellite_name(name)
+        .target_date_between(TimeWindow::new(
+            datetime!(2024-01-01 0:00 +01:00),
+            datetime!(2024-01-02 0:00 +01:00),
+        )?);
+    let requests = client
+        .get_requests_matching(&query)?
+        .collect::<Vec<_>>()
+        .await;
+    assert!(requests.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dry_run_produces_the_body() -> TestResult {
+    let client = Client::from(TestingEnv::new());
+
+    let request = client
+        .new_task_request()
+        .flex_task_after(4)
+        .target_time_utc(datetime!(2030-01-01 05:00 -07:00))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(14)
+        .site_configuration_id(47)
+        .band_ids([1573])
+        .task_minimum_duration(60);
+    request.validate()?;
+
+    let body = request.dry_run();
+    assert_eq!(body["type"], "AFTER");
+    assert_eq!(body["targetDate"], "2030-01-01T12:00:00Z");
+    assert_eq!(body["duration"], 120);
+    assert_eq!(body["minimumDuration"], 60);
+    assert_eq!(body["hoursOfFlex"], 4);
+    assert!(body["satellite"]
+        .as_str()
+        .unwrap()
+        .ends_with("/satellites/710"));
+    assert!(body["targetBands"][0]
+        .as_str()
+        .unwrap()
+        .ends_with("/satellite_bands/1573"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn payloads_round_trip_through_json() -> TestResult {
+    let client = Client::from(TestingEnv::new());
+
+    let payload = client
+        .new_task_request()
+        .test_task("test_file.bin")
+        .target_time_utc(datetime!(2030-01-01 12:00 UTC))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(14)
+        .site_configuration_id(47)
+        .band_ids([1573, 1574])
+        .into_payload();
+
+    let json = serde_json::to_string(&payload)?;
+    let read: TaskRequestPayload = serde_json::from_str(&json)?;
+    assert_eq!(read, payload);
+    assert_eq!(read.test_file(), Some("test_file.bin"));
+    assert_eq!(read.target_bands().len(), 2);
+    assert!(read.site().ends_with("/sites/14"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn validation_reports_every_problem() -> TestResult {
+    let client = Client::from(TestingEnv::new());
+
+    let error = client
+        .new_task_request()
+        .exact_task()
+        .target_time_utc(datetime!(2020-01-01 12:00 UTC))
+        .task_duration(120)
+        .satellite_id(710)
+        .site_id(14)
+        .site_configuration_id(47)
+        .band_ids(Vec::<BandId>::new())
+        .task_minimum_duration(180)
+        .validate()
+        .unwrap_err();
+
+    let Error::InvalidRequest(problems) = error else {
+        panic!("Expected an invalid request, found {error:?}");
+    };
+    let fields: Vec<_> = problems
+        .iter()
+        .map(|problem| problem.field.as_str())
+        .collect();
+    assert_eq!(fields, ["minimumDuration", "targetDate", "targetBands"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn target_times_beyond_utc_are_rejected() -> TestResult {
+    let client = Client::from(TestingEnv::new());
+    let target = time::Date::MAX
+        .with_time(time::macros::time!(23:00))
+        .assume_offset(time::macros::offset!(-05:00));
+    let request = || {
+        client
+            .new_task_request()
+            .exact_task()
+            .target_time_utc(target)
+            .task_duration(120)
+            .satellite_id(710)
+            .site_id(14)
+            .site_configuration_id(47)
+            .band_ids([1573])
+    };
+
+    let Err(Error::InvalidRequest(problems)) = request().validate() else {
+        panic!("Expected an invalid request");
+    };
+    assert_eq!(problems[0].field, "targetDate");
+
+    let error = request().send().await.unwrap_err();
+    assert!(matches!(error, Error::InvalidRequest(_)));
+    let error = request().send_raw().await.unwrap_err();
+    assert!(matches!(error, Error::InvalidRequest(_)));
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::{TestResult, TestingEnv};
+use freedom_api::prelude::*;
+use serde_json::Value as JsonValue;
+
+/// The first task of the fixture, with its links pointing at the testing environment's server
+fn task(env: &TestingEnv) -> Task {
+    let file = std::fs::read_to_string("resources/tasks_1/page_1.json")
+        .unwrap()
+        .replace("http://localhost:8080", &env.base_url());
+    let mut page: JsonValue = serde_json::from_str(&file).unwrap();
+    let task = page["_embedded"]["tasks"][0].take();
+
+    serde_json::from_value(task).unwrap()
+}
+
+/// Serve the body at the path of a relation of the fixture task
+fn serve(env: &TestingEnv, path: &str, body: &JsonValue) {
+    env.mock(|when, then| {
+        when.method(httpmock::Method::GET).path(path);
+        then.status(200)
+            .header("content-type", "application/json")
+            .json_body(body.clone());
+    });
+}
+
+#[tokio::test]
+async fn follow_doppler() -> TestResult {
+    let env = TestingEnv::new();
+    let task = task(&env);
+    let body = serde_json::json!({ "points": [] });
+    serve(&env, "/api/tasks/74344/doppler", &body);
+    let client = Client::from(env);
+
+    assert_eq!(task.get_doppler(&client).await?.into_inner(), body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_file_results() -> TestResult {
+    let env = TestingEnv::new();
+    let task = task(&env);
+    let body = serde_json::json!([{ "fileName": "test_file.bin" }]);
+    serve(&env, "/downloads/tasks/74344", &body);
+    let client = Client::from(env);
+
+    assert_eq!(task.get_file_results(&client).await?.into_inner(), body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_ground_commands() -> TestResult {
+    let env = TestingEnv::new();
+    let task = task(&env);
+    let body = serde_json::json!([]);
+    serve(&env, "/api/tasks/74344/groundCommands", &body);
+    let client = Client::from(env);
+
+    assert_eq!(task.get_ground_commands(&client).await?.into_inner(), body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_metrics() -> TestResult {
+    let env = TestingEnv::new();
+    let task = task(&env);
+    let body = serde_json::json!({ "snr": [] });
+    serve(&env, "/downloads/tasks/metrics/74344", &body);
+    let client = Client::from(env);
+
+    assert_eq!(task.get_metrics(&client).await?.into_inner(), body);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn follow_visibility() -> TestResult {
+    let env = TestingEnv::new();
+    let task = task(&env);
+    let body =
+        serde_json::json!({ "start": "2022-05-26T04:57:26Z", "end": "2022-05-26T05:10:11Z" });
+    serve(&env, "/api/tasks/74344/visibility", &body);
+    let client = Client::from(env);
+
+    assert_eq!(task.get_visibility(&client).await?.into_inner(), body);
+
+    Ok(())
+}